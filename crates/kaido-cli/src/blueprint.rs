@@ -0,0 +1,290 @@
+use std::collections::BTreeSet;
+
+use kaido_core::error::{KaidoError, Result};
+use serde_json::{Map, Value};
+
+/// Where `--emit-blueprint-types` writes the interfaces, relative to the project root
+pub const BLUEPRINT_TYPES_PATH: &str = "sdk/src/blueprint-types.ts";
+
+/// TypeScript interfaces for every validator's datum and redeemer in a CIP-57 `plutus.json`.
+/// Constructor types become an interface (one constructor) or a `tag` union (several),
+/// matching the hand-written SDK types; integers are `bigint`, bytes are hex strings.
+pub fn blueprint_types(plutus_json: &str) -> Result<String> {
+    let blueprint: Value = serde_json::from_str(plutus_json)
+        .map_err(|e| invalid(format!("plutus.json is not valid JSON: {}", e)))?;
+    let empty = Map::new();
+    let definitions = blueprint
+        .get("definitions")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let validators = blueprint
+        .get("validators")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("plutus.json has no validators".to_string()))?;
+
+    let mut out = String::new();
+    out.push_str("// Datum and redeemer types read from plutus.json.\n");
+    out.push_str(
+        "// Generated by Kaido — Aiken Smart Contract Generator. Re-run after aiken build.\n",
+    );
+
+    // Named constructor types, in definition order
+    for (key, schema) in definitions {
+        if let Some(body) = constructor_type(key, schema, definitions)? {
+            out.push('\n');
+            out.push_str(&body);
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    for validator in validators {
+        let title = validator
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        // "module.validator.purpose" -> ValidatorPurpose
+        let name: String = title
+            .split('.')
+            .skip(1)
+            .map(pascal_case)
+            .collect::<Vec<_>>()
+            .concat();
+        if name.is_empty() || !seen.insert(name.clone()) {
+            continue;
+        }
+
+        let mut aliases = Vec::new();
+        for (slot, suffix) in [("datum", "Datum"), ("redeemer", "Redeemer")] {
+            // The fallback `else` handler's redeemer schema is `{}`
+            let schema = validator
+                .get(slot)
+                .and_then(|s| s.get("schema"))
+                .filter(|s| s.as_object().is_some_and(|o| !o.is_empty()));
+            if let Some(schema) = schema {
+                aliases.push(format!(
+                    "export type {}{} = {};\n",
+                    name,
+                    suffix,
+                    ts_type(schema, definitions)?
+                ));
+            }
+        }
+        if !aliases.is_empty() {
+            out.push_str(&format!("\n/** {} */\n", title));
+            out.push_str(&aliases.concat());
+        }
+    }
+
+    Ok(out)
+}
+
+fn invalid(message: String) -> KaidoError {
+    KaidoError::InvalidOption(message)
+}
+
+/// The definition a `{"$ref": "#/definitions/..."}` points at, with its key
+fn resolve<'a>(
+    reference: &str,
+    definitions: &'a Map<String, Value>,
+) -> Result<(String, &'a Value)> {
+    let key = reference
+        .strip_prefix("#/definitions/")
+        .ok_or_else(|| invalid(format!("unsupported $ref '{}'", reference)))?
+        .replace("~1", "/")
+        .replace("~0", "~");
+    let schema = definitions
+        .get(&key)
+        .ok_or_else(|| invalid(format!("$ref '{}' has no definition", reference)))?;
+    Ok((key, schema))
+}
+
+/// Interface name for a definition key: the last path segment without generic markers
+fn type_name(key: &str) -> String {
+    key.rsplit('/')
+        .next()
+        .unwrap_or(key)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+fn is_named_constructor(key: &str, schema: &Value) -> bool {
+    schema.get("anyOf").is_some() && key != "Bool" && !key.starts_with("Option")
+}
+
+/// `export interface`/`export type` for a user-defined constructor type, None for anything else
+fn constructor_type(
+    key: &str,
+    schema: &Value,
+    definitions: &Map<String, Value>,
+) -> Result<Option<String>> {
+    if !is_named_constructor(key, schema) {
+        return Ok(None);
+    }
+    let name = type_name(key);
+    let constructors = schema["anyOf"].as_array().cloned().unwrap_or_default();
+
+    if let [only] = constructors.as_slice() {
+        let fields = fields(only, definitions)?;
+        if fields.is_empty() {
+            return Ok(Some(format!("export interface {} {{}}\n", name)));
+        }
+        let mut body = format!("export interface {} {{\n", name);
+        for (field, ty) in fields {
+            body.push_str(&format!("  {}: {};\n", field, ty));
+        }
+        body.push_str("}\n");
+        return Ok(Some(body));
+    }
+
+    let mut body = format!("export type {} =\n", name);
+    let variants = constructors
+        .iter()
+        .map(|c| {
+            let tag = c.get("title").and_then(Value::as_str).unwrap_or_default();
+            let mut parts = vec![format!("tag: \"{}\"", tag)];
+            parts.extend(
+                fields(c, definitions)?
+                    .into_iter()
+                    .map(|(field, ty)| format!("{}: {}", field, ty)),
+            );
+            Ok(format!("  | {{ {} }}", parts.join("; ")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    body.push_str(&variants.join("\n"));
+    body.push_str(";\n");
+    Ok(Some(body))
+}
+
+/// (camelCase name, TS type) per constructor field; untitled fields are `field0`, `field1`, ...
+fn fields(constructor: &Value, definitions: &Map<String, Value>) -> Result<Vec<(String, String)>> {
+    constructor
+        .get("fields")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let name = match field.get("title").and_then(Value::as_str) {
+                Some(title) => camel_case(title),
+                None => format!("field{}", i),
+            };
+            Ok((name, ts_type(field, definitions)?))
+        })
+        .collect()
+}
+
+/// TypeScript type for a schema (inline, or a `$ref` to a definition)
+fn ts_type(schema: &Value, definitions: &Map<String, Value>) -> Result<String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let (key, target) = resolve(reference, definitions)?;
+        if key == "Bool" {
+            return Ok("boolean".to_string());
+        }
+        if key.starts_with("Option") {
+            // Some(x) carries the wrapped type as its only field
+            let inner = target["anyOf"]
+                .as_array()
+                .and_then(|cs| cs.iter().find_map(|c| c["fields"].as_array()?.first()))
+                .map(|f| ts_type(f, definitions))
+                .transpose()?
+                .unwrap_or_else(|| "unknown".to_string());
+            return Ok(format!("{} | null", inner));
+        }
+        if is_named_constructor(&key, target) {
+            return Ok(type_name(&key));
+        }
+        return ts_type(target, definitions);
+    }
+
+    Ok(match schema.get("dataType").and_then(Value::as_str) {
+        Some("integer") => "bigint".to_string(),
+        Some("bytes") => "string".to_string(),
+        Some("list") => match schema.get("items") {
+            // Tuples are lists with one schema per position
+            Some(Value::Array(items)) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(|item| ts_type(item, definitions))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ")
+            ),
+            Some(item) => format!("Array<{}>", ts_type(item, definitions)?),
+            None => "unknown[]".to_string(),
+        },
+        Some("map") => match (schema.get("keys"), schema.get("values")) {
+            (Some(keys), Some(values)) => format!(
+                "Map<{}, {}>",
+                ts_type(keys, definitions)?,
+                ts_type(values, definitions)?
+            ),
+            _ => "Map<unknown, unknown>".to_string(),
+        },
+        // Opaque `Data` (no dataType) and anything unrecognised
+        _ => "unknown".to_string(),
+    })
+}
+
+fn pascal_case(s: &str) -> String {
+    s.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn camel_case(s: &str) -> String {
+    let pascal = pascal_case(s);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/plutus.json");
+
+    #[test]
+    fn emits_interfaces_for_blueprint_types() {
+        let ts = blueprint_types(FIXTURE).unwrap();
+
+        assert!(ts.contains(
+            "export interface EscrowDatum {\n  buyer: string;\n  seller: string;\n  \
+             deadline: bigint;\n  tags: Array<string>;\n  arbiter: string | null;\n  \
+             settled: boolean;\n}\n"
+        ));
+        assert!(ts.contains(
+            "export type EscrowRedeemer =\n  | { tag: \"Release\" }\n  \
+             | { tag: \"Refund\"; amount: bigint; split: [string, bigint] }\n  \
+             | { tag: \"Arbitrate\"; field0: Map<string, bigint>; extra: unknown };\n"
+        ));
+        assert!(ts.contains(
+            "/** escrow.my_escrow.spend */\n\
+             export type MyEscrowSpendDatum = EscrowDatum;\n\
+             export type MyEscrowSpendRedeemer = EscrowRedeemer;\n"
+        ));
+        // Fallback validators have no datum/redeemer; Bool/Option are inlined, not emitted
+        assert!(!ts.contains("MyEscrowElse"));
+        assert!(!ts.contains("export type Bool"));
+        assert!(!ts.contains("Option"));
+    }
+
+    #[test]
+    fn rejects_invalid_blueprints() {
+        assert!(blueprint_types("not json").is_err());
+        assert!(blueprint_types("{}").is_err());
+        let dangling = r##"{"validators":[{"title":"m.v.spend","datum":{"schema":{"$ref":"#/definitions/Nope"}}}]}"##;
+        let err = blueprint_types(dangling).unwrap_err();
+        assert!(err.to_string().contains("has no definition"));
+    }
+}
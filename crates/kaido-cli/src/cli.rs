@@ -1,4 +1,6 @@
+use clap::builder::{PossibleValue, TypedValueParser};
 use clap::{Parser, Subcommand, ValueEnum};
+use kaido_core::features::Feature;
 
 #[derive(Parser)]
 #[command(
@@ -13,25 +15,128 @@ pub struct Cli {
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 pub enum Commands {
     /// Generate a new Aiken smart contract project
     Generate {
         /// Contract template to use
-        #[arg(short, long)]
-        template: TemplateArg,
+        #[arg(short, long, required_unless_present = "from_stdin")]
+        template: Option<TemplateArg>,
 
         /// Project namespace (e.g., "myorg")
-        #[arg(short, long)]
-        namespace: String,
+        #[arg(
+            short,
+            long,
+            required_unless_present_any = ["from_stdin", "namespace_from_git"]
+        )]
+        namespace: Option<String>,
+
+        /// Infer the namespace from the org in `git config --get remote.origin.url`
+        /// (e.g. "git@github.com:myorg/repo.git" -> "myorg") when --namespace is omitted
+        #[arg(long, default_value_t = false, conflicts_with = "from_stdin")]
+        namespace_from_git: bool,
 
         /// Project name (e.g., "my-token")
-        #[arg(short = 'p', long)]
-        project_name: String,
+        #[arg(
+            short = 'p',
+            long,
+            required_unless_present_any = ["from_stdin", "project_names"]
+        )]
+        project_name: Option<String>,
 
-        /// Output directory (defaults to ./<project_name>)
+        /// Generate several projects from the same template, each into <output>/<name>
+        /// (e.g., "token-a,token-b")
+        #[arg(
+            long,
+            value_delimiter = ',',
+            conflicts_with_all = ["project_name", "from_stdin", "into_workspace", "json_report"]
+        )]
+        project_names: Vec<String>,
+
+        /// Output directory (defaults to ./<project_name>, or . with --project-names)
         #[arg(short, long)]
         output: Option<String>,
 
+        /// Add validator and lib files to the existing Aiken project at ROOT
+        /// (keeps its aiken.toml; lib/ uses the workspace namespace)
+        #[arg(long, value_name = "ROOT", conflicts_with = "output")]
+        into_workspace: Option<String>,
+
+        /// Read template and project options as a JSON object from stdin
+        /// (same shape as the MCP/WASM input; template-specific flags are ignored)
+        #[arg(long, default_value_t = false)]
+        from_stdin: bool,
+
+        /// Rewrite an invalid namespace/project name into snake_case (e.g. "MyToken" -> "my_token")
+        /// with a warning, instead of rejecting it; path-like names are still rejected
+        #[arg(long, default_value_t = false, conflicts_with = "from_stdin")]
+        force_snake_case: bool,
+
+        /// Only check the options (names, features, datum/redeemer and their cross-checks),
+        /// print every error, and exit without rendering or writing files
+        #[arg(long, default_value_t = false)]
+        validate_only: bool,
+
+        /// Print a CBOR diagnostic encoding of the custom datum and each redeemer action
+        /// (using the generated test values) and exit without writing files
+        #[arg(long, default_value_t = false, conflicts_with_all = ["project_names", "validate_only"])]
+        preview_cbor: bool,
+
+        /// Print the test cases (positive and negative) the custom template would embed in the
+        /// validator and exit without writing files
+        #[arg(long, default_value_t = false, conflicts_with_all = ["project_names", "validate_only", "preview_cbor"])]
+        preview_tests: bool,
+
+        /// Render in memory and print a unified diff against the project in <DIR> (changed, added
+        /// and removed files) without writing anything
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["project_names", "validate_only", "preview_cbor", "preview_tests", "into_workspace"])]
+        diff_against: Option<String>,
+
+        /// With --diff-against, list the template changes since this kaido version instead of the
+        /// one recorded in the project's validator headers
+        #[arg(long, value_name = "VERSION", requires = "diff_against")]
+        since: Option<String>,
+
+        /// Print each validator's parameters (name, type, description) in the order they must be
+        /// applied to instantiate the script, and exit without writing files
+        #[arg(long, default_value_t = false, conflicts_with_all = ["project_names", "validate_only", "preview_cbor", "preview_tests", "diff_against"])]
+        print_params: bool,
+
+        /// How to treat files that already exist in the output directory
+        #[arg(long, value_enum, default_value_t = OverwritePolicyArg::Changed)]
+        overwrite_policy: OverwritePolicyArg,
+
+        /// On-disk layout: `flat` writes every file into the output directory itself (e.g.
+        /// lib__ns__module__types.ak) with a kaido-manifest.json of the original paths; it is not
+        /// an aiken project, so it needs --skip-verify
+        #[arg(long, value_enum, default_value_t = OutputFormatArg::Tree)]
+        output_format: OutputFormatArg,
+
+        /// Override the derived validator name (snake_case, e.g., "my_validator")
+        #[arg(long)]
+        validator_name: Option<String>,
+
+        /// Emit an aiken.lock pinning the stdlib version
+        #[arg(long, default_value_t = false)]
+        emit_lock: bool,
+
+        /// aiken-lang/stdlib release tag to pin in aiken.toml / aiken.lock
+        #[arg(long, default_value = kaido_core::templates::DEFAULT_STDLIB_VERSION)]
+        stdlib_version: String,
+
+        /// Target network for the SDK client and the aiken.toml annotation
+        #[arg(long, value_enum, default_value_t = NetworkArg::Preview)]
+        network: NetworkArg,
+
+        /// Emit a CI workflow running aiken check (and the aikido scan when .aikido.toml exists)
+        #[arg(long, value_enum)]
+        emit_ci: Option<CiArg>,
+
+        /// Load <NAME>.tera files from <DIR> in place of the built-in templates of the same
+        /// name (e.g. base/aiken.toml.tera); templates it doesn't provide stay built-in
+        #[arg(long, value_name = "DIR")]
+        template_dir: Option<String>,
+
         // --- Simple Mint options ---
         /// Token display name (for mint template)
         #[arg(long)]
@@ -54,15 +159,24 @@ pub enum Commands {
         #[arg(long, default_value_t = false)]
         partial_claim: bool,
 
+        // --- NFT Marketplace options ---
+        /// Enforce a CIP-27 royalty payout on buy (royalty_pkh/royalty_bps in the listing datum)
+        #[arg(long, default_value_t = false)]
+        royalty: bool,
+
         // --- SDK ---
         /// Generate TypeScript SDK alongside Aiken code
         #[arg(long, default_value_t = false)]
         sdk: bool,
 
+        /// SDK language to generate with --sdk
+        #[arg(long, value_enum, default_value_t = SdkLangArg::Typescript, requires = "sdk")]
+        sdk_lang: SdkLangArg,
+
         // --- Custom template options ---
         /// Composable features (only with --template custom)
         /// e.g., "sig,timelock,datum-continuity"
-        #[arg(long, value_delimiter = ',')]
+        #[arg(long, value_delimiter = ',', value_parser = FeatureNameParser, hide_possible_values = true)]
         features: Vec<String>,
 
         /// Custom datum fields (only with --template custom)
@@ -70,32 +184,205 @@ pub enum Commands {
         #[arg(long)]
         datum: Option<String>,
 
+        /// Read the custom datum fields from a file instead of --datum
+        /// (same grammar; line breaks and indentation are dropped)
+        #[arg(long, value_name = "PATH", conflicts_with = "datum")]
+        datum_from_file: Option<String>,
+
         /// Custom redeemer actions (only with --template custom)
         /// e.g., "Claim,Cancel,Withdraw(amount:Int)"
         #[arg(long)]
         redeemer: Option<String>,
 
+        /// Read the custom redeemer actions from a file instead of --redeemer
+        /// (same grammar; line breaks and indentation are dropped)
+        #[arg(long, value_name = "PATH", conflicts_with = "redeemer")]
+        redeemer_from_file: Option<String>,
+
         /// Validator purpose: "spend" or "mint" (only with --template custom)
         #[arg(long, default_value = "spend")]
         purpose: String,
 
+        /// Int datum field advanced by the monotonic-counter feature
+        /// (only with --template custom; defaults to nonce/sequence/version)
+        #[arg(long)]
+        counter_field: Option<String>,
+
+        /// Int datum field the expiry feature binds
+        /// (only with --template custom; defaults to expiry)
+        #[arg(long)]
+        expiry_field: Option<String>,
+
+        /// UTxO the generated unique-mint tests consume, as <TXHASH>#<INDEX>
+        /// (only with --template custom and unique-mint; defaults to a placeholder)
+        #[arg(long, value_name = "TXHASH#INDEX")]
+        seed_utxo: Option<String>,
+
+        /// Annotate each generated check with the feature that produced it
+        /// (only with --template custom)
+        #[arg(long, default_value_t = false)]
+        explain: bool,
+
+        /// Emit an Aiken trace before each generated check, kept only in builds
+        /// with traces enabled (only with --template custom)
+        #[arg(long, default_value_t = false)]
+        trace: bool,
+
+        /// Also generate Aiken property (fuzz) tests, e.g. fuzzing the lovelace that
+        /// value-preservation keeps (only with --template custom)
+        #[arg(long, default_value_t = false)]
+        property_tests: bool,
+
+        /// Write the types into the validator file instead of a lib/ types module
+        /// (only with --template custom)
+        #[arg(long, default_value_t = false)]
+        single_file: bool,
+
+        /// Fail, before writing any file, if generation produces warnings
+        #[arg(long, default_value_t = false)]
+        strict: bool,
+
         // --- Verification ---
         /// Skip aiken build verification
         #[arg(long, default_value_t = false)]
         skip_verify: bool,
+
+        /// Run `aiken fmt` on the written project before verifying it
+        #[arg(long, default_value_t = false, conflicts_with = "skip_verify")]
+        fmt: bool,
+
+        /// Run build, check and scan even if an earlier step fails, then report every failure
+        #[arg(long, default_value_t = false, conflicts_with = "skip_verify")]
+        keep_going: bool,
+
+        /// Fail verification when aikido reports more than N findings, whatever their severity
+        /// (suppressed findings don't count)
+        #[arg(long, value_name = "N", conflicts_with = "skip_verify")]
+        max_findings: Option<usize>,
+
+        /// Skip verification when no .ak file (or aiken.toml) changed on disk,
+        /// e.g. when regenerating with --overwrite-policy changed
+        #[arg(long, default_value_t = false, conflicts_with = "skip_verify")]
+        check_only_changed: bool,
+
+        /// Write the verification result (steps, findings, tool versions) to a JSON file
+        #[arg(long, value_name = "PATH", conflicts_with = "skip_verify")]
+        json_report: Option<String>,
+
+        /// After verification, write TypeScript datum/redeemer types read from the built
+        /// plutus.json to sdk/src/blueprint-types.ts (works for every template)
+        #[arg(long, default_value_t = false, conflicts_with = "skip_verify")]
+        emit_blueprint_types: bool,
+
+        /// Print a local timing breakdown of each generation phase
+        #[arg(long, default_value_t = false)]
+        stats: bool,
+
+        /// Print --stats output as JSON
+        #[arg(long, default_value_t = false, requires = "stats")]
+        json: bool,
     },
 
     /// List all available templates
     List,
 
+    /// Print the JSON Schema describing all valid generate options
+    Schema,
+
+    /// List the composable features of the custom template
+    Features {
+        /// Print every feature pair as compatible, depends_on, required_by or conflicts (JSON)
+        #[arg(long, default_value_t = false)]
+        matrix: bool,
+    },
+
     /// Verify an existing generated project compiles
     Verify {
         /// Path to the Aiken project to verify
         #[arg(default_value = ".")]
         path: String,
+
+        /// Rewrite the project's .ak files with `aiken fmt` before verifying, listing the
+        /// files it changed
+        #[arg(long, default_value_t = false)]
+        fix_fmt: bool,
+
+        /// Run build, check and scan even if an earlier step fails, then report every failure
+        #[arg(long, default_value_t = false)]
+        keep_going: bool,
+
+        /// Fail when aikido reports more than N findings, whatever their severity
+        /// (suppressed findings don't count)
+        #[arg(long, value_name = "N")]
+        max_findings: Option<usize>,
+
+        /// Write the verification result (steps, findings, tool versions) to a JSON file
+        #[arg(long, value_name = "PATH")]
+        json_report: Option<String>,
+
+        /// Write a Markdown audit summary (project, tool versions, step status, findings table)
+        #[arg(long, value_name = "FILE.md")]
+        audit_report: Option<String>,
+    },
+
+    /// Check a generated project for kaido conventions (snake_case modules, .ak validators,
+    /// lib/ namespace, leftover todo placeholders)
+    Lint {
+        /// Path to the Aiken project to lint
+        #[arg(default_value = ".")]
+        path: String,
+    },
+
+    /// Write kaido's .aikido.toml into an existing Aiken project (nothing else is generated)
+    InitAikido {
+        /// Path to the Aiken project
+        #[arg(default_value = ".")]
+        path: String,
+
+        /// How to treat an existing .aikido.toml
+        #[arg(long, value_enum, default_value_t = OverwritePolicyArg::Changed)]
+        overwrite_policy: OverwritePolicyArg,
+
+        /// Run aikido scan on the project afterwards
+        #[arg(long, default_value_t = false)]
+        scan: bool,
+    },
+
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate the script for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
     },
 }
 
+/// Accepts any `--features` entry (aliases included; `parse_features` reports unknown ones)
+/// while offering the canonical feature names to shell completion
+#[derive(Clone)]
+pub struct FeatureNameParser;
+
+impl TypedValueParser for FeatureNameParser {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<String, clap::Error> {
+        clap::builder::StringValueParser::new().parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            Feature::all()
+                .iter()
+                .map(|f| PossibleValue::new(f.name()).help(f.description())),
+        ))
+    }
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 pub enum TemplateArg {
     /// CIP-25 minting policy with admin signature and optional time-lock
@@ -122,6 +409,62 @@ pub enum TemplateArg {
     Governance,
     /// Streaming payments with time-based tranches and cancel/top-up
     Streaming,
+    /// Hashed timelock (atomic swap) with secret claim and timeout refund
+    Htlc,
+    /// Split payment / payroll paying a fixed recipient list with admin reclaim
+    Payroll,
+    /// Rate-limited testnet faucet with per-address cooldown
+    Faucet,
+    /// Merkle allowlist minting policy with a fixed quantity per claim
+    Allowlist,
+    /// Spend gated by a Groth16 proof (BLS12-381 pairing builtins, Plutus V3)
+    ZkGate,
+    /// Lock any value until unlock_time, then release it to the owner
+    TokenLock,
+    /// Dutch auction with a price falling linearly to a floor
+    DutchAuction,
+    /// Operator-signed price feed with strictly increasing timestamps
+    OracleFeed,
     /// Custom validator with composable features
     Custom,
 }
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OverwritePolicyArg {
+    /// Error if any generated file already exists
+    Never,
+    /// Write only files whose content differs (reports unchanged files)
+    Changed,
+    /// Overwrite every file
+    Always,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormatArg {
+    /// Files at their project paths (default)
+    Tree,
+    /// All files in one directory, path separators replaced by `__`
+    Flat,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum NetworkArg {
+    Mainnet,
+    Preprod,
+    /// Default, so nothing targets mainnet unless asked
+    Preview,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CiArg {
+    /// .github/workflows/aiken.yml
+    Github,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SdkLangArg {
+    /// TypeScript SDK (Anvil adapter)
+    Typescript,
+    /// Python SDK (pycardano dataclasses)
+    Python,
+}
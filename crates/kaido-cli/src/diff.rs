@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use kaido_core::generator::GeneratedFile;
+use similar::TextDiff;
+
+use crate::lint::relative_files;
+
+/// How a rendered file compares with the one on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffStatus {
+    /// Rendered but not on disk
+    Added,
+    /// An `.ak` module on disk under `validators/` or `lib/` the render no longer produces
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+/// One file of `diff_project`, with its unified diff (empty when unchanged)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path relative to the project root, `/`-separated
+    pub path: String,
+    pub status: DiffStatus,
+    pub unified: String,
+}
+
+/// Compare rendered files with the project in `dir`, in render order, followed by removed
+/// modules in sorted order. Nothing is written.
+pub fn diff_project(files: &[GeneratedFile], dir: &Path) -> io::Result<Vec<FileDiff>> {
+    let mut diffs = Vec::new();
+    for file in files {
+        let (status, old) = match fs::read_to_string(dir.join(&file.path)) {
+            Ok(existing) if existing == file.content => (DiffStatus::Unchanged, existing),
+            Ok(existing) => (DiffStatus::Changed, existing),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (DiffStatus::Added, String::new()),
+            Err(e) => return Err(e),
+        };
+        let old_header = if status == DiffStatus::Added {
+            "/dev/null".to_string()
+        } else {
+            format!("a/{}", file.path)
+        };
+        let unified = if status == DiffStatus::Unchanged {
+            String::new()
+        } else {
+            unified_diff(
+                &old,
+                &file.content,
+                &old_header,
+                &format!("b/{}", file.path),
+            )
+        };
+        diffs.push(FileDiff {
+            path: file.path.clone(),
+            status,
+            unified,
+        });
+    }
+
+    let rendered: HashSet<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    for source_dir in ["validators", "lib"] {
+        for rel in relative_files(dir, Path::new(source_dir))? {
+            let path = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if rel.extension().is_none_or(|ext| ext != "ak") || rendered.contains(path.as_str()) {
+                continue;
+            }
+            let old = fs::read_to_string(dir.join(&rel))?;
+            let unified = unified_diff(&old, "", &format!("a/{path}"), "/dev/null");
+            diffs.push(FileDiff {
+                path,
+                status: DiffStatus::Removed,
+                unified,
+            });
+        }
+    }
+
+    Ok(diffs)
+}
+
+fn unified_diff(old: &str, new: &str, old_header: &str, new_header: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_header, new_header)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, content: &str) -> GeneratedFile {
+        GeneratedFile {
+            path: path.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_reports_changed_added_removed_and_unchanged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("validators")).unwrap();
+        fs::write(dir.path().join("aiken.toml"), "name = \"old\"\n").unwrap();
+        fs::write(dir.path().join("validators/same.ak"), "same\n").unwrap();
+        fs::write(dir.path().join("validators/stale.ak"), "stale\n").unwrap();
+        // Not an aiken module, so it is never reported as removed
+        fs::write(dir.path().join("validators/notes.txt"), "notes\n").unwrap();
+
+        let files = [
+            file("aiken.toml", "name = \"new\"\n"),
+            file("validators/same.ak", "same\n"),
+            file("validators/fresh.ak", "fresh\n"),
+        ];
+        let diffs = diff_project(&files, dir.path()).unwrap();
+        let statuses: Vec<(&str, DiffStatus)> =
+            diffs.iter().map(|d| (d.path.as_str(), d.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("aiken.toml", DiffStatus::Changed),
+                ("validators/same.ak", DiffStatus::Unchanged),
+                ("validators/fresh.ak", DiffStatus::Added),
+                ("validators/stale.ak", DiffStatus::Removed),
+            ]
+        );
+
+        assert!(diffs[0].unified.contains("--- a/aiken.toml"));
+        assert!(diffs[0].unified.contains("-name = \"old\""));
+        assert!(diffs[0].unified.contains("+name = \"new\""));
+        assert!(diffs[1].unified.is_empty());
+        assert!(diffs[2].unified.contains("--- /dev/null"));
+        assert!(diffs[3].unified.contains("+++ /dev/null"));
+        assert!(diffs[3].unified.contains("-stale"));
+    }
+}
@@ -0,0 +1,76 @@
+use std::process::Command;
+
+use kaido_core::error::{KaidoError, Result};
+use kaido_core::templates::GenerateOptions;
+
+/// Namespace for `--namespace-from-git`: the org segment of `remote.origin.url`, rewritten like
+/// `--force-snake-case` so e.g. "MyOrg" becomes "my_org"
+pub fn namespace_from_remote() -> Result<String> {
+    let output = Command::new("git")
+        .args(["config", "--get", "remote.origin.url"])
+        .output()
+        .map_err(|e| {
+            KaidoError::InvalidOption(format!(
+                "--namespace-from-git: failed to run git: {}; pass --namespace",
+                e
+            ))
+        })?;
+    let url = String::from_utf8_lossy(&output.stdout);
+    let url = url.trim();
+    if !output.status.success() || url.is_empty() {
+        return Err(KaidoError::InvalidOption(
+            "--namespace-from-git: no git remote 'origin' configured; pass --namespace".into(),
+        ));
+    }
+
+    let org = remote_org(url).ok_or_else(|| {
+        KaidoError::InvalidOption(format!(
+            "--namespace-from-git: cannot find an org in remote URL '{}'; pass --namespace",
+            url
+        ))
+    })?;
+    GenerateOptions::force_snake_case(org, "namespace").map_err(|e| {
+        KaidoError::InvalidOption(format!(
+            "--namespace-from-git: org '{}' from remote URL '{}' is not a valid namespace: {}",
+            org, url, e
+        ))
+    })
+}
+
+/// First path segment of a hosted remote (`https://host/org/repo.git`,
+/// `ssh://git@host/org/repo`, `git@host:org/repo.git`); None for local paths or a bare host
+fn remote_org(url: &str) -> Option<&str> {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        // scp-like syntax: [user@]host:path
+        None => url.split_once(':')?.1,
+    };
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let org = segments.next()?;
+    // The org alone (e.g. "host:org") names no repository
+    segments.next()?;
+    Some(org)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_org_reads_https_ssh_and_scp_urls() {
+        assert_eq!(
+            remote_org("https://github.com/Bajuzjefe/kaido.git"),
+            Some("Bajuzjefe")
+        );
+        assert_eq!(
+            remote_org("ssh://git@gitlab.com/acme/contracts/vault"),
+            Some("acme")
+        );
+        assert_eq!(
+            remote_org("git@github.com:acme-labs/vault.git"),
+            Some("acme-labs")
+        );
+        assert_eq!(remote_org("/srv/git/vault.git"), None);
+        assert_eq!(remote_org("https://github.com/acme"), None);
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::workspace::package_name;
+
+/// A kaido convention a generated project breaks, with its location
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    /// 1-based line, for findings inside a file
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// Check a generated project for the failure modes aiken and aikido don't report:
+/// non-snake_case module paths, non-`.ak` validator files, a `lib/` namespace that
+/// differs from aiken.toml, and leftover `todo` placeholders.
+pub fn lint_project(root: &Path) -> io::Result<Vec<LintWarning>> {
+    let mut warnings = Vec::new();
+    let warn = |path: &Path, line: Option<usize>, message: String| LintWarning {
+        path: path.to_path_buf(),
+        line,
+        message,
+    };
+
+    let config = fs::read_to_string(root.join("aiken.toml"))?;
+    let namespace = package_name(&config).and_then(|name| name.split_once('/').map(|(ns, _)| ns));
+    if namespace.is_none() {
+        warnings.push(warn(
+            Path::new("aiken.toml"),
+            None,
+            "name is not \"<namespace>/<project>\"".to_string(),
+        ));
+    }
+
+    let lib_files = relative_files(root, Path::new("lib"))?;
+    if let Some(expected) = namespace {
+        let lib_namespaces: BTreeSet<PathBuf> = lib_files
+            .iter()
+            .filter_map(|rel| rel.components().nth(1))
+            .map(|ns| Path::new("lib").join(ns))
+            .collect();
+        for ns_dir in lib_namespaces.iter().filter(|d| !d.ends_with(expected)) {
+            warnings.push(warn(
+                ns_dir,
+                None,
+                format!("does not match the aiken.toml namespace '{expected}'"),
+            ));
+        }
+    }
+
+    let validator_files = relative_files(root, Path::new("validators"))?;
+    for rel in &validator_files {
+        if rel.extension().is_none_or(|ext| ext != "ak") {
+            warnings.push(warn(
+                rel,
+                None,
+                "validators/ file is not an .ak module (aiken ignores it)".to_string(),
+            ));
+        }
+    }
+
+    for rel in lib_files.iter().chain(&validator_files) {
+        // Aiken silently skips modules whose path has hyphens or capitals
+        if let Some(bad) = rel
+            .with_extension("")
+            .components()
+            .skip(1)
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .find(|c| !is_snake_case(c))
+        {
+            warnings.push(warn(
+                rel,
+                None,
+                format!("'{bad}' is not snake_case (aiken silently skips this module)"),
+            ));
+        }
+
+        if rel.extension().is_some_and(|ext| ext == "ak") {
+            let source = fs::read_to_string(root.join(rel))?;
+            for (i, line) in source.lines().enumerate() {
+                if has_todo_placeholder(line) {
+                    warnings.push(warn(
+                        rel,
+                        Some(i + 1),
+                        "`todo` placeholder left in generated code".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Files under `root/dir`, relative to `root`, in sorted order (empty if `dir` is missing)
+pub fn relative_files(root: &Path, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(rel_dir) = pending.pop() {
+        let entries = match fs::read_dir(root.join(&rel_dir)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let rel = rel_dir.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                pending.push(rel);
+            } else {
+                files.push(rel);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn is_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Whether a line uses the bare `todo` keyword outside a comment
+fn has_todo_placeholder(line: &str) -> bool {
+    let code = line.split("//").next().unwrap_or_default();
+    code.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .any(|word| word == "todo")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::{write_project, OutputLayout, OverwritePolicy};
+    use kaido_core::features::types::{DatumField, RedeemerAction};
+    use kaido_core::generator::ProjectGenerator;
+    use kaido_core::templates::GenerateOptions;
+
+    fn generated_escrow() -> tempfile::TempDir {
+        generated(&GenerateOptions::escrow("myorg", "my-escrow"))
+    }
+
+    fn generated(options: &GenerateOptions) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let result = ProjectGenerator::new().unwrap().render(options).unwrap();
+        write_project(
+            &result,
+            dir.path(),
+            OverwritePolicy::Always,
+            OutputLayout::Tree,
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn generated_project_is_clean() {
+        let dir = generated_escrow();
+        assert_eq!(lint_project(dir.path()).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn generated_custom_with_list_redeemer_field_is_clean() {
+        let options = GenerateOptions::custom(
+            "myorg",
+            "my-custom",
+            "spend",
+            vec![DatumField {
+                name: "admin".to_string(),
+                aiken_type: "ByteArray".to_string(),
+            }],
+            vec![RedeemerAction {
+                name: "Claim".to_string(),
+                fields: vec![("proof".to_string(), "List<ByteArray>".to_string())],
+            }],
+            vec!["signature-auth".to_string()],
+        );
+        let dir = generated(&options);
+        assert_eq!(lint_project(dir.path()).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn flags_hyphenated_lib_path() {
+        let dir = generated_escrow();
+        let lib = dir.path().join("lib/myorg");
+        fs::rename(lib.join("my_escrow"), lib.join("my-escrow")).unwrap();
+
+        let warnings = lint_project(dir.path()).unwrap();
+        assert!(!warnings.is_empty());
+        assert!(warnings
+            .iter()
+            .all(|w| w.path.starts_with("lib/myorg/my-escrow")
+                && w.message.contains("'my-escrow' is not snake_case")));
+    }
+
+    #[test]
+    fn flags_namespace_mismatch_stray_files_and_todo() {
+        let dir = generated_escrow();
+        fs::rename(dir.path().join("lib/myorg"), dir.path().join("lib/other")).unwrap();
+        fs::write(dir.path().join("validators/notes.txt"), "").unwrap();
+        fs::write(
+            dir.path().join("validators/extra.ak"),
+            "// todo: fine in a comment\ntest t() {\n  todo\n}\n",
+        )
+        .unwrap();
+
+        let messages: Vec<String> = lint_project(dir.path())
+            .unwrap()
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        assert!(messages
+            .iter()
+            .any(|m| m == "lib/other: does not match the aiken.toml namespace 'myorg'"));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("validators/notes.txt: validators/ file is not an .ak module")));
+        assert!(messages.contains(
+            &"validators/extra.ak:3: `todo` placeholder left in generated code".to_string()
+        ));
+        assert_eq!(messages.iter().filter(|m| m.contains("todo")).count(), 1);
+    }
+}
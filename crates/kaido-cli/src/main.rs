@@ -1,18 +1,43 @@
+mod blueprint;
 mod cli;
+mod diff;
+mod git;
+mod lint;
+mod stats;
 mod verify;
+mod workspace;
 mod writer;
 
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use colored::Colorize;
 
-use cli::{Cli, Commands, TemplateArg};
+use cli::{
+    CiArg, Cli, Commands, NetworkArg, OutputFormatArg, OverwritePolicyArg, SdkLangArg, TemplateArg,
+};
+use diff::DiffStatus;
+use kaido_core::diagnostics::Diagnostics;
 use kaido_core::error;
-use kaido_core::features;
-use kaido_core::generator::ProjectGenerator;
-use kaido_core::templates::GenerateOptions;
-use verify::{AikenVerifier, AikidoVerifier};
+use kaido_core::features::{cbor, compose, Feature};
+use kaido_core::generator::{
+    build_options, ignored_options, recorded_kaido_version, validate_options, GeneratedFile,
+    OptionExtras, ProjectGenerator, RenderResult,
+};
+use kaido_core::options::{build_options_from_json, validate_options_from_json};
+use kaido_core::templates::{
+    CiProvider, GenerateOptions, Network, SdkLang, Template, KAIDO_VERSION,
+};
+use stats::GenerationStats;
+use verify::{
+    AikenVerifier, AikidoResult, AikidoVerifier, StageFailures, StepStatus, VerificationReport,
+    SUPPRESS_FILE,
+};
+use workspace::Workspace;
+use writer::{OutputLayout, OverwritePolicy, WriteStatus};
 
 fn main() {
     let cli = Cli::parse();
@@ -20,38 +45,198 @@ fn main() {
     match cli.command {
         Commands::Generate {
             template,
-            namespace,
-            project_name,
+            mut namespace,
+            namespace_from_git,
+            mut project_name,
+            mut project_names,
+            force_snake_case,
             output,
+            into_workspace,
+            from_stdin,
+            validate_only,
+            preview_cbor,
+            preview_tests,
+            diff_against,
+            since,
+            print_params,
+            overwrite_policy,
+            output_format,
+            validator_name,
+            emit_lock,
+            stdlib_version,
+            network,
+            emit_ci,
             token_name,
             asset_name,
             time_lock,
             cancellable,
             partial_claim,
+            royalty,
             features,
             datum,
+            datum_from_file,
             redeemer,
+            redeemer_from_file,
             purpose,
+            counter_field,
+            expiry_field,
+            seed_utxo,
+            explain,
+            trace,
+            property_tests,
+            single_file,
+            strict,
             sdk,
+            sdk_lang,
             skip_verify,
+            fmt,
+            keep_going,
+            max_findings,
+            check_only_changed,
+            json_report,
+            emit_blueprint_types,
+            stats,
+            json,
+            template_dir,
         } => {
-            if let Err(e) = run_generate(
-                template,
-                &namespace,
-                &project_name,
-                output,
-                token_name,
-                asset_name,
-                time_lock,
-                cancellable,
-                partial_claim,
-                features,
-                datum,
-                redeemer,
-                &purpose,
+            let mut settings = RunSettings {
+                overwrite_policy,
+                output_format,
+                template_dir,
+                strict,
                 sdk,
+                sdk_lang,
                 skip_verify,
-            ) {
+                fmt,
+                keep_going,
+                max_findings,
+                check_only_changed,
+                json_report,
+                emit_blueprint_types,
+                show_stats: stats,
+                stats_json: json,
+                ignored_options: Default::default(),
+                since,
+            };
+            // An explicit --namespace wins over the git remote
+            if namespace_from_git && namespace.is_none() {
+                namespace = match git::namespace_from_remote() {
+                    Ok(inferred) => {
+                        println!(
+                            "{} namespace '{}' from git remote origin",
+                            "Inferred".cyan().bold(),
+                            inferred
+                        );
+                        Some(inferred)
+                    }
+                    Err(e) => {
+                        eprintln!("{} {}", "Error:".red().bold(), e);
+                        std::process::exit(1);
+                    }
+                };
+            }
+            let names_ok = if force_snake_case {
+                force_snake_case_names(&mut namespace, &mut project_name, &mut project_names)
+            } else {
+                Ok(())
+            };
+            let specs = names_ok
+                .and_then(|()| spec_or_file(datum, datum_from_file))
+                .and_then(|datum| Ok((datum, spec_or_file(redeemer, redeemer_from_file)?)));
+            let result = match (template, namespace, specs) {
+                (Some(_), Some(_), Err(e)) if !from_stdin => Err(e),
+                (Some(template), Some(namespace), Ok((datum, redeemer))) if !from_stdin => {
+                    let template = template_from_arg(template);
+                    let extras = OptionExtras {
+                        token_name,
+                        asset_name,
+                        time_lock,
+                        cancellable,
+                        partial_claim,
+                        royalty,
+                        features,
+                        datum,
+                        redeemer,
+                        purpose: Some(purpose),
+                        counter_field,
+                        expiry_field,
+                        seed_utxo,
+                        explain,
+                        trace,
+                        property_tests,
+                        single_file,
+                        validator_name,
+                        stdlib_version: Some(stdlib_version),
+                        emit_lock,
+                        network: Some(network_from_arg(network).slug().to_string()),
+                        emit_ci: emit_ci.map(|ci| ci_from_arg(ci).slug().to_string()),
+                    };
+                    settings.ignored_options = ignored_options(template, &extras);
+                    if validate_only {
+                        let names = if project_names.is_empty() {
+                            vec![project_name.unwrap_or_default()]
+                        } else {
+                            project_names
+                        };
+                        let mut errors = Vec::new();
+                        for name in &names {
+                            for e in validate_options(template, &namespace, name, &extras) {
+                                let message = e.to_string();
+                                // Template-wide errors repeat for every project name
+                                if !errors.contains(&message) {
+                                    errors.push(message);
+                                }
+                            }
+                        }
+                        report_validation(&errors)
+                    } else if project_names.is_empty() {
+                        // clap requires --project-name unless --project-names is set
+                        let project_name = project_name.unwrap_or_default();
+                        build_options(template, &namespace, &project_name, &extras).and_then(
+                            |options| {
+                                if preview_cbor {
+                                    print_cbor_preview(&options)
+                                } else if preview_tests {
+                                    print_tests_preview(&options)
+                                } else if print_params {
+                                    print_validator_params(&options, &settings)
+                                } else if let Some(dir) = &diff_against {
+                                    print_project_diff(&options, Path::new(dir), &settings)
+                                } else {
+                                    generate_one(options, output, into_workspace, &settings)
+                                }
+                            },
+                        )
+                    } else {
+                        run_batch(
+                            template,
+                            &namespace,
+                            &project_names,
+                            &extras,
+                            output,
+                            &settings,
+                        )
+                    }
+                }
+                _ if validate_only => read_stdin_json().and_then(|args| {
+                    let errors: Vec<String> = validate_options_from_json(&args)
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect();
+                    report_validation(&errors)
+                }),
+                // clap requires template/namespace/project-name unless --from-stdin is set
+                _ if preview_cbor => options_from_stdin().and_then(|o| print_cbor_preview(&o)),
+                _ if preview_tests => options_from_stdin().and_then(|o| print_tests_preview(&o)),
+                _ if print_params => {
+                    options_from_stdin().and_then(|o| print_validator_params(&o, &settings))
+                }
+                _ => options_from_stdin().and_then(|options| match &diff_against {
+                    Some(dir) => print_project_diff(&options, Path::new(dir), &settings),
+                    None => generate_one(options, output, into_workspace, &settings),
+                }),
+            };
+            if let Err(e) = result {
                 eprintln!("{} {}", "Error:".red().bold(), e);
                 std::process::exit(1);
             }
@@ -59,110 +244,536 @@ fn main() {
         Commands::List => {
             run_list();
         }
-        Commands::Verify { path } => {
-            if let Err(e) = run_verify(&path) {
+        Commands::Schema => {
+            run_schema();
+        }
+        Commands::Features { matrix } => {
+            run_features(matrix);
+        }
+        Commands::Verify {
+            path,
+            fix_fmt,
+            keep_going,
+            max_findings,
+            json_report,
+            audit_report,
+        } => {
+            if let Err(e) = run_verify(
+                &path,
+                fix_fmt,
+                keep_going,
+                max_findings,
+                json_report,
+                audit_report,
+            ) {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Lint { path } => {
+            if let Err(e) = run_lint(&path) {
                 eprintln!("{} {}", "Error:".red().bold(), e);
                 std::process::exit(1);
             }
         }
+        Commands::InitAikido {
+            path,
+            overwrite_policy,
+            scan,
+        } => {
+            if let Err(e) = run_init_aikido(&path, overwrite_policy, scan) {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "kaido", &mut std::io::stdout());
+        }
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn run_generate(
-    template_arg: TemplateArg,
-    namespace: &str,
-    project_name: &str,
-    output: Option<String>,
-    token_name: Option<String>,
-    asset_name: Option<String>,
-    time_lock: bool,
-    cancellable: bool,
-    partial_claim: bool,
-    feature_strs: Vec<String>,
-    datum: Option<String>,
-    redeemer: Option<String>,
-    purpose: &str,
-    sdk: bool,
-    skip_verify: bool,
+fn template_from_arg(template_arg: TemplateArg) -> Template {
+    match template_arg {
+        TemplateArg::Mint => Template::SimpleMint,
+        TemplateArg::Vesting => Template::Vesting,
+        TemplateArg::Escrow => Template::Escrow,
+        TemplateArg::Treasury => Template::MultisigTreasury,
+        TemplateArg::Marketplace => Template::NftMarketplace,
+        TemplateArg::Staking => Template::StakingPool,
+        TemplateArg::Oracle => Template::OracleSettlement,
+        TemplateArg::Referral => Template::ReferralSystem,
+        TemplateArg::Dex => Template::DexPool,
+        TemplateArg::Lending => Template::LendingPool,
+        TemplateArg::Governance => Template::DaoGovernance,
+        TemplateArg::Streaming => Template::StreamingPayments,
+        TemplateArg::Htlc => Template::HashTimelock,
+        TemplateArg::Payroll => Template::Payroll,
+        TemplateArg::Faucet => Template::Faucet,
+        TemplateArg::Allowlist => Template::MerkleAllowlistMint,
+        TemplateArg::ZkGate => Template::ZkGate,
+        TemplateArg::TokenLock => Template::TokenLock,
+        TemplateArg::DutchAuction => Template::DutchAuction,
+        TemplateArg::OracleFeed => Template::OracleFeed,
+        TemplateArg::Custom => Template::Custom,
+    }
+}
+
+fn sdk_lang_from_arg(lang: SdkLangArg) -> SdkLang {
+    match lang {
+        SdkLangArg::Typescript => SdkLang::TypeScript,
+        SdkLangArg::Python => SdkLang::Python,
+    }
+}
+
+fn policy_from_arg(policy: OverwritePolicyArg) -> OverwritePolicy {
+    match policy {
+        OverwritePolicyArg::Never => OverwritePolicy::Never,
+        OverwritePolicyArg::Changed => OverwritePolicy::Changed,
+        OverwritePolicyArg::Always => OverwritePolicy::Always,
+    }
+}
+
+fn ci_from_arg(ci: CiArg) -> CiProvider {
+    match ci {
+        CiArg::Github => CiProvider::GitHub,
+    }
+}
+
+fn network_from_arg(network: NetworkArg) -> Network {
+    match network {
+        NetworkArg::Mainnet => Network::Mainnet,
+        NetworkArg::Preprod => Network::Preprod,
+        NetworkArg::Preview => Network::Preview,
+    }
+}
+
+/// Apply `--force-snake-case` to the namespace and project name(s), warning about each rewrite
+fn force_snake_case_names(
+    namespace: &mut Option<String>,
+    project_name: &mut Option<String>,
+    project_names: &mut [String],
 ) -> error::Result<()> {
-    GenerateOptions::validate_namespace_and_project(namespace, project_name)
-        .map_err(error::KaidoError::InvalidOption)?;
+    let names = namespace
+        .iter_mut()
+        .map(|name| ("namespace", name))
+        .chain(project_name.iter_mut().map(|name| ("project_name", name)))
+        .chain(project_names.iter_mut().map(|name| ("project_name", name)));
+    for (field, name) in names {
+        let fixed = GenerateOptions::force_snake_case(name, field)
+            .map_err(error::KaidoError::InvalidOption)?;
+        if fixed != *name {
+            println!(
+                "{} {} '{}' rewritten to '{}' (--force-snake-case)",
+                "Warning:".yellow().bold(),
+                field,
+                name,
+                fixed
+            );
+            *name = fixed;
+        }
+    }
+    Ok(())
+}
 
-    let output_dir = PathBuf::from(output.unwrap_or_else(|| project_name.to_string()));
+/// The inline datum/redeemer spec, or the one read from `path` with line breaks and
+/// indentation dropped, so a spec can be split across lines at its commas
+fn spec_or_file(inline: Option<String>, path: Option<String>) -> error::Result<Option<String>> {
+    let Some(path) = path else {
+        return Ok(inline);
+    };
+    let content = std::fs::read_to_string(&path).map_err(|e| {
+        error::KaidoError::InvalidOption(format!("Cannot read spec file '{}': {}", path, e))
+    })?;
+    Ok(Some(content.lines().map(str::trim).collect()))
+}
 
-    println!("{} Generating Aiken project...", "Kaido".cyan().bold());
+/// Print the CBOR diagnostic preview of a custom validator's datum and redeemer
+fn print_cbor_preview(options: &GenerateOptions) -> error::Result<()> {
+    if !options.template.is_composed() {
+        return Err(error::KaidoError::InvalidOption(
+            "--preview-cbor requires --template custom or token-lock".to_string(),
+        ));
+    }
+    for (label, diagnostic) in cbor::preview(options)? {
+        println!("{} {}", format!("{label}:").bold(), diagnostic);
+    }
+    Ok(())
+}
+
+fn print_tests_preview(options: &GenerateOptions) -> error::Result<()> {
+    if !options.template.is_composed() {
+        return Err(error::KaidoError::InvalidOption(
+            "--preview-tests requires --template custom or token-lock".to_string(),
+        ));
+    }
+    let tests = compose::preview_tests(options)?;
+    for test in &tests {
+        println!("{}\n", test);
+    }
+    println!(
+        "{} {} test(s), {} expected to fail",
+        "Tests:".bold(),
+        tests.len(),
+        tests.iter().filter(|t| is_failing_test(t)).count()
+    );
+    Ok(())
+}
+
+/// Whether a generated Aiken test is declared `fail` (a negative case)
+fn is_failing_test(test: &str) -> bool {
+    test.lines()
+        .next()
+        .is_some_and(|header| header.trim_end().ends_with("fail {"))
+}
 
-    let options = match template_arg {
-        TemplateArg::Mint => {
-            let tn = token_name.unwrap_or_else(|| project_name.to_string());
-            let an = asset_name.unwrap_or_else(|| tn.to_uppercase().replace(' ', "_"));
-            GenerateOptions::simple_mint(namespace, project_name, &tn, &an, time_lock)
-        }
-        TemplateArg::Vesting => {
-            GenerateOptions::vesting(namespace, project_name, cancellable, partial_claim)
-        }
-        TemplateArg::Escrow => GenerateOptions::escrow(namespace, project_name),
-        TemplateArg::Treasury => GenerateOptions::multisig_treasury(namespace, project_name),
-        TemplateArg::Marketplace => GenerateOptions::nft_marketplace(namespace, project_name),
-        TemplateArg::Staking => GenerateOptions::staking_pool(namespace, project_name),
-        TemplateArg::Oracle => GenerateOptions::oracle_settlement(namespace, project_name),
-        TemplateArg::Referral => GenerateOptions::referral_system(namespace, project_name),
-        TemplateArg::Dex => GenerateOptions::dex_pool(namespace, project_name),
-        TemplateArg::Lending => GenerateOptions::lending_pool(namespace, project_name),
-        TemplateArg::Governance => GenerateOptions::dao_governance(namespace, project_name),
-        TemplateArg::Streaming => GenerateOptions::streaming_payments(namespace, project_name),
-        TemplateArg::Custom => {
-            // Validate purpose
-            if purpose != "spend" && purpose != "mint" {
-                return Err(error::KaidoError::InvalidOption(format!(
-                    "Invalid purpose '{}'. Must be 'spend' or 'mint'",
-                    purpose
-                )));
+/// Print the parameters each validator must be applied with, in application order
+fn print_validator_params(options: &GenerateOptions, settings: &RunSettings) -> error::Result<()> {
+    let validators = project_generator(settings)?.validator_params(options)?;
+    for validator in &validators {
+        println!("{}", validator.path.bold());
+        if validator.params.is_empty() {
+            println!("  (no parameters)");
+        }
+        for (i, param) in validator.params.iter().enumerate() {
+            match &param.description {
+                Some(description) => println!(
+                    "  {}. {}: {} — {}",
+                    i + 1,
+                    param.name,
+                    param.aiken_type,
+                    description
+                ),
+                None => println!("  {}. {}: {}", i + 1, param.name, param.aiken_type),
             }
+        }
+    }
+    Ok(())
+}
 
-            // Parse features
-            let parsed_features = features::parse_features(&feature_strs)?;
-            let resolved = features::compose::resolve_features(&parsed_features, purpose)?;
-            let feature_names: Vec<String> =
-                resolved.iter().map(|f| f.name().to_string()).collect();
-
-            // Parse datum fields
-            let datum_fields = if let Some(ref d) = datum {
-                features::types::parse_datum_fields(d)?
-            } else if purpose == "spend" {
-                return Err(error::KaidoError::InvalidOption(
-                    "--datum is required for spend-purpose custom validators".to_string(),
-                ));
-            } else {
-                vec![]
-            };
+/// Print a unified diff of the rendered project (and SDK with `--sdk`) against `dir`
+fn print_project_diff(
+    options: &GenerateOptions,
+    dir: &Path,
+    settings: &RunSettings,
+) -> error::Result<()> {
+    let gen = project_generator(settings)?;
+    let mut files = gen.render(options)?.files;
+    if settings.sdk {
+        files.extend(
+            gen.render_sdk_lang(options, sdk_lang_from_arg(settings.sdk_lang))?
+                .files,
+        );
+    }
 
-            // Parse redeemer actions
-            let redeemer_actions = if let Some(ref r) = redeemer {
-                features::types::parse_redeemer_actions(r)?
+    let diffs = diff::diff_project(&files, dir)?;
+    for file in &diffs {
+        for line in file.unified.lines() {
+            if line.starts_with("+++") || line.starts_with("---") {
+                println!("{}", line.bold());
+            } else if line.starts_with('+') {
+                println!("{}", line.green());
+            } else if line.starts_with('-') {
+                println!("{}", line.red());
+            } else if line.starts_with("@@") {
+                println!("{}", line.cyan());
             } else {
-                return Err(error::KaidoError::InvalidOption(
-                    "--redeemer is required for custom validators".to_string(),
-                ));
-            };
+                println!("{}", line);
+            }
+        }
+    }
 
-            // Validate features against types
-            features::types::validate_features_against_types(&resolved, &datum_fields, purpose)?;
+    let count = |status: DiffStatus| diffs.iter().filter(|d| d.status == status).count();
+    println!(
+        "{} {} changed, {} added, {} removed, {} unchanged (nothing written)",
+        "Diff".cyan().bold(),
+        count(DiffStatus::Changed),
+        count(DiffStatus::Added),
+        count(DiffStatus::Removed),
+        count(DiffStatus::Unchanged)
+    );
+    print_template_changes(options.template, dir, settings.since.as_deref())
+}
 
-            GenerateOptions::custom(
-                namespace,
-                project_name,
-                purpose,
-                datum_fields,
-                redeemer_actions,
-                feature_names,
-            )
+/// After a `--diff-against`, summarize why the template's output changed: the changelog entries
+/// between `since` (or the kaido version the project's validator headers record) and this release
+fn print_template_changes(
+    template: Template,
+    dir: &Path,
+    since: Option<&str>,
+) -> error::Result<()> {
+    let recorded = || -> error::Result<Option<String>> {
+        let mut validators = lint::relative_files(dir, Path::new("validators"))?;
+        validators.sort();
+        for path in validators {
+            let content = std::fs::read_to_string(dir.join(path))?;
+            if let Some(version) = recorded_kaido_version(&content) {
+                return Ok(Some(version.to_string()));
+            }
         }
+        Ok(None)
     };
+    let since = match since {
+        Some(version) => version.to_string(),
+        None => match recorded()? {
+            Some(version) => version,
+            None => {
+                println!(
+                    "{} no kaido version recorded in {}; pass --since <VERSION> to list template changes",
+                    "Changes".cyan().bold(),
+                    dir.join("validators").display()
+                );
+                return Ok(());
+            }
+        },
+    };
+    if since == KAIDO_VERSION {
+        return Ok(());
+    }
+    let changes = template.changes_since(&since).ok_or_else(|| {
+        error::KaidoError::InvalidOption(format!(
+            "'{}' is not a kaido version (expected major.minor.patch)",
+            since
+        ))
+    })?;
+    if changes.is_empty() {
+        println!(
+            "{} no recorded {} template changes since kaido {}",
+            "Changes".cyan().bold(),
+            template.slug(),
+            since
+        );
+        return Ok(());
+    }
+    println!(
+        "{} {} template changes since kaido {}:",
+        "Changes".cyan().bold(),
+        template.slug(),
+        since
+    );
+    for change in changes {
+        println!("  {} {}", change.version.bold(), change.note);
+    }
+    Ok(())
+}
+
+fn read_stdin_json() -> error::Result<serde_json::Value> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    serde_json::from_str(&input)
+        .map_err(|e| error::KaidoError::InvalidOption(format!("Invalid JSON on stdin: {}", e)))
+}
+
+/// Build generate options from a JSON object on stdin (same shape as the MCP/WASM input)
+fn options_from_stdin() -> error::Result<GenerateOptions> {
+    build_options_from_json(&read_stdin_json()?)
+}
+
+/// Print the `--validate-only` outcome; fails if any error was collected
+fn report_validation(errors: &[String]) -> error::Result<()> {
+    if errors.is_empty() {
+        println!("{} options are valid", "OK".green().bold());
+        return Ok(());
+    }
+    for e in errors {
+        eprintln!("  {} {}", "-".red(), e);
+    }
+    Err(error::KaidoError::InvalidOption(format!(
+        "{} validation error(s)",
+        errors.len()
+    )))
+}
+
+/// Write, SDK and verification settings shared by every project of a generate run
+struct RunSettings {
+    overwrite_policy: OverwritePolicyArg,
+    output_format: OutputFormatArg,
+    template_dir: Option<String>,
+    strict: bool,
+    sdk: bool,
+    sdk_lang: SdkLangArg,
+    skip_verify: bool,
+    fmt: bool,
+    keep_going: bool,
+    max_findings: Option<usize>,
+    check_only_changed: bool,
+    json_report: Option<String>,
+    emit_blueprint_types: bool,
+    show_stats: bool,
+    stats_json: bool,
+    /// Warnings for template-specific flags the chosen template ignores
+    ignored_options: Diagnostics,
+    /// kaido version `--diff-against` lists template changes since (else the recorded one)
+    since: Option<String>,
+}
+
+/// Compile the templates, overridden by those in `--template-dir` when given
+fn project_generator(settings: &RunSettings) -> error::Result<ProjectGenerator> {
+    Ok(match &settings.template_dir {
+        Some(dir) => ProjectGenerator::new_with_overrides(Path::new(dir))?,
+        None => ProjectGenerator::new()?,
+    })
+}
 
-    let gen = ProjectGenerator::new()?;
-    let result = gen.render(&options)?;
+/// Compile the templates and generate a single project
+fn generate_one(
+    options: GenerateOptions,
+    output: Option<String>,
+    into_workspace: Option<String>,
+    settings: &RunSettings,
+) -> error::Result<()> {
+    let mut stats = GenerationStats::new();
+    let gen = stats.time("template compilation", || project_generator(settings))?;
+    run_generate(options, &gen, stats, output, into_workspace, settings)
+}
+
+/// Generate one project per name into `<output>/<name>`, sharing a single compiled generator.
+/// Every project is attempted; the batch fails if any of them failed.
+fn run_batch(
+    template: Template,
+    namespace: &str,
+    project_names: &[String],
+    extras: &OptionExtras,
+    output: Option<String>,
+    settings: &RunSettings,
+) -> error::Result<()> {
+    // Validate every name up front so a typo doesn't leave a half-generated batch
+    let mut seen = HashSet::new();
+    let mut batch = Vec::new();
+    for name in project_names {
+        if !seen.insert(name.as_str()) {
+            return Err(error::KaidoError::InvalidOption(format!(
+                "Duplicate project name '{}' in --project-names",
+                name
+            )));
+        }
+        batch.push(build_options(template, namespace, name, extras)?);
+    }
+    if !settings.skip_verify {
+        ensure_verifiers()?;
+    }
+
+    let root = PathBuf::from(output.unwrap_or_else(|| ".".to_string()));
+    // Compilation is shared, so only the first project's --stats includes it
+    let mut stats = GenerationStats::new();
+    let gen = stats.time("template compilation", || project_generator(settings))?;
+
+    let mut outcomes = Vec::new();
+    for options in batch {
+        let name = options.project_name.clone();
+        let dir = root.join(&name);
+        let result = run_generate(
+            options,
+            &gen,
+            std::mem::take(&mut stats),
+            Some(dir.display().to_string()),
+            None,
+            settings,
+        );
+        if let Err(e) = &result {
+            eprintln!("{} {}: {}", "Error:".red().bold(), name, e);
+        }
+        outcomes.push((dir, result));
+        println!();
+    }
+
+    let failed = outcomes.iter().filter(|(_, r)| r.is_err()).count();
+    println!(
+        "{} {}/{} projects generated",
+        "Batch".cyan().bold(),
+        outcomes.len() - failed,
+        outcomes.len()
+    );
+    for (dir, result) in &outcomes {
+        match result {
+            Ok(()) => println!("  {} {}", "OK".green().bold(), dir.display()),
+            Err(e) => println!("  {} {}: {}", "FAIL".red().bold(), dir.display(), e),
+        }
+    }
+
+    if failed > 0 {
+        return Err(error::KaidoError::InvalidOption(format!(
+            "{} of {} projects failed",
+            failed,
+            outcomes.len()
+        )));
+    }
+    Ok(())
+}
+
+/// The on-disk layout for `--output-format`. A flat directory is not an aiken project, so it
+/// can't be verified, merged into a workspace or share a manifest with the SDK's files.
+fn output_layout(settings: &RunSettings, into_workspace: bool) -> error::Result<OutputLayout> {
+    if settings.output_format == OutputFormatArg::Tree {
+        return Ok(OutputLayout::Tree);
+    }
+    let unsupported = if !settings.skip_verify {
+        Some("verification (pass --skip-verify)")
+    } else if settings.sdk {
+        Some("--sdk")
+    } else if into_workspace {
+        Some("--into-workspace")
+    } else {
+        None
+    };
+    match unsupported {
+        Some(what) => Err(error::KaidoError::InvalidOption(format!(
+            "--output-format flat does not support {}",
+            what
+        ))),
+        None => Ok(OutputLayout::Flat),
+    }
+}
+
+/// Verification needs both aiken and aikido on PATH
+fn ensure_verifiers() -> error::Result<()> {
+    if !AikenVerifier::is_available() {
+        return Err(error::KaidoError::InvalidOption(
+            "aiken not found on PATH (required unless --skip-verify is set)".to_string(),
+        ));
+    }
+    if !AikidoVerifier::is_available() {
+        return Err(error::KaidoError::InvalidOption(
+            "aikido not found on PATH (required unless --skip-verify is set)".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn run_generate(
+    mut options: GenerateOptions,
+    gen: &ProjectGenerator,
+    mut stats: GenerationStats,
+    output: Option<String>,
+    into_workspace: Option<String>,
+    settings: &RunSettings,
+) -> error::Result<()> {
+    let workspace = into_workspace
+        .map(|root| Workspace::open(Path::new(&root)))
+        .transpose()?;
+    if let Some(ws) = &workspace {
+        GenerateOptions::validate_namespace_and_project(&ws.namespace, &options.project_name)
+            .map_err(error::KaidoError::InvalidOption)?;
+    }
+
+    let layout = output_layout(settings, workspace.is_some())?;
+
+    let output_dir = match &workspace {
+        Some(ws) => ws.root.clone(),
+        None => PathBuf::from(output.unwrap_or_else(|| options.project_name.clone())),
+    };
+
+    println!("{} Generating Aiken project...", "Kaido".cyan().bold());
+
+    // lib/ modules live under the workspace's own namespace
+    if let Some(ws) = &workspace {
+        options.namespace = ws.namespace.clone();
+    }
+
+    let mut result = stats.time("render", || gen.render(&options))?;
+    result.diagnostics.extend(settings.ignored_options.clone());
+    if workspace.is_some() {
+        Workspace::retain_generated_files(&mut result);
+    }
 
     println!(
         "  {} {}",
@@ -170,24 +781,50 @@ fn run_generate(
         options.template.description()
     );
     println!("  {} {}", "Output:".white().bold(), output_dir.display());
+    for warning in &result.diagnostics.warnings {
+        println!("  {} {}", "Warning:".yellow().bold(), warning);
+    }
     println!();
+    result.diagnostics.check_strict(settings.strict)?;
 
     // Write files to disk
-    let paths = writer::write_project(&result, &output_dir)?;
-    for path in &paths {
-        println!("  {} {}", "+".green(), path.display());
+    let policy = policy_from_arg(settings.overwrite_policy);
+    // Tree writes stream the files so each is dropped once on disk (a batch holds one project
+    // at a time); `never` and the flat layout look at every file before writing any
+    let written = stats.time("write", || {
+        if layout == OutputLayout::Tree && policy != OverwritePolicy::Never {
+            writer::write_project_streaming(std::mem::take(&mut result.files), &output_dir, policy)
+        } else {
+            writer::write_project(&result, &output_dir, policy, layout)
+        }
+    })?;
+    print_written(&written);
+    let contract_changed = writer::contract_changed(&written);
+
+    if let Some(ws) = &workspace {
+        if ws.ensure_stdlib_dependency(&options.stdlib_version)? {
+            println!(
+                "  {} {} (added aiken-lang/stdlib dependency)",
+                "~".yellow(),
+                ws.root.join("aiken.toml").display()
+            );
+        }
     }
 
     // SDK generation
-    if sdk {
+    if settings.sdk {
+        let (lang, lang_label) = match settings.sdk_lang {
+            SdkLangArg::Typescript => (SdkLang::TypeScript, "TypeScript"),
+            SdkLangArg::Python => (SdkLang::Python, "Python"),
+        };
         println!();
-        println!("{} Generating TypeScript SDK...", "SDK".blue().bold());
-        match gen.render_sdk(&options) {
+        println!("{} Generating {} SDK...", "SDK".blue().bold(), lang_label);
+        let sdk_start = Instant::now();
+        match gen.render_sdk_lang(&options, lang) {
             Ok(sdk_result) => {
-                let sdk_paths = writer::write_project(&sdk_result, &output_dir)?;
-                for path in &sdk_paths {
-                    println!("  {} {}", "+".green(), path.display());
-                }
+                let sdk_written = writer::write_project(&sdk_result, &output_dir, policy, layout)?;
+                stats.record("sdk", sdk_start);
+                print_written(&sdk_written);
             }
             Err(e) => {
                 println!("  {} SDK generation failed: {}", "WARN".yellow().bold(), e);
@@ -198,79 +835,39 @@ fn run_generate(
     println!();
 
     // Verification
-    if !skip_verify {
-        if !AikenVerifier::is_available() {
-            return Err(error::KaidoError::InvalidOption(
-                "aiken not found on PATH (required unless --skip-verify is set)".to_string(),
-            ));
-        }
-        if !AikidoVerifier::is_available() {
-            return Err(error::KaidoError::InvalidOption(
-                "aikido not found on PATH (required unless --skip-verify is set)".to_string(),
-            ));
-        }
-
-        println!("{} Running aiken build...", "Verify".yellow().bold());
-        match AikenVerifier::build(&output_dir) {
-            Ok(()) => {
-                println!("  {} aiken build passed", "OK".green().bold());
-            }
-            Err(e) => {
-                println!("  {} aiken build failed: {}", "FAIL".red().bold(), e);
-                return Err(e);
-            }
-        }
-
-        println!("{} Running aiken check...", "Verify".yellow().bold());
-        match AikenVerifier::check(&output_dir) {
-            Ok(()) => {
-                println!(
-                    "  {} aiken check passed (all tests green)",
-                    "OK".green().bold()
-                );
-            }
-            Err(e) => {
-                println!("  {} aiken check failed: {}", "FAIL".red().bold(), e);
-                return Err(e);
-            }
-        }
-
-        println!("{} Running aikido scan...", "Audit".magenta().bold());
-        match AikidoVerifier::scan(&output_dir) {
-            Ok(result) => {
-                if result.findings.is_empty() {
-                    println!("  {} no findings", "OK".green().bold());
-                } else {
-                    println!(
-                        "  {} {} finding(s) ({} high/critical)",
-                        if result.high_or_critical > 0 {
-                            "WARN".red().bold()
-                        } else {
-                            "INFO".yellow().bold()
-                        },
-                        result.findings.len(),
-                        result.high_or_critical,
-                    );
-                    for f in &result.findings {
-                        println!(
-                            "    [{}] {}: {}",
-                            f.severity.to_uppercase(),
-                            f.detector,
-                            f.message
-                        );
-                    }
-                }
-            }
-            Err(e) => {
-                println!("  {} aikido scan failed: {}", "FAIL".red().bold(), e);
-                return Err(e);
-            }
-        }
-    } else {
+    if settings.skip_verify {
         println!(
             "  {} verification skipped (--skip-verify)",
             "SKIP".yellow().bold()
         );
+    } else if settings.check_only_changed && !contract_changed {
+        println!(
+            "  {} verification skipped (no contract changes)",
+            "SKIP".yellow().bold()
+        );
+    } else {
+        ensure_verifiers()?;
+
+        if settings.fmt {
+            println!("{} Running aiken fmt...", "Format".blue().bold());
+            stats.time("aiken fmt", || AikenVerifier::format(&output_dir))?;
+            println!("  {} aiken fmt", "OK".green().bold());
+        }
+
+        let mut report = VerificationReport::new();
+        let outcome = verify_generated(
+            &output_dir,
+            settings.keep_going,
+            settings.max_findings,
+            &mut stats,
+            &mut report,
+        );
+        finish_report(&mut report, &outcome, settings.json_report.as_deref())?;
+        outcome?;
+    }
+
+    if settings.emit_blueprint_types {
+        write_blueprint_types(&output_dir, options.template, policy)?;
     }
 
     println!();
@@ -285,12 +882,108 @@ fn run_generate(
     println!("    aiken check    # run tests");
     println!("    aiken build    # compile to plutus.json");
 
+    if settings.show_stats {
+        println!();
+        stats.print(settings.stats_json);
+    }
+
     Ok(())
 }
 
-fn run_list() {
-    use kaido_core::templates::Template;
+/// Print the findings of an aikido scan, by severity, then the suppressions applied
+fn print_scan_result(result: &AikidoResult) {
+    if result.findings.is_empty() {
+        println!("  {} no findings", "OK".green().bold());
+    } else {
+        println!(
+            "  {} {} finding(s) ({} high/critical)",
+            if result.high_or_critical > 0 {
+                "WARN".red().bold()
+            } else {
+                "INFO".yellow().bold()
+            },
+            result.findings.len(),
+            result.high_or_critical,
+        );
+        print_severity_breakdown(&result.by_severity);
+        for f in &result.findings {
+            println!(
+                "    [{}] {}: {}",
+                f.severity.to_uppercase(),
+                f.detector,
+                f.message
+            );
+        }
+    }
+    print_suppressions(result);
+}
 
+/// List the `.kaido-suppress` entries and what they downgraded, so accepted findings stay auditable
+fn print_suppressions(result: &AikidoResult) {
+    if result.suppressions.is_empty() {
+        return;
+    }
+    println!(
+        "  {} {} suppression(s) from {}",
+        "INFO".yellow().bold(),
+        result.suppressions.len(),
+        SUPPRESS_FILE
+    );
+    for f in result.suppressed() {
+        println!(
+            "    suppressed [{}] {}: {} (by '{}')",
+            f.original_severity
+                .as_deref()
+                .unwrap_or_default()
+                .to_uppercase(),
+            f.detector,
+            f.message,
+            f.suppressed_by.as_deref().unwrap_or_default()
+        );
+    }
+}
+
+/// Print "critical: N  high: N  ..." with each severity in its own color
+fn print_severity_breakdown(by_severity: &BTreeMap<String, usize>) {
+    const ORDER: [&str; 5] = ["critical", "high", "medium", "low", "info"];
+
+    let mut parts: Vec<String> = ORDER
+        .iter()
+        .map(|sev| {
+            let count = by_severity.get(*sev).copied().unwrap_or(0);
+            let label = format!("{}: {}", sev, count);
+            match *sev {
+                "critical" => label.red().bold().to_string(),
+                "high" => label.red().to_string(),
+                "medium" => label.yellow().to_string(),
+                "low" => label.blue().to_string(),
+                _ => label.dimmed().to_string(),
+            }
+        })
+        .collect();
+    // Severities outside the known set still show up, uncolored
+    parts.extend(
+        by_severity
+            .iter()
+            .filter(|(sev, _)| !ORDER.contains(&sev.as_str()))
+            .map(|(sev, count)| format!("{}: {}", sev, count)),
+    );
+    println!("    {}", parts.join("  "));
+}
+
+fn print_written(written: &[(PathBuf, WriteStatus)]) {
+    for (path, status) in written {
+        match status {
+            WriteStatus::Created => println!("  {} {}", "+".green(), path.display()),
+            WriteStatus::Updated => println!("  {} {}", "~".yellow(), path.display()),
+            WriteStatus::Unchanged => {
+                println!("  {} {} (unchanged)", "=".dimmed(), path.display())
+            }
+        }
+    }
+}
+
+fn run_list() {
     println!("{} Available Templates", "Kaido".cyan().bold());
     println!();
 
@@ -300,13 +993,60 @@ fn run_list() {
             template.slug().white().bold(),
             template.description()
         );
+        let flags: Vec<String> = template
+            .applicable_options()
+            .iter()
+            .map(|o| format!("--{}", o.name.replace('_', "-")))
+            .collect();
+        if !flags.is_empty() {
+            println!("      options: {}", flags.join(" ").dimmed());
+        }
     }
 
     println!();
     println!("Usage: kaido generate --template <TEMPLATE> --namespace <NS> --project-name <NAME>");
 }
 
-fn run_verify(path: &str) -> error::Result<()> {
+fn run_schema() {
+    let schema = kaido_core::schema::options_schema();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&schema).unwrap_or_default()
+    );
+}
+
+fn run_features(matrix: bool) {
+    if matrix {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&kaido_core::features::feature_matrix())
+                .unwrap_or_default()
+        );
+        return;
+    }
+
+    println!("{} Composable Features", "Kaido".cyan().bold());
+    println!();
+    for feature in Feature::all() {
+        println!(
+            "  {} [{}] {}",
+            feature.name().white().bold(),
+            feature.purpose_restriction().unwrap_or("any"),
+            feature.description()
+        );
+    }
+    println!();
+    println!("Usage: kaido generate --template custom --features <A,B,...> (see --matrix for compatibility)");
+}
+
+fn run_verify(
+    path: &str,
+    fix_fmt: bool,
+    keep_going: bool,
+    max_findings: Option<usize>,
+    json_report: Option<String>,
+    audit_report: Option<String>,
+) -> error::Result<()> {
     let project_dir = PathBuf::from(path);
 
     if !project_dir.join("aiken.toml").exists() {
@@ -328,58 +1068,296 @@ fn run_verify(path: &str) -> error::Result<()> {
 
     println!("{} Verifying project at {}", "Kaido".cyan().bold(), path);
 
+    if fix_fmt {
+        println!("{} Running aiken fmt...", "Format".blue().bold());
+        let reformatted = format_in_place(&project_dir)?;
+        println!(
+            "  {} aiken fmt ({} file(s) reformatted)",
+            "OK".green().bold(),
+            reformatted.len()
+        );
+        for path in &reformatted {
+            println!("  {} {}", "~".yellow(), path.display());
+        }
+    }
+
+    let mut report = VerificationReport::new();
+    let outcome = verify_existing(&project_dir, keep_going, max_findings, &mut report);
+    finish_report(&mut report, &outcome, json_report.as_deref())?;
+    if let Some(audit_path) = &audit_report {
+        let config = std::fs::read_to_string(project_dir.join("aiken.toml"))?;
+        report.write_markdown(Path::new(audit_path), workspace::package_name(&config))?;
+        println!("  Audit summary written to {}", audit_path);
+    }
+    outcome?;
+
+    println!();
+    println!("{} All checks passed!", "Done!".green().bold());
+
+    Ok(())
+}
+
+/// Run `aiken fmt` on the project and return the .ak files it rewrote, relative to the project
+fn format_in_place(project_dir: &Path) -> error::Result<Vec<PathBuf>> {
+    let snapshot = || -> error::Result<BTreeMap<PathBuf, String>> {
+        let mut sources = BTreeMap::new();
+        for source_dir in ["validators", "lib"] {
+            for rel in lint::relative_files(project_dir, Path::new(source_dir))? {
+                if rel.extension().is_some_and(|ext| ext == "ak") {
+                    let content = std::fs::read_to_string(project_dir.join(&rel))?;
+                    sources.insert(rel, content);
+                }
+            }
+        }
+        Ok(sources)
+    };
+    let before = snapshot()?;
+    AikenVerifier::format(project_dir)?;
+    let after = snapshot()?;
+    Ok(after
+        .into_iter()
+        .filter(|(rel, content)| before.get(rel) != Some(content))
+        .map(|(rel, _)| rel)
+        .collect())
+}
+
+fn run_lint(path: &str) -> error::Result<()> {
+    let project_dir = PathBuf::from(path);
+
+    if !project_dir.join("aiken.toml").exists() {
+        return Err(error::KaidoError::InvalidOption(
+            "No aiken.toml found — not an Aiken project".to_string(),
+        ));
+    }
+
+    println!("{} Linting project at {}", "Kaido".cyan().bold(), path);
+
+    let warnings = lint::lint_project(&project_dir)?;
+    if warnings.is_empty() {
+        println!("  {} no issues found", "OK".green().bold());
+        return Ok(());
+    }
+    for w in &warnings {
+        println!("  {} {}", "WARN".yellow().bold(), w);
+    }
+    Err(error::KaidoError::InvalidOption(format!(
+        "{} lint warning(s)",
+        warnings.len()
+    )))
+}
+
+fn run_init_aikido(
+    path: &str,
+    overwrite_policy: OverwritePolicyArg,
+    scan: bool,
+) -> error::Result<()> {
+    let project_dir = PathBuf::from(path);
+
+    if !project_dir.join("aiken.toml").exists() {
+        return Err(error::KaidoError::InvalidOption(
+            "No aiken.toml found — not an Aiken project".to_string(),
+        ));
+    }
+    if scan && !AikidoVerifier::is_available() {
+        return Err(error::KaidoError::InvalidOption(
+            "aikido not found on PATH (required by --scan)".to_string(),
+        ));
+    }
+
+    println!(
+        "{} Writing aikido configuration to {}",
+        "Kaido".cyan().bold(),
+        path
+    );
+    let config = ProjectGenerator::new()?.render_aikido_config()?;
+    let written = writer::write_files(&[config], &project_dir, policy_from_arg(overwrite_policy))?;
+    print_written(&written);
+
+    if scan {
+        println!();
+        println!("{} Running aikido scan...", "Audit".magenta().bold());
+        print_scan_result(&AikidoVerifier::scan(&project_dir)?);
+    }
+
+    Ok(())
+}
+
+/// Run build, check and scan on an existing project, filling `report`
+fn verify_existing(
+    project_dir: &Path,
+    keep_going: bool,
+    max_findings: Option<usize>,
+    report: &mut VerificationReport,
+) -> error::Result<()> {
+    let mut failures = StageFailures::new(keep_going);
+
     println!("  Running aiken build...");
-    AikenVerifier::build(&project_dir)?;
-    println!("  {} aiken build", "OK".green().bold());
+    let build = AikenVerifier::build(project_dir);
+    report.aiken_build = StepStatus::of(&build);
+    match build {
+        Ok(()) => println!("  {} aiken build", "OK".green().bold()),
+        Err(e) => {
+            println!("  {} aiken build", "FAIL".red().bold());
+            failures.record(e)?;
+        }
+    }
 
     println!("  Running aiken check...");
-    AikenVerifier::check(&project_dir)?;
-    println!("  {} aiken check", "OK".green().bold());
+    let check = AikenVerifier::check(project_dir);
+    report.aiken_check = StepStatus::of(&check);
+    match check {
+        Ok(()) => println!("  {} aiken check", "OK".green().bold()),
+        Err(e) => {
+            println!("  {} aiken check", "FAIL".red().bold());
+            failures.record(e)?;
+        }
+    }
 
-    if let Some(version) = AikenVerifier::version() {
+    if let Some(version) = &report.aiken_version {
         println!("  Compiler: {}", version);
     }
 
     // Aikido static analysis
     println!();
     println!("{} Running aikido scan...", "Audit".magenta().bold());
-    match AikidoVerifier::scan(&project_dir) {
+    match AikidoVerifier::scan(project_dir) {
         Ok(result) => {
-            if result.findings.is_empty() {
-                println!("  {} no findings", "OK".green().bold());
-            } else {
-                println!(
-                    "  {} {} finding(s) ({} high/critical)",
-                    if result.high_or_critical > 0 {
-                        "WARN".red().bold()
-                    } else {
-                        "INFO".yellow().bold()
-                    },
-                    result.findings.len(),
-                    result.high_or_critical,
-                );
-                for f in &result.findings {
-                    println!(
-                        "    [{}] {}: {}",
-                        f.severity.to_uppercase(),
-                        f.detector,
-                        f.message
-                    );
-                }
+            if let Err(e) = record_scan_result(result, max_findings, report) {
+                failures.record(e)?;
             }
         }
         Err(e) => {
+            report.aikido_scan = StepStatus::Failed;
             println!("  {} aikido scan failed: {}", "FAIL".red().bold(), e);
-            return Err(e);
+            failures.record(e)?;
         }
     }
 
-    if let Some(version) = AikidoVerifier::version() {
+    if let Some(version) = &report.aikido_version {
         println!("  Analyzer: {}", version);
     }
 
+    failures.finish()
+}
+
+/// Run build, check and scan on a freshly generated project, filling `report`
+fn verify_generated(
+    output_dir: &Path,
+    keep_going: bool,
+    max_findings: Option<usize>,
+    stats: &mut GenerationStats,
+    report: &mut VerificationReport,
+) -> error::Result<()> {
+    let mut failures = StageFailures::new(keep_going);
+
+    println!("{} Running aiken build...", "Verify".yellow().bold());
+    let build = stats.time("aiken build", || AikenVerifier::build(output_dir));
+    report.aiken_build = StepStatus::of(&build);
+    match build {
+        Ok(()) => {
+            println!("  {} aiken build passed", "OK".green().bold());
+        }
+        Err(e) => {
+            println!("  {} aiken build failed: {}", "FAIL".red().bold(), e);
+            failures.record(e)?;
+        }
+    }
+
+    println!("{} Running aiken check...", "Verify".yellow().bold());
+    let check = stats.time("aiken check", || AikenVerifier::check(output_dir));
+    report.aiken_check = StepStatus::of(&check);
+    match check {
+        Ok(()) => {
+            println!(
+                "  {} aiken check passed (all tests green)",
+                "OK".green().bold()
+            );
+        }
+        Err(e) => {
+            println!("  {} aiken check failed: {}", "FAIL".red().bold(), e);
+            failures.record(e)?;
+        }
+    }
+
+    println!("{} Running aikido scan...", "Audit".magenta().bold());
+    match stats.time("aikido scan", || AikidoVerifier::scan(output_dir)) {
+        Ok(result) => {
+            if let Err(e) = record_scan_result(result, max_findings, report) {
+                failures.record(e)?;
+            }
+        }
+        Err(e) => {
+            report.aikido_scan = StepStatus::Failed;
+            println!("  {} aikido scan failed: {}", "FAIL".red().bold(), e);
+            failures.record(e)?;
+        }
+    }
+
+    failures.finish()
+}
+
+/// Print a finished scan, record it in `report` and apply the `--max-findings` gate
+fn record_scan_result(
+    result: AikidoResult,
+    max_findings: Option<usize>,
+    report: &mut VerificationReport,
+) -> error::Result<()> {
+    print_scan_result(&result);
+    let gate = result.check_max_findings(max_findings);
+    report.record_scan(result);
+    if let Err(e) = &gate {
+        report.aikido_scan = StepStatus::Failed;
+        println!("  {} {}", "FAIL".red().bold(), e);
+    }
+    gate
+}
+
+/// Write TypeScript types for the validators in the plutus.json that `aiken build` left behind
+fn write_blueprint_types(
+    output_dir: &Path,
+    template: Template,
+    policy: OverwritePolicy,
+) -> error::Result<()> {
+    let plutus_json = std::fs::read_to_string(output_dir.join("plutus.json")).map_err(|e| {
+        error::KaidoError::InvalidOption(format!(
+            "--emit-blueprint-types needs the plutus.json from aiken build: {}",
+            e
+        ))
+    })?;
+    let types = blueprint::blueprint_types(&plutus_json)?;
+
     println!();
-    println!("{} All checks passed!", "Done!".green().bold());
+    println!("{} Generating blueprint types...", "SDK".blue().bold());
+    let result = RenderResult {
+        files: vec![GeneratedFile {
+            path: blueprint::BLUEPRINT_TYPES_PATH.to_string(),
+            content: types,
+        }],
+        template,
+        diagnostics: Default::default(),
+        meta: Default::default(),
+    };
+    print_written(&writer::write_project(
+        &result,
+        output_dir,
+        policy,
+        OutputLayout::Tree,
+    )?);
+    Ok(())
+}
 
+/// Record the verification error (if any) and write the report when requested
+fn finish_report(
+    report: &mut VerificationReport,
+    outcome: &error::Result<()>,
+    path: Option<&str>,
+) -> error::Result<()> {
+    if let Err(e) = outcome {
+        report.error = Some(e.to_string());
+    }
+    if let Some(path) = path {
+        report.write(Path::new(path))?;
+        println!("  Report written to {}", path);
+    }
     Ok(())
 }
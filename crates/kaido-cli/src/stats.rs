@@ -0,0 +1,93 @@
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+
+/// Local timing breakdown for `kaido generate --stats`.
+///
+/// Purely in-process `Instant` deltas — nothing is sent anywhere.
+#[derive(Debug, Default)]
+pub struct GenerationStats {
+    phases: Vec<(String, Duration)>,
+}
+
+impl GenerationStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a phase that started at `start` and ends now
+    pub fn record(&mut self, phase: &str, start: Instant) {
+        self.phases.push((phase.to_string(), start.elapsed()));
+    }
+
+    /// Run `f`, recording its duration under `phase`
+    pub fn time<T>(&mut self, phase: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let out = f();
+        self.record(phase, start);
+        out
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, d)| *d).sum()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let phases: Vec<serde_json::Value> = self
+            .phases
+            .iter()
+            .map(|(name, d)| {
+                serde_json::json!({
+                    "phase": name,
+                    "ms": millis(*d),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "phases": phases,
+            "total_ms": millis(self.total()),
+        })
+    }
+
+    /// Print the breakdown as an indented summary, or as JSON
+    pub fn print(&self, json: bool) {
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&self.to_json()).unwrap_or_default()
+            );
+            return;
+        }
+
+        let width = self.phases.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
+        println!("{} Timing breakdown", "Stats".blue().bold());
+        for (name, d) in &self.phases {
+            println!("  {:<width$}  {:>10.3} ms", name, millis(*d));
+        }
+        println!("  {:<width$}  {:>10.3} ms", "total", millis(self.total()));
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GenerationStats;
+
+    #[test]
+    fn records_phases_in_order() {
+        let mut stats = GenerationStats::new();
+        let value = stats.time("render", || 42);
+        stats.time("write", || ());
+
+        assert_eq!(value, 42);
+        let json = stats.to_json();
+        let phases = json["phases"].as_array().unwrap();
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[0]["phase"], "render");
+        assert_eq!(phases[1]["phase"], "write");
+        assert!(json["total_ms"].as_f64().unwrap() >= 0.0);
+    }
+}
@@ -1,7 +1,14 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
 use std::path::Path;
 use std::process::Command;
 
 use kaido_core::error::{KaidoError, Result};
+use serde::Serialize;
+
+/// Project-root file listing accepted aikido findings, one `<detector> [<location>]` per line
+pub const SUPPRESS_FILE: &str = ".kaido-suppress";
 
 /// Raw aikido JSON output
 #[derive(Debug, serde::Deserialize)]
@@ -13,7 +20,7 @@ struct AikidoOutput {
 }
 
 /// Represents a single aikido finding
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct AikidoFinding {
     pub detector: String,
@@ -24,6 +31,89 @@ pub struct AikidoFinding {
     pub confidence: Option<String>,
     #[serde(default)]
     pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<serde_json::Value>,
+    /// Severity reported by aikido before a suppression downgraded it to info
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_severity: Option<String>,
+    /// The `.kaido-suppress` entry that matched this finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppressed_by: Option<String>,
+}
+
+impl AikidoFinding {
+    fn is_high_or_critical(&self) -> bool {
+        self.severity.eq_ignore_ascii_case("high") || self.severity.eq_ignore_ascii_case("critical")
+    }
+
+    /// Location as written in `.kaido-suppress`: the string itself, or `<file>:<line>`
+    /// for an object location
+    fn location_label(&self) -> Option<String> {
+        match self.location.as_ref()? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Object(map) => {
+                let file = ["file", "path", "module"]
+                    .iter()
+                    .find_map(|key| map.get(*key).and_then(|v| v.as_str()))?;
+                match map.get("line").and_then(|v| v.as_u64()) {
+                    Some(line) => Some(format!("{}:{}", file, line)),
+                    None => Some(file.to_string()),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// One `.kaido-suppress` entry: a detector ID, optionally pinned to a location
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    pub detector: String,
+    pub location: Option<String>,
+}
+
+impl Suppression {
+    fn matches(&self, finding: &AikidoFinding) -> bool {
+        finding.detector == self.detector
+            && self
+                .location
+                .as_ref()
+                .is_none_or(|loc| finding.location_label().as_deref() == Some(loc.as_str()))
+    }
+}
+
+impl std::fmt::Display for Suppression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} {}", self.detector, location),
+            None => write!(f, "{}", self.detector),
+        }
+    }
+}
+
+/// Parse `.kaido-suppress` content; blank lines and `#` comments are skipped
+pub fn parse_suppressions(content: &str) -> Vec<Suppression> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            Suppression {
+                detector: parts.next().unwrap_or_default().to_string(),
+                location: parts.next().map(str::to_string),
+            }
+        })
+        .collect()
+}
+
+/// Load the project's `.kaido-suppress` file (empty if there is none)
+pub fn load_suppressions(project_dir: &Path) -> Result<Vec<Suppression>> {
+    match fs::read_to_string(project_dir.join(SUPPRESS_FILE)) {
+        Ok(content) => Ok(parse_suppressions(&content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
 }
 
 /// Aikido scan result
@@ -32,7 +122,249 @@ pub struct AikidoFinding {
 pub struct AikidoResult {
     pub findings: Vec<AikidoFinding>,
     pub high_or_critical: usize,
+    /// Finding count per lowercased severity
+    pub by_severity: BTreeMap<String, usize>,
     pub total: usize,
+    /// Entries loaded from `.kaido-suppress`
+    pub suppressions: Vec<Suppression>,
+}
+
+impl AikidoResult {
+    /// Downgrade findings matching a suppression to info and recount severities,
+    /// so accepted findings no longer count as high/critical
+    pub fn apply_suppressions(&mut self, suppressions: Vec<Suppression>) {
+        for finding in &mut self.findings {
+            if let Some(rule) = suppressions.iter().find(|s| s.matches(finding)) {
+                finding.original_severity =
+                    Some(std::mem::replace(&mut finding.severity, "info".to_string()));
+                finding.suppressed_by = Some(rule.to_string());
+            }
+        }
+        self.suppressions = suppressions;
+        self.high_or_critical = self
+            .findings
+            .iter()
+            .filter(|f| f.is_high_or_critical())
+            .count();
+        self.by_severity = count_by_severity(&self.findings);
+    }
+
+    /// Findings downgraded by a suppression
+    pub fn suppressed(&self) -> impl Iterator<Item = &AikidoFinding> {
+        self.findings.iter().filter(|f| f.suppressed_by.is_some())
+    }
+
+    /// The `--max-findings` gate: fail when more than `max_findings` findings remain,
+    /// whatever their severity. Suppressed findings don't count.
+    pub fn check_max_findings(&self, max_findings: Option<usize>) -> Result<()> {
+        let Some(max) = max_findings else {
+            return Ok(());
+        };
+        let count = self.findings.len() - self.suppressed().count();
+        if count > max {
+            return Err(KaidoError::AikidoScanFailed(format!(
+                "--max-findings gate tripped: {} finding(s) exceed the limit of {}",
+                count, max
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a single verification step
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    #[default]
+    NotRun,
+    Passed,
+    Failed,
+}
+
+impl StepStatus {
+    pub fn of<T>(result: &Result<T>) -> Self {
+        if result.is_ok() {
+            Self::Passed
+        } else {
+            Self::Failed
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::NotRun => "not run",
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Machine-readable verification result written by `--json-report`
+#[derive(Debug, Default, Serialize)]
+pub struct VerificationReport {
+    pub aiken_build: StepStatus,
+    pub aiken_check: StepStatus,
+    pub aikido_scan: StepStatus,
+    pub findings: Vec<AikidoFinding>,
+    pub high_or_critical: usize,
+    /// `.kaido-suppress` entries applied to the findings
+    pub suppressions: Vec<String>,
+    pub aiken_version: Option<String>,
+    pub aikido_version: Option<String>,
+    /// Error that stopped verification, if any
+    pub error: Option<String>,
+}
+
+impl VerificationReport {
+    pub fn new() -> Self {
+        Self {
+            aiken_version: AikenVerifier::version(),
+            aikido_version: AikidoVerifier::version(),
+            ..Self::default()
+        }
+    }
+
+    /// Record a finished aikido scan, taking ownership of its findings
+    pub fn record_scan(&mut self, result: AikidoResult) {
+        self.aikido_scan = StepStatus::Passed;
+        self.high_or_critical = result.high_or_critical;
+        self.suppressions = result.suppressions.iter().map(|s| s.to_string()).collect();
+        self.findings = result.findings;
+    }
+
+    /// Write the report as pretty-printed JSON
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| KaidoError::InvalidOption(format!("Failed to serialize report: {}", e)))?;
+        std::fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Markdown audit summary for `--audit-report`: project, tool versions, step status and a
+    /// findings table, most severe first. `package` is the aiken.toml `name` ("namespace/project").
+    pub fn to_markdown(&self, package: Option<&str>) -> String {
+        let (namespace, project) = match package.and_then(|p| p.split_once('/')) {
+            Some((namespace, project)) => (namespace, project),
+            None => ("unknown", package.unwrap_or("unknown")),
+        };
+
+        let mut md = format!("# Audit summary: {}\n\n", project);
+        md.push_str(&format!("- **Project:** {}\n", project));
+        md.push_str(&format!("- **Namespace:** {}\n", namespace));
+        md.push_str(&format!(
+            "- **aiken:** {}\n",
+            self.aiken_version.as_deref().unwrap_or("unknown")
+        ));
+        md.push_str(&format!(
+            "- **aikido:** {}\n",
+            self.aikido_version.as_deref().unwrap_or("unknown")
+        ));
+
+        md.push_str("\n## Verification\n\n| Step | Status |\n|------|--------|\n");
+        for (step, status) in [
+            ("aiken build", self.aiken_build),
+            ("aiken check", self.aiken_check),
+            ("aikido scan", self.aikido_scan),
+        ] {
+            md.push_str(&format!("| {} | {} |\n", step, status.label()));
+        }
+        if let Some(error) = &self.error {
+            md.push_str(&format!("\nVerification failed:\n\n```\n{}\n```\n", error));
+        }
+
+        md.push_str("\n## Findings\n\n");
+        if self.findings.is_empty() {
+            md.push_str("No findings.\n");
+        } else {
+            md.push_str(&format!(
+                "{} finding(s), {} high/critical.\n\n",
+                self.findings.len(),
+                self.high_or_critical
+            ));
+            md.push_str("| Severity | Detector | Message |\n|----------|----------|---------|\n");
+            let mut findings: Vec<&AikidoFinding> = self.findings.iter().collect();
+            findings.sort_by_key(|f| severity_rank(&f.severity));
+            for f in findings {
+                let mut message = f.message.clone();
+                if let (Some(original), Some(rule)) = (&f.original_severity, &f.suppressed_by) {
+                    message.push_str(&format!(" (suppressed {} by '{}')", original, rule));
+                }
+                md.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    table_cell(&f.severity),
+                    table_cell(&f.detector),
+                    table_cell(&message)
+                ));
+            }
+        }
+
+        if !self.suppressions.is_empty() {
+            md.push_str(&format!("\n## Suppressions ({})\n\n", SUPPRESS_FILE));
+            for rule in &self.suppressions {
+                md.push_str(&format!("- `{}`\n", rule));
+            }
+        }
+        md
+    }
+
+    /// Write `to_markdown` to `path`
+    pub fn write_markdown(&self, path: &Path, package: Option<&str>) -> Result<()> {
+        std::fs::write(path, self.to_markdown(package))?;
+        Ok(())
+    }
+}
+
+/// Sort key putting the most severe findings first; unknown severities go last
+fn severity_rank(severity: &str) -> usize {
+    ["critical", "high", "medium", "low", "info"]
+        .iter()
+        .position(|s| severity.eq_ignore_ascii_case(s))
+        .unwrap_or(5)
+}
+
+/// Text safe inside a Markdown table cell (pipes escaped, one line)
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Failed verification stages, collected instead of returned under `--keep-going`
+#[derive(Debug, Default)]
+pub struct StageFailures {
+    keep_going: bool,
+    errors: Vec<KaidoError>,
+}
+
+impl StageFailures {
+    pub fn new(keep_going: bool) -> Self {
+        Self {
+            keep_going,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Record a failed stage. Returns the error (stopping verification) unless keep-going is set.
+    pub fn record(&mut self, err: KaidoError) -> Result<()> {
+        if !self.keep_going {
+            return Err(err);
+        }
+        self.errors.push(err);
+        Ok(())
+    }
+
+    /// One consolidated error listing every failed stage, if any failed
+    pub fn finish(self) -> Result<()> {
+        match self.errors.len() {
+            0 => Ok(()),
+            1 => Err(self.errors.into_iter().next().unwrap()),
+            _ => Err(KaidoError::VerificationFailed(
+                self.errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+            )),
+        }
+    }
 }
 
 /// Verifies generated Aiken projects compile and pass tests
@@ -79,6 +411,26 @@ impl AikenVerifier {
         Ok(())
     }
 
+    /// Run `aiken fmt` on the project, rewriting its .ak files in place
+    pub fn format(project_dir: &Path) -> Result<()> {
+        let output = Command::new("aiken")
+            .arg("fmt")
+            .current_dir(project_dir)
+            .output()
+            .map_err(|e| KaidoError::AikenFmtFailed(format!("Failed to run aiken: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return Err(KaidoError::AikenFmtFailed(format!(
+                "stdout:\n{}\nstderr:\n{}",
+                stdout, stderr
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Check if aiken is available on PATH
     pub fn is_available() -> bool {
         Command::new("aiken")
@@ -132,7 +484,8 @@ impl AikidoVerifier {
             })
     }
 
-    /// Run aikido scan on a project, returning findings
+    /// Run aikido scan on a project, returning findings with the project's
+    /// `.kaido-suppress` entries applied
     pub fn scan(project_dir: &Path) -> Result<AikidoResult> {
         let output = Command::new("aikido")
             .arg(project_dir)
@@ -144,12 +497,14 @@ impl AikidoVerifier {
             .output()
             .map_err(|e| KaidoError::AikidoScanFailed(format!("Failed to run aikido: {}", e)))?;
 
-        parse_scan_output(
+        let mut result = parse_scan_output(
             output.status.success(),
             output.status.code(),
             &String::from_utf8_lossy(&output.stdout),
             &String::from_utf8_lossy(&output.stderr),
-        )
+        )?;
+        result.apply_suppressions(load_suppressions(project_dir)?);
+        Ok(result)
     }
 
     /// Run scan and fail if high/critical findings exist
@@ -161,10 +516,7 @@ impl AikidoVerifier {
             let summary: Vec<String> = result
                 .findings
                 .iter()
-                .filter(|f| {
-                    f.severity.eq_ignore_ascii_case("high")
-                        || f.severity.eq_ignore_ascii_case("critical")
-                })
+                .filter(|f| f.is_high_or_critical())
                 .map(|f| {
                     format!(
                         "[{}] {}: {}",
@@ -212,21 +564,53 @@ fn parse_scan_output(
     let high_or_critical = parsed
         .findings
         .iter()
-        .filter(|f| {
-            f.severity.eq_ignore_ascii_case("high") || f.severity.eq_ignore_ascii_case("critical")
-        })
+        .filter(|f| f.is_high_or_critical())
         .count();
 
     Ok(AikidoResult {
+        by_severity: count_by_severity(&parsed.findings),
         findings: parsed.findings,
         high_or_critical,
         total: parsed.total,
+        suppressions: Vec::new(),
     })
 }
 
+fn count_by_severity(findings: &[AikidoFinding]) -> BTreeMap<String, usize> {
+    let mut by_severity = BTreeMap::new();
+    for f in findings {
+        *by_severity.entry(f.severity.to_lowercase()).or_insert(0) += 1;
+    }
+    by_severity
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_scan_output;
+    use super::{
+        parse_scan_output, parse_suppressions, StageFailures, StepStatus, VerificationReport,
+    };
+    use kaido_core::error::KaidoError;
+
+    #[test]
+    fn stage_failures_collects_every_stage_when_keep_going() {
+        let mut stop = StageFailures::new(false);
+        assert!(stop
+            .record(KaidoError::AikenBuildFailed("b".to_string()))
+            .is_err());
+
+        let mut failures = StageFailures::new(true);
+        failures
+            .record(KaidoError::AikenBuildFailed("b".to_string()))
+            .unwrap();
+        failures
+            .record(KaidoError::AikenCheckFailed("c".to_string()))
+            .unwrap();
+        let err = failures.finish().unwrap_err().to_string();
+        assert!(err.starts_with("Verification failed:"));
+        assert!(err.contains("Aiken build failed:\nb"));
+        assert!(err.contains("Aiken check failed:\nc"));
+        assert!(StageFailures::new(true).finish().is_ok());
+    }
 
     #[test]
     fn parse_scan_output_rejects_empty_stdout() {
@@ -249,10 +633,94 @@ mod tests {
         assert_eq!(out.high_or_critical, 1);
     }
 
+    #[test]
+    fn parse_scan_output_counts_findings_by_severity() {
+        let json = r#"{"findings":[
+            {"detector":"a","severity":"Critical","description":"x"},
+            {"detector":"b","severity":"high","description":"x"},
+            {"detector":"c","severity":"low","description":"x"},
+            {"detector":"d","severity":"LOW","description":"x"},
+            {"detector":"e","severity":"info","description":"x"}
+        ],"total":5}"#;
+        let out = parse_scan_output(false, Some(2), json, "").expect("must parse");
+        assert_eq!(out.high_or_critical, 2);
+        assert_eq!(out.by_severity.get("critical"), Some(&1));
+        assert_eq!(out.by_severity.get("high"), Some(&1));
+        assert_eq!(out.by_severity.get("low"), Some(&2));
+        assert_eq!(out.by_severity.get("info"), Some(&1));
+        assert_eq!(out.by_severity.get("medium"), None);
+    }
+
+    #[test]
+    fn max_findings_gate_counts_findings_regardless_of_severity() {
+        let json = r#"{"findings":[
+            {"detector":"a","severity":"low","description":"x"},
+            {"detector":"b","severity":"low","description":"x"},
+            {"detector":"c","severity":"info","description":"x"}
+        ],"total":3}"#;
+        let out = parse_scan_output(true, Some(0), json, "").expect("must parse");
+        assert_eq!(out.high_or_critical, 0);
+        let err = out.check_max_findings(Some(2)).expect_err("must trip");
+        assert!(err.to_string().contains("--max-findings"));
+        assert!(out.check_max_findings(Some(3)).is_ok());
+        assert!(out.check_max_findings(None).is_ok());
+    }
+
     #[test]
     fn parse_scan_output_rejects_non_zero_without_findings() {
         let json = r#"{"findings":[],"total":0}"#;
         let err = parse_scan_output(false, Some(3), json, "stderr").expect_err("must fail");
         assert!(err.to_string().contains("non-zero"));
     }
+
+    #[test]
+    fn suppressions_downgrade_matching_findings() {
+        let json = r#"{"findings":[
+            {"detector":"a","severity":"critical","description":"x"},
+            {"detector":"b","severity":"high","description":"x","location":{"file":"validators/v.ak","line":12}},
+            {"detector":"b","severity":"high","description":"x","location":{"file":"validators/v.ak","line":40}}
+        ],"total":3}"#;
+        let mut out = parse_scan_output(false, Some(2), json, "").expect("must parse");
+        let suppressions =
+            parse_suppressions("# accepted\na\n\nb validators/v.ak:12  # reviewed\n");
+        assert_eq!(suppressions.len(), 2);
+
+        out.apply_suppressions(suppressions);
+        assert_eq!(out.high_or_critical, 1);
+        assert_eq!(out.by_severity.get("info"), Some(&2));
+        assert_eq!(out.by_severity.get("critical"), None);
+        let suppressed: Vec<_> = out.suppressed().collect();
+        assert_eq!(suppressed.len(), 2);
+        assert_eq!(suppressed[0].original_severity.as_deref(), Some("critical"));
+        assert_eq!(
+            suppressed[1].suppressed_by.as_deref(),
+            Some("b validators/v.ak:12")
+        );
+        assert_eq!(out.findings[2].severity, "high");
+    }
+
+    #[test]
+    fn audit_markdown_sorts_findings_by_severity() {
+        let json = r#"{"findings":[
+            {"detector":"low-one","severity":"low","description":"a | b"},
+            {"detector":"crit-one","severity":"critical","description":"boom"},
+            {"detector":"odd","severity":"note","description":"c"}
+        ],"total":3}"#;
+        let mut report = VerificationReport {
+            aiken_build: StepStatus::Passed,
+            aiken_version: Some("aiken v1.1.21".to_string()),
+            ..Default::default()
+        };
+        report.record_scan(parse_scan_output(false, Some(2), json, "").expect("must parse"));
+
+        let md = report.to_markdown(Some("acme/vault"));
+        assert!(md.starts_with("# Audit summary: vault\n"));
+        assert!(md.contains("- **Namespace:** acme\n"));
+        assert!(md.contains("- **aikido:** unknown\n"));
+        assert!(md.contains("| aiken check | not run |\n| aikido scan | passed |\n"));
+        let crit = md.find("| critical | crit-one | boom |").unwrap();
+        let low = md.find("| low | low-one | a \\| b |").unwrap();
+        let odd = md.find("| note | odd | c |").unwrap();
+        assert!(crit < low && low < odd);
+    }
 }
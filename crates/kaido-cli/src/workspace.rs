@@ -0,0 +1,165 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use kaido_core::generator::RenderResult;
+
+/// Project-level files the workspace already owns; never emitted with `--into-workspace`
+const PROJECT_FILES: &[&str] = &[
+    "aiken.toml",
+    "aiken.lock",
+    ".aikido.toml",
+    ".editorconfig",
+    ".github/workflows/aiken.yml",
+];
+
+/// An existing Aiken project that generated validators and lib modules are added to
+pub struct Workspace {
+    pub root: PathBuf,
+    /// Namespace from the workspace `name = "<namespace>/<project>"`, used for `lib/`
+    pub namespace: String,
+    config: String,
+}
+
+impl Workspace {
+    /// Read `<root>/aiken.toml` and take the namespace from its package name
+    pub fn open(root: &Path) -> io::Result<Self> {
+        let config_path = root.join("aiken.toml");
+        let config = fs::read_to_string(&config_path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("{}: {} (not an Aiken project)", config_path.display(), e),
+            )
+        })?;
+
+        let namespace = package_name(&config)
+            .and_then(|name| name.split_once('/').map(|(ns, _)| ns.to_string()))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "{} has no name = \"<namespace>/<project>\"",
+                        config_path.display()
+                    ),
+                )
+            })?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            namespace,
+            config,
+        })
+    }
+
+    /// Drop aiken.toml / aiken.lock / .aikido.toml / .editorconfig / the CI workflow so only
+    /// validator, lib and SDK files are written
+    pub fn retain_generated_files(result: &mut RenderResult) {
+        result
+            .files
+            .retain(|f| !PROJECT_FILES.contains(&f.path.as_str()));
+    }
+
+    /// Append the stdlib dependency to the workspace aiken.toml if it is missing.
+    /// Returns whether the config was changed.
+    pub fn ensure_stdlib_dependency(&self, stdlib_version: &str) -> io::Result<bool> {
+        if self.config.contains("\"aiken-lang/stdlib\"") {
+            return Ok(false);
+        }
+
+        let mut config = self.config.clone();
+        if !config.ends_with('\n') {
+            config.push('\n');
+        }
+        config.push_str(&format!(
+            "\n[[dependencies]]\nname = \"aiken-lang/stdlib\"\nversion = \"{}\"\nsource = \"github\"\n",
+            stdlib_version
+        ));
+        fs::write(self.root.join("aiken.toml"), config)?;
+        Ok(true)
+    }
+}
+
+/// Top-level `name = "..."` of an aiken.toml (keys inside tables are ignored)
+pub(crate) fn package_name(config: &str) -> Option<&str> {
+    config
+        .lines()
+        .map(str::trim)
+        .take_while(|line| !line.starts_with('['))
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "name").then(|| value.trim().trim_matches('"'))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::{write_project, OutputLayout, OverwritePolicy};
+    use kaido_core::generator::ProjectGenerator;
+    use kaido_core::templates::GenerateOptions;
+
+    const WORKSPACE_TOML: &str = "name = \"acme/contracts\"\nversion = \"0.0.0\"\nplutus = \"v3\"\n\n[[dependencies]]\nname = \"aiken-lang/stdlib\"\nversion = \"v3.0.0\"\nsource = \"github\"\n";
+
+    #[test]
+    fn generates_only_validator_and_lib_files_into_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("aiken.toml"), WORKSPACE_TOML).unwrap();
+
+        let ws = Workspace::open(dir.path()).unwrap();
+        assert_eq!(ws.namespace, "acme");
+
+        let mut options = GenerateOptions::escrow("myorg", "my-escrow");
+        options.namespace = ws.namespace.clone();
+        let mut result = ProjectGenerator::new().unwrap().render(&options).unwrap();
+        Workspace::retain_generated_files(&mut result);
+
+        let written = write_project(
+            &result,
+            dir.path(),
+            OverwritePolicy::Changed,
+            OutputLayout::Tree,
+        )
+        .unwrap();
+        assert!(!written.is_empty());
+        for (path, _) in &written {
+            let rel = path.strip_prefix(dir.path()).unwrap();
+            assert!(
+                rel.starts_with("validators") || rel.starts_with("lib/acme/my_escrow"),
+                "unexpected file {}",
+                rel.display()
+            );
+        }
+        assert!(!ws.ensure_stdlib_dependency("v3.0.0").unwrap());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("aiken.toml")).unwrap(),
+            WORKSPACE_TOML
+        );
+        assert!(!dir.path().join(".aikido.toml").exists());
+    }
+
+    #[test]
+    fn appends_missing_stdlib_dependency() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("aiken.toml"), "name = \"acme/contracts\"").unwrap();
+
+        let ws = Workspace::open(dir.path()).unwrap();
+        assert!(ws.ensure_stdlib_dependency("v2.2.0").unwrap());
+        let config = fs::read_to_string(dir.path().join("aiken.toml")).unwrap();
+        assert!(config.starts_with("name = \"acme/contracts\"\n"));
+        assert!(config.contains("name = \"aiken-lang/stdlib\"\nversion = \"v2.2.0\""));
+    }
+
+    #[test]
+    fn rejects_workspace_without_namespaced_name() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Workspace::open(dir.path()).is_err());
+
+        fs::write(
+            dir.path().join("aiken.toml"),
+            "[[dependencies]]\nname = \"aiken-lang/stdlib\"\n",
+        )
+        .unwrap();
+        let err = Workspace::open(dir.path()).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
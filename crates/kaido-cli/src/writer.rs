@@ -1,23 +1,413 @@
+use std::collections::HashSet;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 
-use kaido_core::generator::RenderResult;
+use kaido_core::generator::{GeneratedFile, RenderResult};
 
-/// Write a RenderResult to disk, returning the list of created file paths
-pub fn write_project(result: &RenderResult, output_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
-    let mut paths = Vec::new();
+/// How `write_project` treats files that already exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// Error if any target file already exists (nothing is written)
+    Never,
+    /// Write only files whose content differs
+    Changed,
+    /// Always write every file
+    Always,
+}
+
+/// What happened to a single file during `write_project`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteStatus {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+/// How `write_project` lays files out on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputLayout {
+    /// Each file at its project path (`validators/v.ak`, `lib/ns/module/types.ak`, ...)
+    Tree,
+    /// Every file directly in the output directory, named by its path with `/` replaced by
+    /// `__`, plus a `FLAT_MANIFEST` recording the original paths
+    Flat,
+}
+
+/// Manifest mapping flattened file names back to their project paths
+pub const FLAT_MANIFEST: &str = "kaido-manifest.json";
+
+/// Write a RenderResult to disk, returning each file path with its write status
+pub fn write_project(
+    result: &RenderResult,
+    output_dir: &Path,
+    policy: OverwritePolicy,
+    layout: OutputLayout,
+) -> io::Result<Vec<(PathBuf, WriteStatus)>> {
+    match layout {
+        OutputLayout::Tree => write_files(&result.files, output_dir, policy),
+        OutputLayout::Flat => write_files(&flatten(&result.files)?, output_dir, policy),
+    }
+}
 
-    for file in &result.files {
+/// Rename files for the flat layout and append the manifest. Two paths flattening to the
+/// same name (or to the manifest's) is an error rather than a silent overwrite.
+fn flatten(files: &[GeneratedFile]) -> io::Result<Vec<GeneratedFile>> {
+    let mut seen = HashSet::from([FLAT_MANIFEST.to_string()]);
+    let mut flat = Vec::new();
+    let mut entries = Vec::new();
+    for file in files {
+        let name = file.path.replace('/', "__");
+        if !seen.insert(name.clone()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} flattens to {}, which is already taken", file.path, name),
+            ));
+        }
+        entries.push(serde_json::json!({ "path": file.path, "file": name }));
+        flat.push(GeneratedFile {
+            path: name,
+            content: file.content.clone(),
+        });
+    }
+    let manifest = serde_json::json!({ "layout": "flat", "files": entries });
+    flat.push(GeneratedFile {
+        path: FLAT_MANIFEST.to_string(),
+        content: format!("{}\n", serde_json::to_string_pretty(&manifest)?),
+    });
+    Ok(flat)
+}
+
+/// Write individual files (e.g. `.aikido.toml` alone) under the same overwrite policy
+pub fn write_files(
+    files: &[GeneratedFile],
+    output_dir: &Path,
+    policy: OverwritePolicy,
+) -> io::Result<Vec<(PathBuf, WriteStatus)>> {
+    // Check up front so `never` leaves the directory untouched on conflict
+    if policy == OverwritePolicy::Never {
+        if let Some(existing) = files
+            .iter()
+            .map(|f| output_dir.join(&f.path))
+            .find(|p| p.exists())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "{} already exists (--overwrite-policy never)",
+                    existing.display()
+                ),
+            ));
+        }
+    }
+
+    files
+        .iter()
+        .map(|file| write_file(file, output_dir, policy))
+        .collect()
+}
+
+/// Write files one at a time as `files` yields them, so a caller producing them lazily (e.g. a
+/// batch rendering project after project) never holds more than the file being written. Unlike
+/// `write_files`, `Never` can only refuse an existing file when it is reached, after the files
+/// before it were written.
+pub fn write_project_streaming<I>(
+    files: I,
+    output_dir: &Path,
+    policy: OverwritePolicy,
+) -> io::Result<Vec<(PathBuf, WriteStatus)>>
+where
+    I: IntoIterator<Item = GeneratedFile>,
+{
+    let mut written = Vec::new();
+    for file in files {
         let full_path = output_dir.join(&file.path);
+        if policy == OverwritePolicy::Never && full_path.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "{} already exists (--overwrite-policy never)",
+                    full_path.display()
+                ),
+            ));
+        }
+        written.push(write_file(&file, output_dir, policy)?);
+    }
+    Ok(written)
+}
 
+/// Write one file under `policy` (conflicts for `Never` are checked by the callers)
+fn write_file(
+    file: &GeneratedFile,
+    output_dir: &Path,
+    policy: OverwritePolicy,
+) -> io::Result<(PathBuf, WriteStatus)> {
+    let full_path = output_dir.join(&file.path);
+
+    let status = match fs::read(&full_path) {
+        Ok(existing) if policy == OverwritePolicy::Changed => {
+            if existing == file.content.as_bytes() {
+                WriteStatus::Unchanged
+            } else {
+                WriteStatus::Updated
+            }
+        }
+        Ok(_) => WriteStatus::Updated,
+        Err(_) => WriteStatus::Created,
+    };
+
+    if status != WriteStatus::Unchanged {
         // Create parent directories
         if let Some(parent) = full_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
         fs::write(&full_path, &file.content)?;
-        paths.push(full_path);
+    }
+    Ok((full_path, status))
+}
+
+/// Whether any written file can change what aiken builds: a `.ak` module or aiken.toml
+pub fn contract_changed(written: &[(PathBuf, WriteStatus)]) -> bool {
+    written.iter().any(|(path, status)| {
+        *status != WriteStatus::Unchanged
+            && (path.extension().is_some_and(|ext| ext == "ak")
+                || path.file_name().is_some_and(|name| name == "aiken.toml"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaido_core::generator::ProjectGenerator;
+    use kaido_core::templates::{GenerateOptions, Template};
+    use std::cell::Cell;
+
+    fn sample() -> RenderResult {
+        RenderResult {
+            files: vec![
+                GeneratedFile {
+                    path: "aiken.toml".to_string(),
+                    content: "name = \"new\"\n".to_string(),
+                },
+                GeneratedFile {
+                    path: "validators/v.ak".to_string(),
+                    content: "same\n".to_string(),
+                },
+            ],
+            template: Template::Escrow,
+            diagnostics: Default::default(),
+            meta: Default::default(),
+        }
+    }
+
+    fn prepopulated() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("aiken.toml"), "name = \"old\"\n").unwrap();
+        fs::create_dir_all(dir.path().join("validators")).unwrap();
+        fs::write(dir.path().join("validators/v.ak"), "same\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn never_refuses_existing_files() {
+        let dir = prepopulated();
+        let err = write_project(
+            &sample(),
+            dir.path(),
+            OverwritePolicy::Never,
+            OutputLayout::Tree,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        let toml = fs::read_to_string(dir.path().join("aiken.toml")).unwrap();
+        assert_eq!(toml, "name = \"old\"\n");
+    }
+
+    #[test]
+    fn never_writes_into_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_project(
+            &sample(),
+            dir.path(),
+            OverwritePolicy::Never,
+            OutputLayout::Tree,
+        )
+        .unwrap();
+        assert!(written.iter().all(|(_, s)| *s == WriteStatus::Created));
+    }
+
+    #[test]
+    fn changed_skips_identical_files() {
+        let dir = prepopulated();
+        let written = write_project(
+            &sample(),
+            dir.path(),
+            OverwritePolicy::Changed,
+            OutputLayout::Tree,
+        )
+        .unwrap();
+        assert_eq!(written[0].1, WriteStatus::Updated);
+        assert_eq!(written[1].1, WriteStatus::Unchanged);
+        let toml = fs::read_to_string(dir.path().join("aiken.toml")).unwrap();
+        assert_eq!(toml, "name = \"new\"\n");
+    }
+
+    #[test]
+    fn always_rewrites_every_file() {
+        let dir = prepopulated();
+        let written = write_project(
+            &sample(),
+            dir.path(),
+            OverwritePolicy::Always,
+            OutputLayout::Tree,
+        )
+        .unwrap();
+        assert!(written.iter().all(|(_, s)| *s == WriteStatus::Updated));
     }
 
-    Ok(paths)
+    #[test]
+    fn flat_layout_flattens_names_and_records_original_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut result = sample();
+        result.files.push(GeneratedFile {
+            path: "lib/ns/module/types.ak".to_string(),
+            content: "types\n".to_string(),
+        });
+        let written = write_project(
+            &result,
+            dir.path(),
+            OverwritePolicy::Never,
+            OutputLayout::Flat,
+        )
+        .unwrap();
+        let names: Vec<_> = written
+            .iter()
+            .map(|(p, _)| p.strip_prefix(dir.path()).unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "aiken.toml",
+                "validators__v.ak",
+                "lib__ns__module__types.ak",
+                FLAT_MANIFEST
+            ]
+        );
+        assert!(!dir.path().join("lib").exists());
+        let types = fs::read_to_string(dir.path().join("lib__ns__module__types.ak")).unwrap();
+        assert_eq!(types, "types\n");
+
+        // The manifest is enough to rebuild the tree
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join(FLAT_MANIFEST)).unwrap())
+                .unwrap();
+        assert_eq!(manifest["layout"], "flat");
+        let restored: Vec<(&str, &str)> = manifest["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| (e["file"].as_str().unwrap(), e["path"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            restored,
+            vec![
+                ("aiken.toml", "aiken.toml"),
+                ("validators__v.ak", "validators/v.ak"),
+                ("lib__ns__module__types.ak", "lib/ns/module/types.ak"),
+            ]
+        );
+    }
+
+    #[test]
+    fn flat_layout_rejects_colliding_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut result = sample();
+        result.files.push(GeneratedFile {
+            path: "validators__v.ak".to_string(),
+            content: "clash\n".to_string(),
+        });
+        let err = write_project(
+            &result,
+            dir.path(),
+            OverwritePolicy::Always,
+            OutputLayout::Flat,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(fs::read_dir(dir.path()).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn streaming_writes_each_project_before_the_next_is_rendered() {
+        let dir = tempfile::tempdir().unwrap();
+        let gen = ProjectGenerator::new().unwrap();
+        let root = dir.path().to_path_buf();
+        let rendered = Cell::new(0);
+
+        // Each project is rendered only when the writer pulls its first file, and by then every
+        // earlier project is already on disk: nothing is collected up front
+        let files = (0..20).flat_map(|i| {
+            let previous = (0..i).map(|j| format!("p{j}/validators/p{j}_escrow.ak"));
+            assert!(previous.clone().all(|p| root.join(p).exists()));
+            assert!(!root.join(format!("p{i}")).exists());
+            rendered.set(rendered.get() + 1);
+
+            let options = GenerateOptions::escrow("myorg", &format!("p{i}"));
+            gen.render(&options)
+                .unwrap()
+                .files
+                .into_iter()
+                .map(move |f| GeneratedFile {
+                    path: format!("p{i}/{}", f.path),
+                    content: f.content,
+                })
+        });
+        let written = write_project_streaming(files, dir.path(), OverwritePolicy::Never).unwrap();
+
+        assert_eq!(rendered.get(), 20);
+        assert!(written.iter().all(|(_, s)| *s == WriteStatus::Created));
+        assert!(dir.path().join("p19/validators/p19_escrow.ak").exists());
+    }
+
+    #[test]
+    fn streaming_never_stops_at_the_first_existing_file() {
+        let dir = prepopulated();
+        let files = vec![
+            GeneratedFile {
+                path: "validators/new.ak".to_string(),
+                content: "new\n".to_string(),
+            },
+            GeneratedFile {
+                path: "aiken.toml".to_string(),
+                content: "name = \"new\"\n".to_string(),
+            },
+        ];
+        let err = write_project_streaming(files, dir.path(), OverwritePolicy::Never).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(dir.path().join("validators/new.ak").exists());
+        let toml = fs::read_to_string(dir.path().join("aiken.toml")).unwrap();
+        assert_eq!(toml, "name = \"old\"\n");
+    }
+
+    #[test]
+    fn contract_changed_ignores_unchanged_and_non_contract_files() {
+        let written = |path: &str, status| vec![(PathBuf::from(path), status)];
+        assert!(contract_changed(&written(
+            "p/validators/v.ak",
+            WriteStatus::Updated
+        )));
+        assert!(contract_changed(&written(
+            "p/aiken.toml",
+            WriteStatus::Created
+        )));
+        assert!(!contract_changed(&written(
+            "p/validators/v.ak",
+            WriteStatus::Unchanged
+        )));
+        assert!(!contract_changed(&written(
+            "p/sdk/src/index.ts",
+            WriteStatus::Updated
+        )));
+    }
 }
@@ -0,0 +1,818 @@
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::prelude::PredicateBooleanExt;
+use tempfile::TempDir;
+
+fn kaido_bin() -> Command {
+    Command::new(assert_cmd::cargo::cargo_bin!("kaido"))
+}
+
+#[test]
+fn generate_from_stdin_reads_custom_options_json() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("generated");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--from-stdin",
+        "--skip-verify",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ])
+    .write_stdin(
+        r#"{
+  "template": "custom",
+  "namespace": "myorg",
+  "project_name": "piped-validator",
+  "features": "sig,timelock",
+  "datum": "owner:ByteArray,deadline:Int",
+  "redeemer": "Claim,Cancel"
+}"#,
+    );
+
+    cmd.assert().success();
+
+    let toml = fs::read_to_string(output_dir.join("aiken.toml")).expect("aiken.toml");
+    assert!(toml.contains("name = \"myorg/piped-validator\""));
+    assert!(output_dir
+        .join("lib/myorg/piped_validator/types.ak")
+        .exists());
+    let validator = fs::read_to_string(output_dir.join("validators/piped_validator_validator.ak"))
+        .expect("validator");
+    assert!(validator.contains("expect list.has(self.extra_signatories, admin_pkh)"));
+    assert!(validator.contains("interval.is_entirely_after(self.validity_range, datum.deadline)"));
+}
+
+#[test]
+fn generate_from_stdin_rejects_invalid_json() {
+    let mut cmd = kaido_bin();
+    cmd.args(["generate", "--from-stdin", "--skip-verify"])
+        .write_stdin("not-json");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid JSON on stdin"));
+}
+
+#[test]
+fn generate_reads_multi_line_redeemer_file() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("generated");
+    let spec = output.path().join("redeemer.spec");
+    fs::write(
+        &spec,
+        "Deposit(amount:Int),\n  Withdraw(\n    amount:Int,\n    to:ByteArray\n  ),\nClose\n",
+    )
+    .expect("redeemer spec");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "custom",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "spec-file",
+        "--datum",
+        "owner:ByteArray",
+        "--redeemer-from-file",
+        spec.to_str().expect("spec path"),
+        "--skip-verify",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ]);
+
+    cmd.assert().success();
+
+    let types = fs::read_to_string(output_dir.join("lib/myorg/spec_file/types.ak")).expect("types");
+    assert!(types.contains("Deposit { amount: Int }"));
+    assert!(types.contains("Withdraw { amount: Int, to: ByteArray }"));
+    assert!(types.contains("  Close\n"));
+}
+
+#[test]
+fn generate_rejects_inline_and_file_spec_together() {
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "custom",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "p",
+        "--redeemer",
+        "Claim",
+        "--redeemer-from-file",
+        "redeemer.spec",
+        "--skip-verify",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+}
+
+#[test]
+fn generate_check_only_changed_skips_verification_when_nothing_changed() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("generated");
+    let args = |verify_flag: &str| {
+        let mut cmd = kaido_bin();
+        cmd.args([
+            "generate",
+            "--template",
+            "escrow",
+            "--namespace",
+            "myorg",
+            "--project-name",
+            "my-escrow",
+            verify_flag,
+            "--output",
+            output_dir.to_str().expect("output path"),
+        ]);
+        cmd
+    };
+
+    args("--skip-verify").assert().success();
+
+    // Identical regeneration: nothing to verify, so aiken/aikido are never needed
+    args("--check-only-changed")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "verification skipped (no contract changes)",
+        ));
+}
+
+#[test]
+fn generate_force_snake_case_rewrites_project_name_with_warning() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("generated");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "MyToken",
+        "--force-snake-case",
+        "--skip-verify",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ]);
+
+    cmd.assert().success().stdout(predicates::str::contains(
+        "project_name 'MyToken' rewritten to 'my_token' (--force-snake-case)",
+    ));
+    assert!(output_dir.join("lib/myorg/my_token/types.ak").exists());
+    let toml = fs::read_to_string(output_dir.join("aiken.toml")).expect("aiken.toml");
+    assert!(toml.contains("name = \"myorg/my_token\""));
+}
+
+#[test]
+fn generate_force_snake_case_still_rejects_path_traversal() {
+    let output = TempDir::new().expect("output tempdir");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "../evil",
+        "--force-snake-case",
+        "--skip-verify",
+        "--output",
+        output
+            .path()
+            .join("generated")
+            .to_str()
+            .expect("output path"),
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid path characters"));
+    assert!(!output.path().join("generated").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn generate_namespace_from_git_uses_the_remote_org() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // A fake git on PATH answering `git config --get remote.origin.url`
+    let bin = TempDir::new().expect("bin tempdir");
+    let git = bin.path().join("git");
+    fs::write(
+        &git,
+        "#!/bin/sh\necho 'git@github.com:AcmeLabs/contracts.git'\n",
+    )
+    .expect("fake git");
+    fs::set_permissions(&git, fs::Permissions::from_mode(0o755)).expect("chmod fake git");
+    let path = format!(
+        "{}:{}",
+        bin.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("generated");
+    let mut cmd = kaido_bin();
+    cmd.env("PATH", &path).args([
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace-from-git",
+        "--project-name",
+        "vault",
+        "--skip-verify",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ]);
+    cmd.assert().success().stdout(predicates::str::contains(
+        "namespace 'acme_labs' from git remote origin",
+    ));
+    let toml = fs::read_to_string(output_dir.join("aiken.toml")).expect("aiken.toml");
+    assert!(toml.contains("name = \"acme_labs/vault\""));
+
+    // An explicit --namespace still wins
+    let override_dir = output.path().join("override");
+    let mut cmd = kaido_bin();
+    cmd.env("PATH", &path).args([
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace-from-git",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "vault",
+        "--skip-verify",
+        "--output",
+        override_dir.to_str().expect("output path"),
+    ]);
+    cmd.assert().success();
+    let toml = fs::read_to_string(override_dir.join("aiken.toml")).expect("aiken.toml");
+    assert!(toml.contains("name = \"myorg/vault\""));
+}
+
+#[test]
+fn generate_project_names_scaffolds_each_into_its_own_directory() {
+    let output = TempDir::new().expect("output tempdir");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "mint",
+        "--namespace",
+        "myorg",
+        "--project-names",
+        "token-a,token-b",
+        "--skip-verify",
+        "--output",
+        output.path().to_str().expect("output path"),
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("2/2 projects generated"));
+
+    for (dir, module) in [("token-a", "token_a"), ("token-b", "token_b")] {
+        let project = output.path().join(dir);
+        let toml = fs::read_to_string(project.join("aiken.toml")).expect("aiken.toml");
+        assert!(toml.contains(&format!("name = \"myorg/{}\"", dir)));
+        assert!(project
+            .join(format!("lib/myorg/{}/types.ak", module))
+            .exists());
+    }
+}
+
+#[test]
+fn generate_project_names_rejects_duplicates_before_writing() {
+    let output = TempDir::new().expect("output tempdir");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace",
+        "myorg",
+        "--project-names",
+        "deal,deal",
+        "--skip-verify",
+        "--output",
+        output.path().to_str().expect("output path"),
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("Duplicate project name 'deal'"));
+    assert!(!output.path().join("deal").exists());
+}
+
+#[test]
+fn generate_strict_fails_on_warnings_without_writing() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("generated");
+    // datum-continuity alone leaves Claim without a per-action check
+    let args = [
+        "generate",
+        "--template",
+        "custom",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "loose",
+        "--features",
+        "datum-continuity",
+        "--datum",
+        "owner:ByteArray",
+        "--redeemer",
+        "Claim",
+        "--skip-verify",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ];
+
+    kaido_bin()
+        .args(args)
+        .arg("--strict")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Strict mode"))
+        .stderr(predicates::str::contains("[unchecked-action]"));
+    assert!(!output_dir.exists());
+
+    kaido_bin()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("[unchecked-action]"));
+    assert!(output_dir.join("aiken.toml").exists());
+}
+
+#[test]
+fn generate_validate_only_reports_errors_without_writing() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("never-written");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "custom",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "checked",
+        "--features",
+        "sig,bogus",
+        "--redeemer",
+        "claim",
+        "--validate-only",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("Unknown feature 'bogus'"))
+        .stderr(predicates::str::contains("datum is required"))
+        .stderr(predicates::str::contains(
+            "must start with an uppercase letter",
+        ));
+    assert!(!output_dir.exists());
+
+    let mut ok = kaido_bin();
+    ok.args([
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "checked",
+        "--validate-only",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ]);
+    ok.assert()
+        .success()
+        .stdout(predicates::str::contains("options are valid"));
+    assert!(!output_dir.exists());
+}
+
+#[test]
+fn generate_preview_tests_prints_composed_cases_without_writing() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("never-written");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "custom",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "previewed",
+        "--features",
+        "sig",
+        "--datum",
+        "owner:ByteArray",
+        "--redeemer",
+        "Execute",
+        "--preview-tests",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("test execute_valid() {"))
+        .stdout(predicates::str::contains("test no_datum_fails() fail {"))
+        .stdout(predicates::str::contains("previewed_validator.spend("));
+    assert!(!output_dir.exists());
+}
+
+#[test]
+fn generate_warns_about_flags_the_template_ignores() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("token");
+    let generate = |extra: &[&str]| {
+        let mut cmd = kaido_bin();
+        cmd.args([
+            "generate",
+            "--template",
+            "mint",
+            "--namespace",
+            "myorg",
+            "--project-name",
+            "token",
+            "--cancellable",
+            "--skip-verify",
+            "--output",
+            output_dir.to_str().expect("output path"),
+        ])
+        .args(extra);
+        cmd
+    };
+
+    generate(&["--strict"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "cancellable is ignored for the simple_mint template",
+        ));
+    assert!(!output_dir.exists());
+
+    generate(&[])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Warning: cancellable is ignored for the simple_mint template (it applies to: vesting)",
+        ));
+}
+
+#[test]
+fn generate_print_params_lists_validator_params_in_order() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("never-written");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "custom",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "vault",
+        "--features",
+        "sig,bounded",
+        "--datum",
+        "owner:ByteArray",
+        "--redeemer",
+        "Withdraw",
+        "--print-params",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("validators/vault_validator.ak"))
+        .stdout(predicates::str::contains(
+            "  1. admin_pkh: ByteArray — added by signature-auth\n  2. min_lovelace: Int — added by bounded-operations\n",
+        ));
+    assert!(!output_dir.exists());
+}
+
+#[test]
+fn generate_diff_against_reports_changes_without_writing() {
+    let output = TempDir::new().expect("output tempdir");
+    let project_dir = output.path().join("vault");
+    let project = project_dir.to_str().expect("project path");
+    let generate = |extra: &[&str]| {
+        let mut cmd = kaido_bin();
+        cmd.args([
+            "generate",
+            "--template",
+            "vesting",
+            "--namespace",
+            "myorg",
+            "--project-name",
+            "vault",
+            "--skip-verify",
+        ])
+        .args(extra);
+        cmd
+    };
+
+    generate(&["--output", project]).assert().success();
+    let validator_path = project_dir.join("validators/vault_vesting.ak");
+    let before = fs::read_to_string(&validator_path).expect("validator");
+
+    generate(&["--cancellable", "--diff-against", project])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "--- a/validators/vault_vesting.ak",
+        ))
+        .stdout(predicates::str::contains(
+            "+++ b/validators/vault_vesting.ak",
+        ))
+        .stdout(predicates::str::contains(
+            "+validator vault_vesting(owner_pkh: ByteArray) {",
+        ))
+        .stdout(predicates::str::contains("2 changed, 0 added, 0 removed"));
+    assert_eq!(
+        fs::read_to_string(&validator_path).expect("validator"),
+        before
+    );
+
+    // A renamed validator shows up as one added and one removed module
+    generate(&["--validator-name", "vault", "--diff-against", project])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("+++ b/validators/vault.ak"))
+        .stdout(predicates::str::contains(
+            "--- a/validators/vault_vesting.ak",
+        ))
+        .stdout(predicates::str::contains("0 changed, 1 added, 1 removed"));
+    assert!(!project_dir.join("validators/vault.ak").exists());
+}
+
+#[test]
+fn generate_diff_against_summarizes_template_changes_since_recorded_version() {
+    let output = TempDir::new().expect("output tempdir");
+    let project_dir = output.path().join("stream");
+    let project = project_dir.to_str().expect("project path");
+    let generate = |extra: &[&str]| {
+        let mut cmd = kaido_bin();
+        cmd.args([
+            "generate",
+            "--template",
+            "streaming",
+            "--namespace",
+            "myorg",
+            "--project-name",
+            "stream",
+            "--skip-verify",
+        ])
+        .args(extra);
+        cmd
+    };
+
+    generate(&["--output", project]).assert().success();
+    // Same kaido version: nothing to summarize
+    generate(&["--diff-against", project])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("template changes").not());
+
+    // A project recorded by an older kaido lists what changed since
+    let validator_path = project_dir.join("validators/stream_stream.ak");
+    let validator = fs::read_to_string(&validator_path).expect("validator");
+    let recorded = format!("//// Generated by kaido {}\n", env!("CARGO_PKG_VERSION"));
+    fs::write(
+        &validator_path,
+        validator.replace(&recorded, "//// Generated by kaido 0.0.1\n"),
+    )
+    .expect("rewrite header");
+    generate(&["--diff-against", project])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "streaming_payments template changes since kaido 0.0.1:",
+        ))
+        .stdout(predicates::str::contains(
+            "claims are capped at the linearly vested amount between tranches",
+        ));
+
+    // --since overrides the recorded version
+    generate(&[
+        "--diff-against",
+        project,
+        "--since",
+        env!("CARGO_PKG_VERSION"),
+    ])
+    .assert()
+    .success()
+    .stdout(predicates::str::contains("template changes").not());
+}
+
+#[test]
+fn generate_template_dir_overrides_builtin_templates() {
+    let templates = TempDir::new().expect("templates tempdir");
+    fs::create_dir_all(templates.path().join("base")).expect("base dir");
+    fs::write(
+        templates.path().join("base/aiken.toml.tera"),
+        "name = \"{{ namespace }}/{{ project_name }}\"\n# house style\n",
+    )
+    .expect("override");
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("generated");
+
+    kaido_bin()
+        .args([
+            "generate",
+            "--template",
+            "escrow",
+            "--namespace",
+            "myorg",
+            "--project-name",
+            "my-escrow",
+            "--skip-verify",
+            "--template-dir",
+            templates.path().to_str().expect("templates path"),
+            "--output",
+            output_dir.to_str().expect("output path"),
+        ])
+        .assert()
+        .success();
+
+    let toml = fs::read_to_string(output_dir.join("aiken.toml")).expect("aiken.toml");
+    assert_eq!(toml, "name = \"myorg/my-escrow\"\n# house style\n");
+    assert!(output_dir.join("validators/my_escrow_escrow.ak").exists());
+}
+
+#[test]
+fn generate_flat_output_format_writes_one_directory_with_manifest() {
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir = output.path().join("flat");
+    let args = [
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "my-escrow",
+        "--output-format",
+        "flat",
+        "--output",
+        output_dir.to_str().expect("output path"),
+    ];
+
+    kaido_bin()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "--output-format flat does not support verification",
+        ));
+    assert!(!output_dir.exists());
+
+    kaido_bin()
+        .args(args)
+        .arg("--skip-verify")
+        .assert()
+        .success();
+
+    assert!(output_dir.join("validators__my_escrow_escrow.ak").exists());
+    assert!(output_dir.join("lib__myorg__my_escrow__types.ak").exists());
+    assert!(!output_dir.join("validators").exists());
+    let manifest = fs::read_to_string(output_dir.join("kaido-manifest.json")).expect("manifest");
+    assert!(manifest.contains("\"path\": \"lib/myorg/my_escrow/types.ak\""));
+}
+
+#[test]
+fn lint_warns_on_hyphenated_lib_path() {
+    let output = TempDir::new().expect("output tempdir");
+    let project = output.path().join("my-escrow");
+
+    kaido_bin()
+        .args([
+            "generate",
+            "--template",
+            "escrow",
+            "--namespace",
+            "myorg",
+            "--project-name",
+            "my-escrow",
+            "--skip-verify",
+            "--output",
+            project.to_str().expect("output path"),
+        ])
+        .assert()
+        .success();
+
+    kaido_bin()
+        .args(["lint", project.to_str().expect("project path")])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("no issues found"));
+
+    let lib = project.join("lib/myorg");
+    fs::rename(lib.join("my_escrow"), lib.join("my-escrow")).expect("rename lib dir");
+
+    kaido_bin()
+        .args(["lint", project.to_str().expect("project path")])
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "lib/myorg/my-escrow/types.ak: 'my-escrow' is not snake_case",
+        ))
+        .stderr(predicates::str::contains("lint warning(s)"));
+}
+
+#[test]
+fn features_matrix_prints_pairwise_relations() {
+    let output = kaido_bin()
+        .args(["features", "--matrix"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let matrix: serde_json::Value = serde_json::from_slice(&output).expect("matrix is JSON");
+    assert_eq!(
+        matrix["value-preservation"]["datum-continuity"],
+        "depends_on"
+    );
+    assert_eq!(matrix["deadline-window"]["expiry"], "conflicts");
+}
+
+#[test]
+fn init_aikido_writes_only_the_aikido_config() {
+    let project = TempDir::new().expect("project tempdir");
+    let aiken_toml = "name = \"legacy/vault\"\nversion = \"0.0.0\"\n";
+    fs::write(project.path().join("aiken.toml"), aiken_toml).expect("write aiken.toml");
+    let path = project.path().to_str().expect("project path");
+
+    kaido_bin()
+        .args(["init-aikido", path])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(".aikido.toml"));
+
+    let config = fs::read_to_string(project.path().join(".aikido.toml")).expect("read config");
+    assert!(config.contains("[detectors]"));
+    // The hand-written project is left alone
+    assert_eq!(
+        fs::read_to_string(project.path().join("aiken.toml")).expect("read aiken.toml"),
+        aiken_toml
+    );
+    assert_eq!(fs::read_dir(project.path()).expect("list").count(), 2);
+
+    kaido_bin()
+        .args(["init-aikido", path, "--overwrite-policy", "never"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("already exists"));
+
+    let empty = TempDir::new().expect("empty tempdir");
+    kaido_bin()
+        .args(["init-aikido", empty.path().to_str().expect("empty path")])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No aiken.toml found"));
+}
+
+#[test]
+fn completions_bash_prints_script_with_subcommands_and_features() {
+    let output = kaido_bin()
+        .args(["completions", "bash"])
+        .output()
+        .expect("run kaido completions");
+    assert!(output.status.success());
+
+    let script = String::from_utf8(output.stdout).expect("utf-8 script");
+    assert!(!script.is_empty());
+    assert!(script.contains("generate"));
+    assert!(script.contains("datum-continuity"));
+    assert!(script.contains("dutch-auction"));
+}
@@ -2,6 +2,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use assert_cmd::Command;
+use predicates::prelude::PredicateBooleanExt;
 use tempfile::TempDir;
 
 fn write_executable(path: &Path, content: &str) {
@@ -27,6 +28,23 @@ if [ "$1" = "--version" ]; then
   echo "aiken 1.1.21"
   exit 0
 fi
+if [ "$1" = "fmt" ]; then
+  # Leave a marker so tests can tell the formatter ran in the project root
+  : > .fmt-ran
+  if [ "$AIKEN_MODE" = "fmt_rewrite" ]; then
+    printf 'validator messy {\n  else(_) {\n    fail\n  }\n}\n' > validators/messy.ak
+  fi
+  exit 0
+fi
+if [ "$1" = "build" ] && [ "$AIKEN_MODE" = "build_fail" ]; then
+  echo "type mismatch" >&2
+  exit 1
+fi
+if [ "$1" = "build" ] && [ -n "$BLUEPRINT_FIXTURE" ]; then
+  # PATH only holds the fake tools, so copy with shell builtins
+  while IFS= read -r line; do printf '%s\n' "$line"; done < "$BLUEPRINT_FIXTURE" > plutus.json
+  exit 0
+fi
 if [ "$1" = "build" ] || [ "$1" = "check" ]; then
   exit 0
 fi
@@ -108,6 +126,221 @@ fn verify_accepts_non_zero_aikido_exit_with_findings_json() {
         .stdout(predicates::str::contains("1 finding(s) (1 high/critical)"));
 }
 
+#[test]
+fn verify_fix_fmt_runs_aiken_fmt_and_lists_rewritten_files() {
+    let (_tools, path_env) = setup_fake_tooling(true);
+    let project = setup_project();
+    fs::create_dir_all(project.path().join("validators")).expect("validators dir");
+    fs::write(
+        project.path().join("validators/messy.ak"),
+        "validator messy { else(_) { fail } }\n",
+    )
+    .expect("write messy.ak");
+    fs::write(
+        project.path().join("validators/tidy.ak"),
+        "validator tidy {}\n",
+    )
+    .expect("write tidy.ak");
+
+    kaido_bin()
+        .args([
+            "verify",
+            project.path().to_str().expect("project path"),
+            "--fix-fmt",
+        ])
+        .env("PATH", &path_env)
+        .env("AIKEN_MODE", "fmt_rewrite")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("1 file(s) reformatted"))
+        .stdout(predicates::str::contains("validators/messy.ak"))
+        .stdout(predicates::str::contains("tidy.ak").not());
+    assert!(project.path().join(".fmt-ran").exists());
+
+    // Without the flag the project is never rewritten
+    let untouched = setup_project();
+    kaido_bin()
+        .args(["verify", untouched.path().to_str().expect("project path")])
+        .env("PATH", &path_env)
+        .assert()
+        .success();
+    assert!(!untouched.path().join(".fmt-ran").exists());
+}
+
+#[test]
+fn verify_writes_json_report_with_findings() {
+    let (tools, path_env) = setup_fake_tooling(true);
+    let project = setup_project();
+    let report_path = tools.path().join("report.json");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "verify",
+        project.path().to_str().expect("project path"),
+        "--json-report",
+        report_path.to_str().expect("report path"),
+    ])
+    .env("PATH", &path_env)
+    .env("AIKIDO_MODE", "critical_findings");
+
+    cmd.assert().success();
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report_path).expect("read report"))
+            .expect("report is JSON");
+    assert_eq!(report["aiken_build"], "passed");
+    assert_eq!(report["aikido_scan"], "passed");
+    assert_eq!(report["high_or_critical"], 1);
+    assert_eq!(report["findings"][0]["severity"], "critical");
+    assert_eq!(report["findings"][0]["message"], "boom");
+    assert_eq!(report["aikido_version"], "aikido 1.0.0");
+}
+
+#[test]
+fn verify_writes_markdown_audit_report() {
+    let (tools, path_env) = setup_fake_tooling(true);
+    let project = setup_project();
+    let audit_path = tools.path().join("audit.md");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "verify",
+        project.path().to_str().expect("project path"),
+        "--audit-report",
+        audit_path.to_str().expect("audit path"),
+    ])
+    .env("PATH", &path_env)
+    .env("AIKIDO_MODE", "critical_findings");
+
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Audit summary written to"));
+
+    let audit = fs::read_to_string(&audit_path).expect("read audit report");
+    assert!(audit.contains("- **Project:** project\n- **Namespace:** test\n"));
+    assert!(audit.contains("- **aikido:** aikido 1.0.0\n"));
+    assert!(audit.contains("| aiken build | passed |"));
+    assert!(
+        audit.contains("| Severity | Detector | Message |\n|----------|----------|---------|\n")
+    );
+    assert!(audit.contains("| critical | x | boom |"));
+}
+
+#[test]
+fn verify_suppressed_finding_is_downgraded_and_reported() {
+    let (tools, path_env) = setup_fake_tooling(true);
+    let project = setup_project();
+    fs::write(
+        project.path().join(".kaido-suppress"),
+        "# accepted after review\nx\n",
+    )
+    .expect("write suppress file");
+    let report_path = tools.path().join("report.json");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "verify",
+        project.path().to_str().expect("project path"),
+        "--json-report",
+        report_path.to_str().expect("report path"),
+    ])
+    .env("PATH", &path_env)
+    .env("AIKIDO_MODE", "critical_findings");
+
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("1 finding(s) (0 high/critical)"))
+        .stdout(predicates::str::contains(
+            "1 suppression(s) from .kaido-suppress",
+        ))
+        .stdout(predicates::str::contains(
+            "suppressed [CRITICAL] x: boom (by 'x')",
+        ));
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report_path).expect("read report"))
+            .expect("report is JSON");
+    assert_eq!(report["high_or_critical"], 0);
+    assert_eq!(report["suppressions"][0], "x");
+    assert_eq!(report["findings"][0]["severity"], "info");
+    assert_eq!(report["findings"][0]["original_severity"], "critical");
+    assert_eq!(report["findings"][0]["suppressed_by"], "x");
+}
+
+#[test]
+fn verify_keep_going_runs_check_and_scan_after_build_failure() {
+    let (tools, path_env) = setup_fake_tooling(true);
+    let project = setup_project();
+    let report_path = tools.path().join("report.json");
+
+    let mut stop = kaido_bin();
+    stop.args(["verify", project.path().to_str().expect("project path")])
+        .env("PATH", &path_env)
+        .env("AIKEN_MODE", "build_fail");
+    stop.assert()
+        .failure()
+        .stdout(predicates::str::contains("Running aiken check").not());
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "verify",
+        project.path().to_str().expect("project path"),
+        "--keep-going",
+        "--json-report",
+        report_path.to_str().expect("report path"),
+    ])
+    .env("PATH", &path_env)
+    .env("AIKEN_MODE", "build_fail")
+    .env("AIKIDO_MODE", "critical_findings");
+
+    cmd.assert()
+        .failure()
+        .stdout(predicates::str::contains("Running aiken check"))
+        .stdout(predicates::str::contains("1 finding(s) (1 high/critical)"))
+        .stderr(predicates::str::contains("Aiken build failed"));
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report_path).expect("read report"))
+            .expect("report is JSON");
+    assert_eq!(report["aiken_build"], "failed");
+    assert_eq!(report["aiken_check"], "passed");
+    assert_eq!(report["aikido_scan"], "passed");
+    assert_eq!(report["high_or_critical"], 1);
+}
+
+#[test]
+fn generate_emits_blueprint_types_from_built_plutus_json() {
+    let (_tools, path_env) = setup_fake_tooling(true);
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir: PathBuf = output.path().join("generated");
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/plutus.json");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "my_escrow",
+        "--output",
+        output_dir.to_str().expect("output path"),
+        "--emit-blueprint-types",
+    ])
+    .env("PATH", &path_env)
+    .env("BLUEPRINT_FIXTURE", &fixture);
+
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("blueprint-types.ts"));
+
+    let types = fs::read_to_string(output_dir.join("sdk/src/blueprint-types.ts"))
+        .expect("read blueprint types");
+    assert!(types.contains("export interface EscrowDatum {"));
+    assert!(types.contains("export type MyEscrowSpendRedeemer = EscrowRedeemer;"));
+}
+
 #[test]
 fn generate_requires_aikido_when_verification_enabled() {
     let (_tools, path_env) = setup_fake_tooling(false);
@@ -132,3 +365,34 @@ fn generate_requires_aikido_when_verification_enabled() {
         .failure()
         .stderr(predicates::str::contains("aikido not found on PATH"));
 }
+
+#[test]
+fn generate_fmt_runs_aiken_fmt_before_verification() {
+    let (_tools, path_env) = setup_fake_tooling(true);
+    let output = TempDir::new().expect("output tempdir");
+    let output_dir: PathBuf = output.path().join("generated");
+
+    let mut cmd = kaido_bin();
+    cmd.args([
+        "generate",
+        "--template",
+        "escrow",
+        "--namespace",
+        "myorg",
+        "--project-name",
+        "my_escrow",
+        "--output",
+        output_dir.to_str().expect("output path"),
+        "--fmt",
+    ])
+    .env("PATH", &path_env);
+
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Running aiken fmt"));
+
+    assert!(output_dir.join(".fmt-ran").exists());
+    let editorconfig =
+        fs::read_to_string(output_dir.join(".editorconfig")).expect("read .editorconfig");
+    assert!(editorconfig.contains("indent_size = 2"));
+}
@@ -0,0 +1,59 @@
+//! Non-fatal findings collected while generating a project. Errors stop generation;
+//! diagnostics ride along with the result so every entry point can show them.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{KaidoError, Result};
+
+/// A single finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Stable kebab-case identifier, e.g. "unchecked-action"
+    pub code: String,
+    pub message: String,
+    /// What the finding refers to, e.g. "CustomRedeemer.Claim"
+    pub location: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.message, self.code)
+    }
+}
+
+/// Findings gathered across composition and rendering
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    /// Record a warning
+    pub fn warn(&mut self, code: &str, message: String, location: Option<String>) {
+        self.warnings.push(Diagnostic {
+            code: code.to_string(),
+            message,
+            location,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    /// Append another set of findings
+    pub fn extend(&mut self, other: Diagnostics) {
+        self.warnings.extend(other.warnings);
+    }
+
+    /// In strict mode (`--strict` / `"strict": true`) any warning fails generation
+    pub fn check_strict(&self, strict: bool) -> Result<()> {
+        if !strict || self.is_empty() {
+            return Ok(());
+        }
+        let lines: Vec<String> = self.warnings.iter().map(|w| format!("  {}", w)).collect();
+        Err(KaidoError::StrictWarnings(lines.join("\n")))
+    }
+}
@@ -5,6 +5,9 @@ pub enum KaidoError {
     #[error("Template rendering failed: {0}")]
     TemplateError(#[from] tera::Error),
 
+    #[error("Template rendered '{0}' without any validator, fn or type declaration")]
+    EmptyRender(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -17,11 +20,20 @@ pub enum KaidoError {
     #[error("Aiken check failed:\n{0}")]
     AikenCheckFailed(String),
 
+    #[error("Aiken fmt failed:\n{0}")]
+    AikenFmtFailed(String),
+
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
     #[error("Aikido scan found issues:\n{0}")]
     AikidoScanFailed(String),
+
+    #[error("Verification failed:\n{0}")]
+    VerificationFailed(String),
+
+    #[error("Strict mode: generation produced warnings:\n{0}")]
+    StrictWarnings(String),
 }
 
 pub type Result<T> = std::result::Result<T, KaidoError>;
@@ -0,0 +1,164 @@
+//! CBOR diagnostic preview of the custom datum and redeemer, using the values the
+//! generated tests use. Plutus `Data` encodes a constructor as a tagged field list:
+//! tags 121..=127 for indices 0..=6, 1280..=1400 for 7..=127, and `102([index, fields])`
+//! beyond. Non-empty lists are indefinite-length (`[_ ...]`), as the ledger serialises them.
+
+use super::compose::{find_deadline_field, redeemer_field_value, test_value_for_type};
+use super::types::{split_top_level, DatumField, RedeemerAction};
+use super::{parse_features, Feature};
+use crate::error::Result;
+use crate::templates::GenerateOptions;
+
+/// (label, diagnostic) lines for the custom datum (spend only) and each redeemer action.
+/// Empty for templates other than custom.
+pub fn preview(options: &GenerateOptions) -> Result<Vec<(String, String)>> {
+    if !options.template.is_composed() {
+        return Ok(Vec::new());
+    }
+    let features = parse_features(&options.feature_names)?;
+    let mut lines = Vec::new();
+    if options.purpose == "spend" {
+        let expiry_field = options
+            .expiry_field
+            .as_deref()
+            .filter(|_| features.contains(&Feature::Expiry));
+        lines.push((
+            "CustomDatum".to_string(),
+            datum_diagnostic(
+                &options.datum_fields,
+                features.contains(&Feature::TimeLock),
+                expiry_field,
+                features.contains(&Feature::DeadlineWindow),
+                features.contains(&Feature::PeriodicLimit),
+            ),
+        ));
+    }
+    lines.extend(
+        redeemer_diagnostics(&options.redeemer_actions)
+            .into_iter()
+            .map(|(name, diag)| (format!("CustomRedeemer.{name}"), diag)),
+    );
+    Ok(lines)
+}
+
+/// The `test_datum()` value as constructor 0
+pub fn datum_diagnostic(
+    fields: &[DatumField],
+    has_timelock: bool,
+    expiry_field: Option<&str>,
+    has_window: bool,
+    has_periodic: bool,
+) -> String {
+    let deadline_field = find_deadline_field(fields);
+    let values: Vec<String> = fields
+        .iter()
+        .map(|f| {
+            literal_diagnostic(&test_value_for_type(
+                &f.aiken_type,
+                &f.name,
+                has_timelock,
+                &deadline_field,
+                expiry_field,
+                has_window,
+                has_periodic,
+            ))
+        })
+        .collect();
+    constr(0, &values)
+}
+
+/// Each action's test value, tagged with its position in the redeemer enum
+pub fn redeemer_diagnostics(actions: &[RedeemerAction]) -> Vec<(String, String)> {
+    actions
+        .iter()
+        .enumerate()
+        .map(|(index, action)| {
+            let values: Vec<String> = action
+                .fields
+                .iter()
+                .map(|(_, ty)| literal_diagnostic(&redeemer_field_value(ty)))
+                .collect();
+            (action.name.clone(), constr(index as u64, &values))
+        })
+        .collect()
+}
+
+fn constr(index: u64, fields: &[String]) -> String {
+    let fields = list(fields);
+    match index {
+        0..=6 => format!("{}({fields})", 121 + index),
+        7..=127 => format!("{}({fields})", 1280 + index - 7),
+        _ => format!("102([{index}, {fields}])"),
+    }
+}
+
+fn list(items: &[String]) -> String {
+    if items.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[_ {}]", items.join(", "))
+    }
+}
+
+/// Translate a generated Aiken test literal into diagnostic notation
+fn literal_diagnostic(literal: &str) -> String {
+    match literal {
+        "test_admin" => "h'aabbccdd'".to_string(),
+        "test_deadline" | "test_window_start" | "test_period_start" => "1000000".to_string(),
+        "test_expiry" | "test_window_end" => "2000000".to_string(),
+        "False" | "Void" => constr(0, &[]),
+        "True" => constr(1, &[]),
+        l if l.starts_with("#\"") => {
+            format!("h'{}'", l.trim_start_matches("#\"").trim_end_matches('"'))
+        }
+        // Tuples and lists both encode as arrays
+        l if (l.starts_with('(') && l.ends_with(')'))
+            || (l.starts_with('[') && l.ends_with(']')) =>
+        {
+            let elements: Vec<String> = split_top_level(&l[1..l.len() - 1])
+                .iter()
+                .map(|e| literal_diagnostic(e.trim()))
+                .collect();
+            list(&elements)
+        }
+        l => l.replace('_', ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::types::{parse_datum_fields, parse_redeemer_actions};
+
+    #[test]
+    fn test_two_field_datum_is_constructor_zero() {
+        let fields = parse_datum_fields("owner:ByteArray,amount:Int").unwrap();
+        assert_eq!(
+            datum_diagnostic(&fields, false, None, false, false),
+            "121([_ h'aabbccdd', 10000000])"
+        );
+
+        let fields = parse_datum_fields("pair:(ByteArray, Int),open:Bool,note:Data").unwrap();
+        assert_eq!(
+            datum_diagnostic(&fields, false, None, false, false),
+            "121([_ [_ h'aabbccdd', 0], 122([]), 121([])])"
+        );
+
+        let fields =
+            parse_datum_fields("signers:List<ByteArray>,threshold:Int,log:List<Int>").unwrap();
+        assert_eq!(
+            datum_diagnostic(&fields, false, None, false, false),
+            "121([_ [_ h'a1a1a1a1', h'b2b2b2b2', h'c3c3c3c3'], 2, []])"
+        );
+    }
+
+    #[test]
+    fn test_redeemer_constructor_indices() {
+        let actions = parse_redeemer_actions("A,B(amount:Int),C,D,E,F,G,H").unwrap();
+        let diags = redeemer_diagnostics(&actions);
+        assert_eq!(diags[0], ("A".to_string(), "121([])".to_string()));
+        assert_eq!(diags[1].1, "122([_ 5000000])");
+        assert_eq!(diags[7].1, "1280([])");
+        assert_eq!(constr(128, &[]), "102([128, []])");
+    }
+}
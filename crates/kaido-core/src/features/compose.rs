@@ -1,8 +1,15 @@
 use std::collections::HashSet;
 
-use super::types::{DatumField, RedeemerAction};
-use super::{feature_spec, Feature};
+use super::types::{
+    tuple_elements, DatumField, RedeemerAction, SeedUtxo, ADMIN_FIELD, BURN_ACTION, CLAIM_ACTION,
+    CLAIM_DEADLINE_FIELD, DEPOSITOR_FIELD, DEPOSIT_ACTION, PAUSED_FIELD, PERIOD_SPENT_FIELD,
+    PERIOD_START_FIELD, REFUND_ACTION, ROTATE_ADMIN_ACTION, SIGNERS_FIELD, THRESHOLD_FIELD,
+    TOTAL_POOL_FIELD, TOTAL_SHARES_FIELD, UNPAUSE_ACTION, WINDOW_END_FIELD, WINDOW_START_FIELD,
+};
+use super::{feature_spec, parse_features, Feature};
+use crate::diagnostics::Diagnostics;
 use crate::error::{KaidoError, Result};
+use crate::templates::{GenerateOptions, Template, TOKEN_LOCK_OWNER_FIELD};
 
 /// Result of composing multiple features into a single validator
 #[derive(Debug, Clone)]
@@ -19,8 +26,47 @@ pub struct ComposedValidator {
     pub test_helpers: String,
     /// Test cases
     pub test_cases: Vec<String>,
+    /// What each feature contributed, in composition order (the strings above are derived from these)
+    pub contributions: Vec<FeatureContribution>,
+    /// Non-fatal findings about the composition, e.g. actions no feature checks
+    pub diagnostics: Diagnostics,
 }
 
+/// The code one feature contributes to a composed validator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureContribution {
+    pub feature: Feature,
+    /// Import lines, before merging
+    pub imports: Vec<String>,
+    /// Validator parameters (name, type), before deduplication across features
+    pub params: Vec<(String, String)>,
+    /// Block emitted before the redeemer match
+    pub preamble: Option<String>,
+    /// Block emitted inside each redeemer branch
+    pub action_check: Option<String>,
+}
+
+/// Address search in the DatumContinuity preamble that `continuation-at-index` replaces
+const CONTINUATION_SEARCH: &str = concat!(
+    "    // Find continuing output at same address\n",
+    "    expect Some(cont_output) =\n",
+    "      list.find(self.outputs, fn(o) { o.address == own_address })",
+);
+
+/// `inline-datum-only` preamble when DatumContinuity has already found the own input
+const OWN_CREDENTIAL: &str = concat!(
+    "    // This script's payment credential\n",
+    "    let script_credential = own_address.payment_credential",
+);
+
+/// Keys test_datum() lists under `signers` for `signature-threshold`
+const TEST_SIGNER_A: &str = "#\"a1a1a1a1\"";
+const TEST_SIGNER_B: &str = "#\"b2b2b2b2\"";
+const TEST_SIGNER_C: &str = "#\"c3c3c3c3\"";
+
+/// Emitted in spend branches when no selected feature adds a per-action check
+const NO_CHECKS_WARNING: &str = "    // WARNING: no runtime checks on this action";
+
 /// Resolve feature dependencies and check for conflicts.
 /// Returns features in composition order (sorted by Feature::order()).
 pub fn resolve_features(selected: &[Feature], purpose: &str) -> Result<Vec<Feature>> {
@@ -78,19 +124,53 @@ pub fn resolve_features(selected: &[Feature], purpose: &str) -> Result<Vec<Featu
     Ok(resolved)
 }
 
-/// Compose resolved features into a single validator specification.
+/// How `compose` emits a validator, beyond its features, datum and redeemer.
+/// The default is a plain validator with placeholder field bindings.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ComposeOptions<'a> {
+    /// Prefix each emitted block with a comment naming the feature that produced it and the
+    /// property it enforces
+    pub explain: bool,
+    /// Precede each block with an Aiken `trace`, which only survives in builds with traces
+    /// enabled
+    pub trace: bool,
+    /// Add fuzz-based property tests (value preservation) to the generated tests
+    pub property_tests: bool,
+    /// Datum field `monotonic-counter` advances (see `types::resolve_counter_field`)
+    pub counter_field: Option<&'a str>,
+    /// Datum field `expiry` binds
+    pub expiry_field: Option<&'a str>,
+    /// UTxO the generated `unique-mint` tests consume (a placeholder if None)
+    pub seed_utxo: Option<&'a SeedUtxo>,
+    /// ByteArray datum field `signature-auth` checks instead of the `admin_pkh` parameter
+    pub signer_field: Option<&'a str>,
+}
+
+impl<'a> From<&'a GenerateOptions> for ComposeOptions<'a> {
+    fn from(options: &'a GenerateOptions) -> Self {
+        Self {
+            explain: options.explain,
+            trace: options.trace,
+            property_tests: options.property_tests,
+            counter_field: options.counter_field.as_deref(),
+            expiry_field: options.expiry_field.as_deref(),
+            seed_utxo: options.seed_utxo.as_ref(),
+            signer_field: (options.template == Template::TokenLock)
+                .then_some(TOKEN_LOCK_OWNER_FIELD),
+        }
+    }
+}
+
+/// Compose resolved features into a single validator specification
 pub fn compose(
     features: &[Feature],
     purpose: &str,
     datum_fields: &[DatumField],
     redeemer_actions: &[RedeemerAction],
     validator_name: &str,
+    opts: &ComposeOptions,
 ) -> Result<ComposedValidator> {
     let mut import_set: HashSet<String> = HashSet::new();
-    let mut params: Vec<(String, String)> = Vec::new();
-    let mut preamble_parts: Vec<String> = Vec::new();
-    let mut action_parts: Vec<String> = Vec::new();
-    let mut param_names: HashSet<String> = HashSet::new();
 
     // Base imports depending on purpose
     // Need both `use cardano/transaction` (for transaction.placeholder in tests)
@@ -105,44 +185,88 @@ pub fn compose(
         import_set.insert("use cardano/transaction.{Transaction}".to_string());
     }
 
-    // Collect from each feature
-    for f in features {
-        let spec = feature_spec(*f);
-
-        for imp in &spec.imports {
-            import_set.insert(imp.to_string());
-        }
+    let contributions: Vec<FeatureContribution> = features
+        .iter()
+        .map(|f| contribution(*f, features, purpose, datum_fields, opts))
+        .collect();
 
-        for (name, ty) in &spec.validator_params {
-            if param_names.insert(name.to_string()) {
-                params.push((name.to_string(), ty.to_string()));
+    // Derive the merged pieces from the per-feature contributions
+    let mut params: Vec<(String, String)> = Vec::new();
+    let mut param_names: HashSet<String> = HashSet::new();
+    for c in &contributions {
+        import_set.extend(c.imports.iter().cloned());
+        for (name, ty) in &c.params {
+            if param_names.insert(name.clone()) {
+                params.push((name.clone(), ty.clone()));
             }
         }
+    }
+    let preamble_parts: Vec<String> = contributions
+        .iter()
+        .filter_map(|c| c.preamble.clone())
+        .collect();
+    let mut action_parts: Vec<String> = contributions
+        .iter()
+        .filter_map(|c| c.action_check.clone())
+        .collect();
 
-        if !spec.preamble_code.is_empty() {
-            let code = spec
-                .preamble_code
-                .replace("CustomDatum", &datum_type_name(datum_fields));
-            preamble_parts.push(code);
+    // A spend branch with no per-action check accepts any transaction the preamble lets through.
+    // (Mint branches always check the minted quantities.)
+    let mut diagnostics = Diagnostics::default();
+    if purpose == "spend" && action_parts.is_empty() {
+        action_parts.push(NO_CHECKS_WARNING.to_string());
+        for action in redeemer_actions {
+            diagnostics.warn(
+                "unchecked-action",
+                format!(
+                    "Action '{}' has no per-action checks; add e.g. signature-auth or timelock to restrict who can run it",
+                    action.name
+                ),
+                Some(format!("CustomRedeemer.{}", action.name)),
+            );
         }
+    }
 
-        if !spec.per_action_code.is_empty() {
-            // For TimeLock, replace datum.deadline with the actual field name
-            let mut code = spec.per_action_code.to_string();
-            if *f == Feature::TimeLock {
-                if let Some(deadline_field) = find_deadline_field(datum_fields) {
-                    code = code.replace("datum.deadline", &format!("datum.{}", deadline_field));
-                }
-            }
-            action_parts.push(code);
+    // A mint branch only checks that something positive is minted; without a feature limiting
+    // the supply, whoever passes the other checks mints any amount of any token name
+    if purpose == "mint" {
+        if !features
+            .iter()
+            .any(|f| matches!(f, Feature::ExactMintAmount | Feature::UniqueMint))
+        {
+            diagnostics.warn(
+                "unconstrained-mint",
+                "Mint is unconstrained: no feature limits the minted quantity or token names; add e.g. exact-mint-amount or unique-mint".to_string(),
+                None,
+            );
+        }
+        if redeemer_actions.iter().all(|a| a.name == BURN_ACTION) {
+            diagnostics.warn(
+                "no-mint-action",
+                format!(
+                    "No redeemer action mints: '{}' only burns; add a mint action (e.g. Mint,Burn)",
+                    BURN_ACTION
+                ),
+                Some("CustomRedeemer".to_string()),
+            );
+        }
+        // BurnVerification only checks the Burn branch; without one it checks nothing
+        if features.contains(&Feature::BurnVerification)
+            && !redeemer_actions.iter().any(|a| a.name == BURN_ACTION)
+        {
+            let names: Vec<&str> = redeemer_actions.iter().map(|a| a.name.as_str()).collect();
+            diagnostics.warn(
+                "burn-without-burn-action",
+                format!(
+                    "Feature 'burn-verification' only checks a redeemer action named '{}' (e.g. Mint,Burn), found: {}; it checks nothing",
+                    BURN_ACTION,
+                    names.join(", ")
+                ),
+                Some("CustomRedeemer".to_string()),
+            );
         }
     }
 
-    // Merge imports from the same module path
-    // e.g., "use cardano/transaction.{A, B}" + "use cardano/transaction.{C, D}"
-    //     => "use cardano/transaction.{A, B, C, D}"
-    let imports = merge_imports(import_set);
-
     // Build preamble
     let preamble = preamble_parts.join("\n\n");
 
@@ -154,7 +278,16 @@ pub fn compose(
         redeemer_actions,
         &params,
         validator_name,
+        opts,
     );
+    if test_cases.iter().any(|t| t.contains(" via fuzz.")) {
+        import_set.insert("use aiken/fuzz".to_string());
+    }
+
+    // Merge imports from the same module path
+    // e.g., "use cardano/transaction.{A, B}" + "use cardano/transaction.{C, D}"
+    //     => "use cardano/transaction.{A, B, C, D}"
+    let imports = merge_imports(import_set);
 
     Ok(ComposedValidator {
         imports,
@@ -163,32 +296,183 @@ pub fn compose(
         action_checks: action_parts,
         test_helpers,
         test_cases,
+        contributions,
+        diagnostics,
     })
 }
 
+impl ComposedValidator {
+    /// Each validator parameter with the feature that added it, in parameter order
+    pub fn param_descriptions(&self) -> Vec<(String, String)> {
+        self.validator_params
+            .iter()
+            .filter_map(|(name, _)| {
+                self.contributions
+                    .iter()
+                    .find(|c| c.params.iter().any(|(n, _)| n == name))
+                    .map(|c| (name.clone(), format!("added by {}", c.feature.name())))
+            })
+            .collect()
+    }
+}
+
+/// Compose the validator of custom-template `options`, without rendering the project
+pub(crate) fn compose_options(options: &GenerateOptions) -> Result<ComposedValidator> {
+    let parsed = parse_features(&options.feature_names)?;
+    let resolved = resolve_features(&parsed, &options.purpose)?;
+    compose(
+        &resolved,
+        &options.purpose,
+        &options.datum_fields,
+        &options.redeemer_actions,
+        &options.validator_name,
+        &ComposeOptions::from(options),
+    )
+}
+
+/// The test cases (positive and negative) `compose` would embed in a custom validator,
+/// without rendering the project. Empty for templates other than custom.
+pub fn preview_tests(options: &GenerateOptions) -> Result<Vec<String>> {
+    if !options.template.is_composed() {
+        return Ok(Vec::new());
+    }
+    Ok(compose_options(options)?.test_cases)
+}
+
+/// Build the code a single feature contributes, with field names substituted
+fn contribution(
+    f: Feature,
+    features: &[Feature],
+    purpose: &str,
+    datum_fields: &[DatumField],
+    opts: &ComposeOptions,
+) -> FeatureContribution {
+    let ComposeOptions {
+        explain,
+        trace,
+        counter_field,
+        expiry_field,
+        signer_field,
+        ..
+    } = *opts;
+    let spec = feature_spec(f);
+
+    // Mint validators have no preamble; inline-datum-only checks against the policy instead
+    let inline_in_mint = f == Feature::InlineDatumOnly && purpose == "mint";
+
+    // ContinuationAtIndex has no block of its own; it rewrites the continuity lookup
+    let preamble = (!spec.preamble_code.is_empty()
+        && f != Feature::ContinuationAtIndex
+        && !inline_in_mint)
+        .then(|| {
+            let mut code = spec
+                .preamble_code
+                .replace("CustomDatum", &datum_type_name(datum_fields));
+            if f == Feature::DatumContinuity && features.contains(&Feature::ContinuationAtIndex) {
+                let indexed = feature_spec(Feature::ContinuationAtIndex).preamble_code;
+                code = code.replace(CONTINUATION_SEARCH, indexed);
+            }
+            if f == Feature::InlineDatumOnly && features.contains(&Feature::DatumContinuity) {
+                code = OWN_CREDENTIAL.to_string();
+            }
+            with_provenance(f, with_trace(f, code, trace), explain)
+        });
+
+    // With admin rotation the signer is the datum admin, not a fixed param
+    let datum_signer = if features.contains(&Feature::AdminRotation) {
+        Some(ADMIN_FIELD)
+    } else {
+        signer_field
+    }
+    .filter(|_| f == Feature::SignatureAuth);
+    let admin_in_datum = datum_signer.is_some();
+
+    let action_check = (!spec.per_action_code.is_empty()).then(|| {
+        // For TimeLock, replace datum.deadline with the actual field name
+        let mut code = spec.per_action_code.to_string();
+        if let Some(field) = datum_signer {
+            code = code.replace("admin_pkh", &format!("datum.{field}"));
+        }
+        if f == Feature::TimeLock {
+            if let Some(deadline_field) = find_deadline_field(datum_fields) {
+                code = code.replace("datum.deadline", &format!("datum.{}", deadline_field));
+            }
+        }
+        // For MonotonicCounter, substitute the resolved counter field
+        if f == Feature::MonotonicCounter {
+            code = code.replace("counter_field", counter_field.unwrap_or("nonce"));
+        }
+        if inline_in_mint {
+            code = code.replace("script_credential", "Script(policy_id)");
+        }
+        if f == Feature::Expiry {
+            code = code.replace(
+                "datum.expiry",
+                &format!("datum.{}", expiry_field.unwrap_or("expiry")),
+            );
+        }
+        with_provenance(f, with_trace(f, code, trace), explain)
+    });
+
+    let mut imports: Vec<String> = spec.imports.iter().map(|i| i.to_string()).collect();
+    if f == Feature::InlineDatumOnly && purpose == "spend" {
+        // For the script_input() test helper, which DatumContinuity may not provide
+        imports.push("use cardano/assets".to_string());
+        imports.push("use cardano/transaction.{Input}".to_string());
+    }
+
+    FeatureContribution {
+        feature: f,
+        imports,
+        params: spec
+            .validator_params
+            .iter()
+            .filter(|_| !admin_in_datum)
+            .map(|(name, ty)| (name.to_string(), ty.to_string()))
+            .collect(),
+        preamble,
+        action_check,
+    }
+}
+
 /// Merge imports from the same module into a single line.
 /// e.g., "use cardano/transaction.{A}" + "use cardano/transaction.{B, C}"
 ///     => "use cardano/transaction.{A, B, C}"
+///
+/// Whitespace is normalized first, so "use  cardano/assets " and "use cardano/assets" are one
+/// import. A bare import may still sit next to a typed one for the same module (e.g.
+/// "use cardano/assets" and "use cardano/assets.{PolicyId}"): the bare line is what allows
+/// qualified calls like `assets.quantity_of`, so the pair is kept on purpose.
 fn merge_imports(import_set: HashSet<String>) -> Vec<String> {
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
     let mut module_types: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    let mut plain_imports: Vec<String> = Vec::new();
+    let mut plain_imports: BTreeSet<String> = BTreeSet::new();
 
     for imp in &import_set {
-        if let Some(dot_pos) = imp.find(".{") {
-            let module = imp[..dot_pos].to_string();
-            let types_str = &imp[dot_pos + 2..imp.len() - 1]; // strip ".{" and "}"
-            let types: Vec<String> = types_str.split(',').map(|s| s.trim().to_string()).collect();
+        // A module path never contains spaces, so all whitespace after `use` can go
+        let imp = imp.trim();
+        let body: String = imp
+            .strip_prefix("use")
+            .unwrap_or(imp)
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        if let Some(dot_pos) = body.find(".{") {
+            let module = format!("use {}", &body[..dot_pos]);
+            let types_str = body[dot_pos + 2..].trim_end_matches('}');
+            let types = types_str
+                .split(',')
+                .filter(|t| !t.is_empty())
+                .map(str::to_string);
             module_types.entry(module).or_default().extend(types);
         } else {
-            plain_imports.push(imp.clone());
+            plain_imports.insert(format!("use {}", body));
         }
     }
 
     let mut result = Vec::new();
 
     // Add plain imports first
-    plain_imports.sort();
     result.extend(plain_imports);
 
     // Add merged module imports
@@ -202,12 +486,40 @@ fn merge_imports(import_set: HashSet<String>) -> Vec<String> {
     result
 }
 
+/// Prefix a code block with a `trace @"X: <description>"` statement when tracing.
+fn with_trace(feature: Feature, code: String, trace: bool) -> String {
+    if trace {
+        format!(
+            "    trace @\"{}: {}\"\n{}",
+            feature.name(),
+            feature.description(),
+            code
+        )
+    } else {
+        code
+    }
+}
+
+/// Prefix a code block with a `// [feature: X] <description>` comment when explaining.
+fn with_provenance(feature: Feature, code: String, explain: bool) -> String {
+    if explain {
+        format!(
+            "    // [feature: {}] {}\n{}",
+            feature.name(),
+            feature.description(),
+            code
+        )
+    } else {
+        code
+    }
+}
+
 fn datum_type_name(_fields: &[DatumField]) -> String {
     "CustomDatum".to_string()
 }
 
 /// Find the first Int field that looks like a deadline
-fn find_deadline_field(fields: &[DatumField]) -> Option<String> {
+pub(crate) fn find_deadline_field(fields: &[DatumField]) -> Option<String> {
     // Look for fields named deadline, lock_until, expiry, etc.
     let deadline_names = [
         "deadline",
@@ -216,8 +528,12 @@ fn find_deadline_field(fields: &[DatumField]) -> Option<String> {
         "expires_at",
         "lock_time",
     ];
+    // Only Int fields can hold a POSIX time; an opaque `Data` field named `deadline` is skipped
     for name in &deadline_names {
-        if fields.iter().any(|f| f.name == *name) {
+        if fields
+            .iter()
+            .any(|f| f.name == *name && f.aiken_type == "Int")
+        {
             return Some(name.to_string());
         }
     }
@@ -235,30 +551,220 @@ fn build_tests(
     redeemer_actions: &[RedeemerAction],
     params: &[(String, String)],
     validator_name: &str,
+    opts: &ComposeOptions,
 ) -> (String, Vec<String>) {
+    let ComposeOptions {
+        property_tests,
+        counter_field,
+        expiry_field,
+        seed_utxo,
+        ..
+    } = *opts;
     let mut helpers = String::new();
     let mut cases = Vec::new();
 
     let has_sig = features.contains(&Feature::SignatureAuth);
     let has_timelock = features.contains(&Feature::TimeLock);
+    let expiry = expiry_field.filter(|_| features.contains(&Feature::Expiry));
+    let has_window = features.contains(&Feature::DeadlineWindow);
     let has_continuity = features.contains(&Feature::DatumContinuity);
-    let _has_value = features.contains(&Feature::ValuePreservation);
+    let has_value = features.contains(&Feature::ValuePreservation);
     let has_ref_safety = features.contains(&Feature::ReferenceSafety);
     let _has_bounded = features.contains(&Feature::BoundedOperations);
+    let has_change_to_self = features.contains(&Feature::ChangeToSelf);
+    let has_exact_mint = features.contains(&Feature::ExactMintAmount);
+    let has_unique_mint = features.contains(&Feature::UniqueMint);
+    let has_cip25 = features.contains(&Feature::Cip25Metadata);
+    let has_cont_index = features.contains(&Feature::ContinuationAtIndex);
+    let has_pausable = features.contains(&Feature::Pausable);
+    let has_rotation = features.contains(&Feature::AdminRotation);
+    let has_swap_fee = features.contains(&Feature::SwapFee);
+    let has_require_ref_script = features.contains(&Feature::RequireReferenceScript);
+    let has_inline_only = features.contains(&Feature::InlineDatumOnly);
+    let has_threshold = features.contains(&Feature::SignatureThresholdFromDatum);
+    let has_whitelist = features.contains(&Feature::AssetWhitelist);
+    let has_periodic = features.contains(&Feature::PeriodicLimit);
+    let has_token_gate = features.contains(&Feature::TokenGated);
+    let has_nft = features.contains(&Feature::NftPreservation);
+    let has_payout = features.contains(&Feature::ProportionalPayout);
+    let has_min_signers = features.contains(&Feature::MinSigners);
+    let has_deposit_only = features.contains(&Feature::DepositOnly);
+    let has_bounded_validity = features.contains(&Feature::BoundedValidity);
+    let has_refund = features.contains(&Feature::RefundableDeposit);
+    let has_no_ref_inputs = features.contains(&Feature::NoReferenceInputs);
+    let has_no_minting = features.contains(&Feature::NoMinting);
+    // Two of the three test_datum() signers meet its threshold of 2
+    let threshold_signers = if has_threshold {
+        format!(", {TEST_SIGNER_A}, {TEST_SIGNER_B}")
+    } else {
+        String::new()
+    };
+    // Signatures the positive path carries; min-signers adds a cosigner on top of the keys
+    // the other checks want
+    let cosigner = if has_min_signers {
+        ", test_cosigner"
+    } else {
+        ""
+    };
+    let signatories = |with_cosigner: bool| {
+        (has_sig || has_threshold || has_min_signers).then(|| {
+            let mut signers = Vec::new();
+            if has_sig {
+                signers.push("test_admin");
+            }
+            if has_min_signers && with_cosigner {
+                signers.push("test_cosigner");
+            }
+            if has_threshold {
+                signers.extend([TEST_SIGNER_A, TEST_SIGNER_B]);
+            }
+            format!("      extra_signatories: [{}],", signers.join(", "))
+        })
+    };
+    let signatories_ok = signatories(true);
+    // Reference script the continuing output carries in the positive path
+    let cont_ref_script = if has_require_ref_script {
+        "Some(#\"deadbeef\")"
+    } else {
+        "None"
+    };
+    // The continuing output carries the advanced datum when a counter or swap fee is enforced
+    let counter = counter_field.filter(|_| features.contains(&Feature::MonotonicCounter));
+    let mut next_updates = Vec::new();
+    if let Some(field) = counter {
+        next_updates.push(format!("{field}: test_datum().{field} + 1"));
+    }
+    if has_swap_fee {
+        // A 1% trade in, 0.6% out: the product grows by ~0.4%, above the 0.3% test fee
+        next_updates.push("reserve_a: test_datum().reserve_a + 100_000".to_string());
+        next_updates.push("reserve_b: test_datum().reserve_b - 60_000".to_string());
+    }
+    if has_periodic {
+        next_updates.push(format!(
+            "{PERIOD_SPENT_FIELD}: test_datum().{PERIOD_SPENT_FIELD} + test_withdrawal"
+        ));
+    }
+    let cont_datum = if next_updates.is_empty() {
+        "test_datum()"
+    } else {
+        "test_datum_next()"
+    };
+    // With NFT preservation the script input holds an NFT that every output to the script keeps
+    let with_nft = |value: &str| {
+        if has_nft {
+            format!("assets.add({value}, test_nft_policy, test_nft_name, 1)")
+        } else {
+            value.to_string()
+        }
+    };
+    let script_value = with_nft("assets.from_lovelace(10_000_000)");
+    // With a spending limit the positive path withdraws test_withdrawal from the script
+    let cont_value = &if has_periodic {
+        with_nft("assets.from_lovelace(10_000_000 - test_withdrawal)")
+    } else {
+        script_value.clone()
+    };
 
     // Test constants
     if has_sig {
         helpers.push_str("const test_admin: ByteArray = #\"aabbccdd\"\n");
     }
+    if has_rotation {
+        helpers.push_str("const test_new_admin: ByteArray = #\"99999999\"\n");
+    }
+    if has_min_signers {
+        // Every key the positive path signs with, so dropping the cosigner falls one short
+        let signer_count = usize::from(has_sig) + 1 + if has_threshold { 2 } else { 0 };
+        helpers.push_str("const test_cosigner: ByteArray = #\"d4d4d4d4\"\n");
+        helpers.push_str(&format!("const test_min_signers: Int = {signer_count}\n"));
+    }
     if purpose == "mint" {
         helpers.push_str("const test_policy: ByteArray = #\"eeff0011\"\n");
     }
+    if has_exact_mint {
+        helpers.push_str("const test_max_supply: Int = 1_000\n");
+    }
+    if has_token_gate {
+        helpers.push_str("const test_gov_policy: ByteArray = #\"c0ffee\"\n");
+        helpers.push_str("const test_gov_asset: ByteArray = \"gov\"\n");
+        helpers.push_str("const test_min_tokens: Int = 100\n");
+    }
+    if has_nft {
+        // The #"00" asset-whitelist test params, so a whitelist accepts the NFT too
+        helpers.push_str("const test_nft_policy: ByteArray = #\"00\"\n");
+        helpers.push_str("const test_nft_name: ByteArray = #\"00\"\n");
+    }
 
     // Deadline constant
     let deadline_field = find_deadline_field(datum_fields);
     if has_timelock {
         helpers.push_str("const test_deadline: Int = 1_000_000\n");
     }
+    if expiry.is_some() {
+        helpers.push_str("const test_expiry: Int = 2_000_000\n");
+    }
+    if has_window {
+        helpers.push_str("const test_window_start: Int = 1_000_000\n");
+        helpers.push_str("const test_window_end: Int = 2_000_000\n");
+    }
+    if has_periodic {
+        // Starts with test_deadline/test_window_start, so their ranges fall in the first period
+        helpers.push_str("const test_period_start: Int = 1_000_000\n");
+        helpers.push_str("const test_period: Int = 500_000\n");
+        helpers.push_str("const test_limit: Int = 5_000_000\n");
+        helpers.push_str("const test_withdrawal: Int = 1_000_000\n");
+    }
+    if has_payout {
+        // 2 of 7 shares of test_datum()'s 10_000_000 pool: 2_857_142.86, floored
+        helpers.push_str("const test_total_shares: Int = 7\n");
+        helpers.push_str("const test_claimant_share: Int = 2\n");
+        helpers.push_str("const test_payout: Int = 2_857_142\n");
+    }
+    if has_deposit_only {
+        helpers.push_str("const test_deposit: Int = 1_000_000\n");
+    }
+    if has_bounded_validity {
+        // Narrower than every time window above, so a bounded range fits inside each
+        helpers.push_str("const test_max_ttl: Int = 100_000\n");
+    }
+    if has_refund {
+        // Later than every time window above, so the positive refund fits inside each
+        helpers.push_str("const test_depositor: ByteArray = #\"d5d5d5d5\"\n");
+        helpers.push_str("const test_claim_deadline: Int = 3_000_000\n");
+    }
+
+    // Bounds of the validity range satisfying the time checks in the positive path
+    // (periodic-limit needs a finite lower bound to read the current time from)
+    let (valid_from, valid_until) = match (has_timelock, expiry.is_some()) {
+        (true, true) => (Some("test_deadline + 1"), Some("test_expiry - 1")),
+        (true, false) => (Some("test_deadline + 1"), None),
+        (false, true) if has_periodic => (Some("test_period_start + 1"), Some("test_expiry - 1")),
+        (false, true) => (None, Some("test_expiry - 1")),
+        // deadline-window conflicts with both
+        (false, false) if has_window => {
+            (Some("test_window_start + 1"), Some("test_window_end - 1"))
+        }
+        (false, false) if has_periodic => (Some("test_period_start + 1"), None),
+        (false, false) => (None, None),
+    };
+    // bounded-validity wants both ends finite and at most test_max_ttl apart: anchor a range of
+    // `width` at whichever bound the other checks need
+    let bounded_range = |width: &str| match (valid_from, valid_until) {
+        (Some(from), _) => format!("interval.between({from}, {from} + {width})"),
+        (None, Some(until)) => format!("interval.between({until} - {width}, {until})"),
+        (None, None) => format!("interval.between(0, {width})"),
+    };
+    let validity_ok = if has_bounded_validity {
+        Some(bounded_range("test_max_ttl"))
+    } else {
+        match (valid_from, valid_until) {
+            (Some(from), Some(until)) => Some(format!("interval.between({from}, {until})")),
+            (Some(from), None) => Some(format!("interval.after({from})")),
+            (None, Some(until)) => Some(format!("interval.before({until})")),
+            (None, None) => None,
+        }
+    };
+    let validity_ok = validity_ok.as_deref();
 
     helpers.push('\n');
 
@@ -267,17 +773,41 @@ fn build_tests(
         helpers.push_str("fn test_datum() -> CustomDatum {\n");
         helpers.push_str("  CustomDatum {\n");
         for field in datum_fields {
-            let value = test_value_for_type(
-                &field.aiken_type,
-                &field.name,
-                has_timelock,
-                &deadline_field,
-            );
+            let value = match field.name.as_str() {
+                DEPOSITOR_FIELD if has_refund => "test_depositor".to_string(),
+                CLAIM_DEADLINE_FIELD if has_refund => "test_claim_deadline".to_string(),
+                _ => test_value_for_type(
+                    &field.aiken_type,
+                    &field.name,
+                    has_timelock,
+                    &deadline_field,
+                    expiry,
+                    has_window,
+                    has_periodic,
+                ),
+            };
             helpers.push_str(&format!("    {}: {},\n", field.name, value));
         }
         helpers.push_str("  }\n");
         helpers.push_str("}\n\n");
 
+        if has_pausable {
+            helpers.push_str("fn test_datum_paused() -> CustomDatum {\n");
+            helpers.push_str(&format!(
+                "  CustomDatum {{ ..test_datum(), {PAUSED_FIELD}: True }}\n"
+            ));
+            helpers.push_str("}\n\n");
+        }
+
+        if !next_updates.is_empty() {
+            helpers.push_str("fn test_datum_next() -> CustomDatum {\n");
+            helpers.push_str(&format!(
+                "  CustomDatum {{ ..test_datum(), {} }}\n",
+                next_updates.join(", ")
+            ));
+            helpers.push_str("}\n\n");
+        }
+
         // OutputReference helper
         helpers.push_str("fn test_oref() -> OutputReference {\n");
         helpers.push_str("  OutputReference {\n");
@@ -286,7 +816,7 @@ fn build_tests(
         helpers.push_str("  }\n");
         helpers.push_str("}\n");
 
-        if has_continuity {
+        if has_continuity || has_inline_only || has_refund {
             helpers.push_str("\nfn script_addr() -> Address {\n");
             helpers.push_str(
                 "  Address { payment_credential: Script(#\"ee\"), stake_credential: None }\n",
@@ -298,24 +828,138 @@ fn build_tests(
             helpers.push_str("    output_reference: test_oref(),\n");
             helpers.push_str("    output: Output {\n");
             helpers.push_str("      address: script_addr(),\n");
-            helpers.push_str("      value: assets.from_lovelace(10_000_000),\n");
+            helpers.push_str(&format!("      value: {},\n", script_value));
             helpers.push_str("      datum: InlineDatum(test_datum()),\n");
             helpers.push_str("      reference_script: None,\n");
             helpers.push_str("    },\n");
             helpers.push_str("  }\n");
             helpers.push_str("}\n");
+        }
 
+        if has_continuity {
             helpers.push_str("\nfn cont_output_ok() -> Output {\n");
             helpers.push_str("  Output {\n");
             helpers.push_str("    address: script_addr(),\n");
-            helpers.push_str("    value: assets.from_lovelace(10_000_000),\n");
-            helpers.push_str("    datum: InlineDatum(test_datum()),\n");
-            helpers.push_str("    reference_script: None,\n");
+            helpers.push_str(&format!("    value: {},\n", cont_value));
+            helpers.push_str(&format!("    datum: InlineDatum({}),\n", cont_datum));
+            helpers.push_str(&format!("    reference_script: {},\n", cont_ref_script));
             helpers.push_str("  }\n");
             helpers.push_str("}\n");
         }
     }
 
+    // Output committing the CIP-25 721 map for the minted token
+    if has_cip25 {
+        helpers.push_str("fn test_cip25_output() -> Output {\n");
+        helpers.push_str("  let metadata: Pairs<Int, Pairs<ByteArray, Pairs<ByteArray, Pairs<ByteArray, ByteArray>>>> =\n");
+        helpers.push_str("    [Pair(721, [Pair(test_policy, [Pair(\"token\", [Pair(\"name\", \"Token\")])])])]\n");
+        helpers.push_str("  Output {\n");
+        helpers.push_str("    address: address.from_verification_key(#\"aabbccdd\"),\n");
+        helpers.push_str("    value: assets.from_lovelace(2_000_000),\n");
+        helpers.push_str("    datum: InlineDatum(metadata),\n");
+        helpers.push_str("    reference_script: None,\n");
+        helpers.push_str("  }\n");
+        helpers.push_str("}\n\n");
+    }
+
+    // Seed UTxO helpers for one-shot mints
+    if has_unique_mint {
+        // --seed-utxo, so the test consumes the UTxO the real mint will spend
+        let (seed_tx, seed_index) = match seed_utxo {
+            Some(seed) => (seed.transaction_id.as_str(), seed.output_index),
+            None => (
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                0,
+            ),
+        };
+        helpers.push_str("fn test_seed() -> OutputReference {\n");
+        helpers.push_str("  OutputReference {\n");
+        helpers.push_str(&format!("    transaction_id: #\"{}\",\n", seed_tx));
+        helpers.push_str(&format!("    output_index: {},\n", seed_index));
+        helpers.push_str("  }\n");
+        helpers.push_str("}\n");
+
+        helpers.push_str("\nfn seed_input() -> Input {\n");
+        helpers.push_str("  Input {\n");
+        helpers.push_str("    output_reference: test_seed(),\n");
+        helpers.push_str("    output: Output {\n");
+        helpers.push_str("      address: address.from_verification_key(#\"aabbccdd\"),\n");
+        helpers.push_str("      value: assets.from_lovelace(2_000_000),\n");
+        helpers.push_str("      datum: NoDatum,\n");
+        helpers.push_str("      reference_script: None,\n");
+        helpers.push_str("    },\n");
+        helpers.push_str("  }\n");
+        helpers.push_str("}\n");
+    }
+
+    // Input holding exactly the governance tokens the token gate asks for
+    if has_token_gate {
+        helpers.push_str("\nfn gov_input() -> Input {\n");
+        helpers.push_str("  Input {\n");
+        helpers.push_str("    output_reference: OutputReference {\n");
+        helpers.push_str("      transaction_id: #\"0000000000000000000000000000000000000000000000000000000000000004\",\n");
+        helpers.push_str("      output_index: 0,\n");
+        helpers.push_str("    },\n");
+        helpers.push_str("    output: Output {\n");
+        helpers.push_str("      address: address.from_verification_key(#\"aabbccdd\"),\n");
+        helpers.push_str("      value: assets.merge(\n");
+        helpers.push_str("        assets.from_lovelace(2_000_000),\n");
+        helpers.push_str(
+            "        assets.from_asset(test_gov_policy, test_gov_asset, test_min_tokens),\n",
+        );
+        helpers.push_str("      ),\n");
+        helpers.push_str("      datum: NoDatum,\n");
+        helpers.push_str("      reference_script: None,\n");
+        helpers.push_str("    },\n");
+        helpers.push_str("  }\n");
+        helpers.push_str("}\n");
+    }
+
+    // Output to this script hiding its datum behind a hash
+    if has_inline_only {
+        let script_address = if purpose == "spend" {
+            "script_addr()"
+        } else {
+            "Address { payment_credential: Script(test_policy), stake_credential: None }"
+        };
+        helpers.push_str("\nfn datum_hash_output() -> Output {\n");
+        helpers.push_str("  Output {\n");
+        helpers.push_str(&format!("    address: {},\n", script_address));
+        helpers.push_str("    value: assets.from_lovelace(2_000_000),\n");
+        helpers.push_str("    datum: DatumHash(#\"0000000000000000000000000000000000000000000000000000000000000003\"),\n");
+        helpers.push_str("    reference_script: None,\n");
+        helpers.push_str("  }\n");
+        helpers.push_str("}\n");
+    }
+
+    // Any UTxO to reference; no-reference-inputs must reject it whatever it holds
+    if has_no_ref_inputs {
+        helpers.push_str("\nfn stray_reference_input() -> Input {\n");
+        helpers.push_str("  Input {\n");
+        helpers.push_str("    output_reference: OutputReference {\n");
+        helpers.push_str("      transaction_id: #\"0000000000000000000000000000000000000000000000000000000000000005\",\n");
+        helpers.push_str("      output_index: 0,\n");
+        helpers.push_str("    },\n");
+        helpers.push_str("    output: Output {\n");
+        helpers.push_str("      address: address.from_verification_key(#\"aabbccdd\"),\n");
+        helpers.push_str("      value: assets.from_lovelace(2_000_000),\n");
+        helpers.push_str("      datum: NoDatum,\n");
+        helpers.push_str("      reference_script: None,\n");
+        helpers.push_str("    },\n");
+        helpers.push_str("  }\n");
+        helpers.push_str("}\n");
+    }
+
+    // Wallets change-to-self allowlists: a payee and the fee payer collecting their change
+    if has_change_to_self {
+        helpers.push_str("\nfn test_payee() -> Address {\n");
+        helpers.push_str("  address.from_verification_key(#\"aa01\")\n");
+        helpers.push_str("}\n");
+        helpers.push_str("\nfn test_fee_payer() -> Address {\n");
+        helpers.push_str("  address.from_verification_key(#\"aa02\")\n");
+        helpers.push_str("}\n");
+    }
+
     // Build param args string for test calls
     let param_args: Vec<String> = params
         .iter()
@@ -323,6 +967,12 @@ fn build_tests(
             "ByteArray" => {
                 if name == "admin_pkh" {
                     "test_admin".to_string()
+                } else if name == "mint_asset_name" {
+                    "\"token\"".to_string()
+                } else if name == "gov_policy" {
+                    "test_gov_policy".to_string()
+                } else if name == "gov_asset" {
+                    "test_gov_asset".to_string()
                 } else {
                     "#\"00\"".to_string()
                 }
@@ -330,10 +980,30 @@ fn build_tests(
             "Int" => {
                 if name == "min_lovelace" {
                     "2_000_000".to_string()
+                } else if name == "continuation_index" {
+                    "0".to_string()
+                } else if name == "max_supply" {
+                    "test_max_supply".to_string()
+                } else if name == "fee_bps" {
+                    "30".to_string()
+                } else if name == "period" {
+                    "test_period".to_string()
+                } else if name == "limit" {
+                    "test_limit".to_string()
+                } else if name == "min_tokens" {
+                    "test_min_tokens".to_string()
+                } else if name == "min_signers" {
+                    "test_min_signers".to_string()
+                } else if name == "max_ttl" {
+                    "test_max_ttl".to_string()
                 } else {
                     "100".to_string()
                 }
             }
+            "OutputReference" => "test_seed()".to_string(),
+            "List<Address>" if name == "change_recipients" => {
+                "[test_payee(), test_fee_payer()]".to_string()
+            }
             _ => "todo".to_string(),
         })
         .collect();
@@ -341,19 +1011,28 @@ fn build_tests(
 
     // Generate test cases per action
     if purpose == "spend" {
-        let first_action = redeemer_actions.first();
+        // Built from the positive transaction, before the governance input is added below
+        let mut no_gov_case = None;
+
+        // RotateAdmin and Claim must change the datum, a Deposit may not withdraw and a Refund
+        // must pay the depositor, so the shared tests run another action
+        let first_action = redeemer_actions.iter().find(|a| {
+            !(has_rotation && a.name == ROTATE_ADMIN_ACTION
+                || has_payout && a.name == CLAIM_ACTION
+                || has_deposit_only && a.name == DEPOSIT_ACTION
+                || has_refund && a.name == REFUND_ACTION)
+        });
 
         if let Some(action) = first_action {
             let action_expr = redeemer_action_expr(action);
 
             // Positive test
             let mut tx_fields = Vec::new();
-            if has_sig {
-                tx_fields.push("      extra_signatories: [test_admin],".to_string());
+            if let Some(line) = &signatories_ok {
+                tx_fields.push(line.clone());
             }
-            if has_timelock {
-                tx_fields
-                    .push("      validity_range: interval.after(test_deadline + 1),".to_string());
+            if let Some(range) = validity_ok {
+                tx_fields.push(format!("      validity_range: {},", range));
             }
             if has_continuity {
                 tx_fields.push("      inputs: [script_input()],".to_string());
@@ -378,14 +1057,23 @@ fn build_tests(
                 call_params = call_params,
             ));
 
+            // Negative: the actor holds no governance tokens
+            if has_token_gate {
+                no_gov_case = Some(format!(
+                    "test {action_name}_without_gov_tokens_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    action_name = action.name.to_lowercase(),
+                    vname = validator_name,
+                    tx_body = tx_body,
+                    call_params = call_params,
+                ));
+            }
+
             // Negative: wrong signer
             if has_sig {
                 let mut tx_fields_bad = Vec::new();
                 tx_fields_bad.push("      extra_signatories: [#\"deadbeef\"],".to_string());
-                if has_timelock {
-                    tx_fields_bad.push(
-                        "      validity_range: interval.after(test_deadline + 1),".to_string(),
-                    );
+                if let Some(range) = validity_ok {
+                    tx_fields_bad.push(format!("      validity_range: {},", range));
                 }
                 if has_continuity {
                     tx_fields_bad.push("      inputs: [script_input()],".to_string());
@@ -402,11 +1090,35 @@ fn build_tests(
                 ));
             }
 
+            // Negative: one datum signer short of the threshold
+            if has_threshold {
+                cases.push(format!(
+                    "test {action_name}_below_threshold_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    action_name = action.name.to_lowercase(),
+                    vname = validator_name,
+                    tx_body = tx_body.replace(&format!(", {TEST_SIGNER_B}]"), "]"),
+                    call_params = call_params,
+                ));
+            }
+
+            // Negative: the cosigner is missing, so one signature short of min_signers
+            if let (true, Some(ok), Some(short)) =
+                (has_min_signers, &signatories_ok, signatories(false))
+            {
+                cases.push(format!(
+                    "test {action_name}_too_few_signers_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    action_name = action.name.to_lowercase(),
+                    vname = validator_name,
+                    tx_body = tx_body.replace(ok.as_str(), &short),
+                    call_params = call_params,
+                ));
+            }
+
             // Negative: before deadline
             if has_timelock {
                 let mut tx_fields_time = Vec::new();
-                if has_sig {
-                    tx_fields_time.push("      extra_signatories: [test_admin],".to_string());
+                if let Some(line) = &signatories_ok {
+                    tx_fields_time.push(line.clone());
                 }
                 tx_fields_time
                     .push("      validity_range: interval.before(test_deadline - 1),".to_string());
@@ -425,14 +1137,62 @@ fn build_tests(
                 ));
             }
 
+            // Negative: after expiry (past the deadline too, so only the expiry check fails)
+            if expiry.is_some() {
+                let mut tx_fields_exp = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields_exp.push(line.clone());
+                }
+                tx_fields_exp
+                    .push("      validity_range: interval.after(test_expiry + 1),".to_string());
+                if has_continuity {
+                    tx_fields_exp.push("      inputs: [script_input()],".to_string());
+                    tx_fields_exp.push("      outputs: [cont_output_ok()],".to_string());
+                }
+                let tx_body_exp = tx_fields_exp.join("\n");
+
+                cases.push(format!(
+                    "test {action_name}_after_expiry_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    action_name = action.name.to_lowercase(),
+                    vname = validator_name,
+                    tx_body = tx_body_exp,
+                    call_params = call_params,
+                ));
+            }
+
+            // Negative: before and after the window
+            if has_window {
+                for (suffix, range) in [
+                    ("before_window", "interval.before(test_window_start - 1)"),
+                    ("after_window", "interval.after(test_window_end + 1)"),
+                ] {
+                    let mut tx_fields_win = Vec::new();
+                    if let Some(line) = &signatories_ok {
+                        tx_fields_win.push(line.clone());
+                    }
+                    tx_fields_win.push(format!("      validity_range: {},", range));
+                    if has_continuity {
+                        tx_fields_win.push("      inputs: [script_input()],".to_string());
+                        tx_fields_win.push("      outputs: [cont_output_ok()],".to_string());
+                    }
+
+                    cases.push(format!(
+                        "test {action_name}_{suffix}_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                        action_name = action.name.to_lowercase(),
+                        vname = validator_name,
+                        tx_body = tx_fields_win.join("\n"),
+                        call_params = call_params,
+                    ));
+                }
+            }
+
             // Negative: no datum
             let mut tx_fields_nd = Vec::new();
-            if has_sig {
-                tx_fields_nd.push("      extra_signatories: [test_admin],".to_string());
+            if let Some(line) = &signatories_ok {
+                tx_fields_nd.push(line.clone());
             }
-            if has_timelock {
-                tx_fields_nd
-                    .push("      validity_range: interval.after(test_deadline + 1),".to_string());
+            if let Some(range) = validity_ok {
+                tx_fields_nd.push(format!("      validity_range: {},", range));
             }
             if has_continuity {
                 tx_fields_nd.push("      inputs: [script_input()],".to_string());
@@ -452,19 +1212,28 @@ fn build_tests(
                 call_params = call_params_nd,
             ));
 
+            // Negative: extra output to this script with a datum hash
+            if has_inline_only {
+                cases.push(format!(
+                    "test {action_name}_datum_hash_output_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  let tx =\n    Transaction {{ ..tx, outputs: list.concat(tx.outputs, [datum_hash_output()]) }}\n  {vname}.spend({call_params})\n}}",
+                    action_name = action.name.to_lowercase(),
+                    vname = validator_name,
+                    tx_body = tx_body,
+                    call_params = call_params,
+                ));
+            }
+
             // Negative: reference script injection
             if has_ref_safety {
                 let mut tx_fields_ref = Vec::new();
-                if has_sig {
-                    tx_fields_ref.push("      extra_signatories: [test_admin],".to_string());
+                if let Some(line) = &signatories_ok {
+                    tx_fields_ref.push(line.clone());
                 }
-                if has_timelock {
-                    tx_fields_ref.push(
-                        "      validity_range: interval.after(test_deadline + 1),".to_string(),
-                    );
+                if let Some(range) = validity_ok {
+                    tx_fields_ref.push(format!("      validity_range: {},", range));
                 }
                 tx_fields_ref.push("      inputs: [script_input()],".to_string());
-                tx_fields_ref.push("      outputs: [\n        Output {\n          address: script_addr(),\n          value: assets.from_lovelace(10_000_000),\n          datum: InlineDatum(test_datum()),\n          reference_script: Some(#\"deadbeef\"),\n        },\n      ],".to_string());
+                tx_fields_ref.push(format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {},\n          datum: InlineDatum({}),\n          reference_script: Some(#\"deadbeef\"),\n        }},\n      ],", cont_value, cont_datum));
                 let tx_body_ref = tx_fields_ref.join("\n");
                 let call_params_ref = if params_str.is_empty() {
                     format!("Some(test_datum()), {}, test_oref(), tx", action_expr)
@@ -482,57 +1251,762 @@ fn build_tests(
                     call_params = call_params_ref,
                 ));
             }
-        }
-    } else {
-        // Mint purpose tests
-        let has_burn = features.contains(&Feature::BurnVerification);
-
-        // Positive: mint
-        let mut tx_fields = Vec::new();
-        if has_sig {
-            tx_fields.push("      extra_signatories: [test_admin],".to_string());
-        }
-        tx_fields.push("      mint: assets.from_asset(test_policy, \"token\", 1),".to_string());
-        let tx_body = tx_fields.join("\n");
 
-        let first_action = redeemer_actions.first();
-        if let Some(action) = first_action {
-            let action_expr = redeemer_action_expr(action);
-            let call_params = if params_str.is_empty() {
-                format!("{}, test_policy, tx", action_expr)
-            } else {
-                format!("{}, {}, test_policy, tx", params_str, action_expr)
-            };
+            // Negative: continuing output without the required reference script
+            if has_require_ref_script {
+                let mut tx_fields_noref = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields_noref.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields_noref.push(format!("      validity_range: {},", range));
+                }
+                tx_fields_noref.push("      inputs: [script_input()],".to_string());
+                tx_fields_noref.push(format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {cont_value},\n          datum: InlineDatum({cont_datum}),\n          reference_script: None,\n        }},\n      ],"));
+                let tx_body_noref = tx_fields_noref.join("\n");
 
-            cases.push(format!(
-                "test mint_valid() {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.mint({call_params})\n}}",
-                vname = validator_name,
-                tx_body = tx_body,
-                call_params = call_params,
-            ));
+                cases.push(format!(
+                    "test reference_script_missing_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = tx_body_noref,
+                    call_params = call_params,
+                ));
+            }
 
-            // Negative: no sig
-            if has_sig {
-                let tx_bad = [
-                    "      extra_signatories: [],".to_string(),
-                    "      mint: assets.from_asset(test_policy, \"token\", 1),".to_string(),
-                ];
-                let tx_body_bad = tx_bad.join("\n");
+            // Negative: counter does not advance (replayed datum)
+            if counter.is_some() {
+                let mut tx_fields_ctr = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields_ctr.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields_ctr.push(format!("      validity_range: {},", range));
+                }
+                tx_fields_ctr.push("      inputs: [script_input()],".to_string());
+                tx_fields_ctr.push(format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {cont_value},\n          datum: InlineDatum(test_datum()),\n          reference_script: {cont_ref_script},\n        }},\n      ],"));
+                let tx_body_ctr = tx_fields_ctr.join("\n");
 
                 cases.push(format!(
-                    "test mint_no_signature_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.mint({call_params})\n}}",
+                    "test counter_not_advanced_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
                     vname = validator_name,
-                    tx_body = tx_body_bad,
+                    tx_body = tx_body_ctr,
                     call_params = call_params,
                 ));
             }
-        }
+
+            // Negative: reserves move but the product does not grow by the fee
+            if has_swap_fee {
+                let mut tx_fields_fee = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields_fee.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields_fee.push(format!("      validity_range: {},", range));
+                }
+                tx_fields_fee.push("      inputs: [script_input()],".to_string());
+                tx_fields_fee.push(format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {cont_value},\n          datum: InlineDatum(\n            CustomDatum {{\n              ..{cont_datum},\n              reserve_a: test_datum().reserve_a + 100_000,\n              reserve_b: test_datum().reserve_b - 99_000,\n            }},\n          ),\n          reference_script: {cont_ref_script},\n        }},\n      ],"));
+                let tx_body_fee = tx_fields_fee.join("\n");
+
+                cases.push(format!(
+                    "test swap_fee_not_collected_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = tx_body_fee,
+                    call_params = call_params,
+                ));
+            }
+
+            // Negative: a foreign token smuggled into the continuing output
+            if has_whitelist {
+                let mut tx_fields_dust = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields_dust.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields_dust.push(format!("      validity_range: {},", range));
+                }
+                tx_fields_dust.push("      inputs: [script_input()],".to_string());
+                tx_fields_dust.push(format!("      outputs: [\n        Output {{\n          ..cont_output_ok(),\n          value: assets.merge(\n            {script_value},\n            assets.from_asset(#\"ff\", \"dust\", 1),\n          ),\n        }},\n      ],"));
+                let tx_body_dust = tx_fields_dust.join("\n");
+
+                cases.push(format!(
+                    "test foreign_token_in_continuing_output_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = tx_body_dust,
+                    call_params = call_params,
+                ));
+            }
+
+            // NFT preservation: ADA may leave the script, the NFT may not
+            if has_nft {
+                let nft_tx = |value: &str| {
+                    let mut tx_fields = Vec::new();
+                    if let Some(line) = &signatories_ok {
+                        tx_fields.push(line.clone());
+                    }
+                    if let Some(range) = validity_ok {
+                        tx_fields.push(format!("      validity_range: {},", range));
+                    }
+                    tx_fields.push("      inputs: [script_input()],".to_string());
+                    tx_fields.push(format!("      outputs: [\n        Output {{\n          ..cont_output_ok(),\n          value: {value},\n        }},\n      ],"));
+                    tx_fields.join("\n")
+                };
+                // ValuePreservation forbids the ADA decrease; a spending limit tracks it
+                if !has_value && !has_periodic {
+                    cases.push(format!(
+                        "test nft_preserved_with_less_ada() {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                        vname = validator_name,
+                        tx_body = nft_tx(&with_nft("assets.from_lovelace(8_000_000)")),
+                        call_params = call_params,
+                    ));
+                }
+                cases.push(format!(
+                    "test nft_removed_from_continuing_output_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = nft_tx("assets.from_lovelace(10_000_000)"),
+                    call_params = call_params,
+                ));
+            }
+
+            // Property tests: fuzz the script's lovelace and what the continuing output keeps of
+            // it; value preservation accepts every increase and rejects every decrease (a spending
+            // limit withdraws lovelace, so it has its own fixed tests above)
+            if property_tests && has_value && !has_periodic {
+                let fuzz_tx = |kept: &str| {
+                    let mut tx_fields = Vec::new();
+                    if let Some(line) = &signatories_ok {
+                        tx_fields.push(line.clone());
+                    }
+                    if let Some(range) = validity_ok {
+                        tx_fields.push(format!("      validity_range: {},", range));
+                    }
+                    tx_fields.push(format!("      inputs: [\n        Input {{\n          ..script_input(),\n          output: Output {{ ..script_input().output, value: {} }},\n        }},\n      ],", with_nft("assets.from_lovelace(lovelace)")));
+                    tx_fields.push(format!("      outputs: [\n        Output {{ ..cont_output_ok(), value: {} }},\n      ],", with_nft(&format!("assets.from_lovelace({kept})"))));
+                    tx_fields.join("\n")
+                };
+                // `fail` on a property test expects every fuzzed run to fail
+                for (name, fail, delta, fuzzer, kept) in [
+                    (
+                        "value_preserved_property",
+                        "",
+                        "surplus",
+                        "fuzz.int_between(0, 1_000_000_000_000)",
+                        "lovelace + surplus",
+                    ),
+                    (
+                        "value_decreased_property",
+                        " fail",
+                        "shortfall",
+                        "fuzz.int_between(1, 2_000_000)",
+                        "lovelace - shortfall",
+                    ),
+                ] {
+                    cases.push(format!(
+                        "test {action_name}_{name}(\n  amounts via fuzz.both(fuzz.int_between(2_000_000, 1_000_000_000_000), {fuzzer}),\n){fail} {{\n  let (lovelace, {delta}) = amounts\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                        action_name = action.name.to_lowercase(),
+                        vname = validator_name,
+                        tx_body = fuzz_tx(kept),
+                        call_params = call_params,
+                    ));
+                }
+            }
+
+            // Negative: continuing output present but not at the required index
+            if has_cont_index {
+                let mut tx_fields_idx = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields_idx.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields_idx.push(format!("      validity_range: {},", range));
+                }
+                tx_fields_idx.push("      inputs: [script_input()],".to_string());
+                tx_fields_idx.push("      outputs: [\n        Output {\n          address: Address { payment_credential: Script(#\"ff\"), stake_credential: None },\n          value: assets.from_lovelace(1_000_000),\n          datum: InlineDatum(test_datum()),\n          reference_script: None,\n        },\n        cont_output_ok(),\n      ],".to_string());
+                let tx_body_idx = tx_fields_idx.join("\n");
+
+                cases.push(format!(
+                    "test continuation_at_wrong_index_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = tx_body_idx,
+                    call_params = call_params,
+                ));
+            }
+
+            // Positive: a payout and the fee payer's change next to the continuing output
+            if has_change_to_self {
+                let mut tx_fields_payout = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields_payout.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields_payout.push(format!("      validity_range: {},", range));
+                }
+                tx_fields_payout.push("      inputs: [script_input()],".to_string());
+                tx_fields_payout.push("      outputs: [\n        cont_output_ok(),\n        Output {\n          address: test_payee(),\n          value: assets.from_lovelace(5_000_000),\n          datum: NoDatum,\n          reference_script: None,\n        },\n        Output {\n          address: test_fee_payer(),\n          value: assets.from_lovelace(1_500_000),\n          datum: NoDatum,\n          reference_script: None,\n        },\n      ],".to_string());
+                let tx_body_payout = tx_fields_payout.join("\n");
+
+                cases.push(format!(
+                    "test payout_and_fee_payer_change_valid() {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = tx_body_payout,
+                    call_params = call_params,
+                ));
+            }
+
+            // Negative: change leaks to an address outside the allowlist
+            if has_change_to_self {
+                let mut tx_fields_change = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields_change.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields_change.push(format!("      validity_range: {},", range));
+                }
+                tx_fields_change.push("      inputs: [script_input()],".to_string());
+                tx_fields_change.push("      outputs: [\n        cont_output_ok(),\n        Output {\n          address: Address { payment_credential: Script(#\"ff\"), stake_credential: None },\n          value: assets.from_lovelace(1_000_000),\n          datum: InlineDatum(test_datum()),\n          reference_script: None,\n        },\n      ],".to_string());
+                let tx_body_change = tx_fields_change.join("\n");
+
+                cases.push(format!(
+                    "test change_to_other_address_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = tx_body_change,
+                    call_params = call_params,
+                ));
+            }
+
+            // Spending limit: over the limit fails, and only a new period resets the spent total
+            if has_periodic {
+                let reset_from = "test_period_start + test_period + 1";
+                let reset_range = if has_bounded_validity {
+                    format!("interval.between({reset_from}, {reset_from} + test_max_ttl)")
+                } else if expiry.is_some() {
+                    format!("interval.between({reset_from}, test_expiry - 1)")
+                } else if has_window {
+                    format!("interval.between({reset_from}, test_window_end - 1)")
+                } else {
+                    format!("interval.after({reset_from})")
+                };
+                let exhausted =
+                    format!("CustomDatum {{ ..test_datum(), {PERIOD_SPENT_FIELD}: test_limit }}");
+                let limit_tx = |range: Option<&str>, value: &str, out_datum: &str| {
+                    let mut tx_fields = Vec::new();
+                    if let Some(line) = &signatories_ok {
+                        tx_fields.push(line.clone());
+                    }
+                    if let Some(range) = range {
+                        tx_fields.push(format!("      validity_range: {},", range));
+                    }
+                    tx_fields.push("      inputs: [script_input()],".to_string());
+                    tx_fields.push(format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {value},\n          datum: InlineDatum({out_datum}),\n          reference_script: {cont_ref_script},\n        }},\n      ],"));
+                    tx_fields.join("\n")
+                };
+                let limit_call = |in_datum: &str| {
+                    if params_str.is_empty() {
+                        format!("Some({}), {}, test_oref(), tx", in_datum, action_expr)
+                    } else {
+                        format!(
+                            "{}, Some({}), {}, test_oref(), tx",
+                            params_str, in_datum, action_expr
+                        )
+                    }
+                };
+
+                let over_datum = format!(
+                    "CustomDatum {{ ..{cont_datum}, {PERIOD_SPENT_FIELD}: test_limit + 1 }}"
+                );
+                let exhausted_datum = format!("CustomDatum {{ ..{cont_datum}, {PERIOD_SPENT_FIELD}: test_limit + test_withdrawal }}");
+                let reset_datum = format!("CustomDatum {{ ..{cont_datum}, {PERIOD_START_FIELD}: {reset_from}, {PERIOD_SPENT_FIELD}: test_withdrawal }}");
+                let limit_cases = [
+                    (
+                        "over_limit_fails() fail",
+                        limit_tx(
+                            validity_ok,
+                            &with_nft("assets.from_lovelace(10_000_000 - test_limit - 1)"),
+                            &over_datum,
+                        ),
+                        limit_call("test_datum()"),
+                    ),
+                    (
+                        "period_exhausted_fails() fail",
+                        limit_tx(validity_ok, cont_value, &exhausted_datum),
+                        limit_call(&exhausted),
+                    ),
+                    (
+                        "new_period_resets_limit()",
+                        limit_tx(Some(&reset_range), cont_value, &reset_datum),
+                        limit_call(&exhausted),
+                    ),
+                ];
+                for (name, tx_body, call_params) in limit_cases {
+                    cases.push(format!(
+                        "test {action_name}_{name} {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                        action_name = action.name.to_lowercase(),
+                        vname = validator_name,
+                    ));
+                }
+            }
+        }
+
+        // Admin rotation: the current admin hands over to test_new_admin; nothing else may change
+        if has_rotation {
+            let rotation_tx = |signer: &str, out_datum: &str| {
+                let mut tx_fields = vec![format!(
+                    "      extra_signatories: [{}{}{}],",
+                    signer, cosigner, threshold_signers
+                )];
+                if let Some(range) = validity_ok {
+                    tx_fields.push(format!("      validity_range: {},", range));
+                }
+                tx_fields.push("      inputs: [script_input()],".to_string());
+                tx_fields.push(format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {script_value},\n          datum: InlineDatum({out_datum}),\n          reference_script: {cont_ref_script},\n        }},\n      ],"));
+                tx_fields.join("\n")
+            };
+            let spend_call = |action_expr: &str| {
+                if params_str.is_empty() {
+                    format!("Some(test_datum()), {}, test_oref(), tx", action_expr)
+                } else {
+                    format!(
+                        "{}, Some(test_datum()), {}, test_oref(), tx",
+                        params_str, action_expr
+                    )
+                }
+            };
+            let rotate_expr = format!("{ROTATE_ADMIN_ACTION} {{ new_admin: test_new_admin }}");
+            let rotated =
+                format!("CustomDatum {{ ..{cont_datum}, {ADMIN_FIELD}: test_new_admin }}");
+            // Another field to tamper with during rotation, with a value that differs from test_datum()
+            let tampered = datum_fields
+                .iter()
+                .filter(|f| f.name != ADMIN_FIELD)
+                .find_map(|f| {
+                    let value = match f.aiken_type.as_str() {
+                        "Int" => format!("test_datum().{} + 1", f.name),
+                        "ByteArray" => "#\"ff\"".to_string(),
+                        "Bool" => format!("!test_datum().{}", f.name),
+                        _ => return None,
+                    };
+                    Some(format!(
+                        "CustomDatum {{ ..{cont_datum}, {ADMIN_FIELD}: test_new_admin, {}: {} }}",
+                        f.name, value
+                    ))
+                });
+
+            let mut rotation_cases = vec![
+                (
+                    "rotate_admin_valid()",
+                    "test_admin",
+                    rotated.clone(),
+                    rotate_expr.clone(),
+                ),
+                (
+                    "rotate_admin_by_stranger_fails() fail",
+                    "test_new_admin",
+                    rotated.clone(),
+                    rotate_expr.clone(),
+                ),
+            ];
+            if let Some(tampered) = tampered {
+                rotation_cases.push((
+                    "rotate_admin_changing_other_fields_fails() fail",
+                    "test_admin",
+                    tampered,
+                    rotate_expr.clone(),
+                ));
+            }
+            let other_name = first_action
+                .map(|a| format!("{}_changing_admin_fails() fail", a.name.to_lowercase()));
+            if let (Some(action), Some(name)) = (first_action, other_name.as_deref()) {
+                rotation_cases.push((name, "test_admin", rotated, redeemer_action_expr(action)));
+            }
+            for (name, signer, out_datum, action_expr) in rotation_cases {
+                cases.push(format!(
+                    "test {name} {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = rotation_tx(signer, &out_datum),
+                    call_params = spend_call(&action_expr),
+                ));
+            }
+        }
+
+        // Proportional payout: a claim may take its floored share of the pool, not a lovelace more
+        if has_payout {
+            let claim_tx = |payout: &str, remaining: &str| {
+                let mut out_updates = vec![
+                    format!("{TOTAL_POOL_FIELD}: test_datum().{TOTAL_POOL_FIELD} - test_payout"),
+                    format!("{TOTAL_SHARES_FIELD}: test_total_shares - test_claimant_share"),
+                ];
+                if has_periodic {
+                    out_updates.push(format!(
+                        "{PERIOD_SPENT_FIELD}: test_datum().{PERIOD_SPENT_FIELD} + {payout}"
+                    ));
+                }
+                let value = with_nft(&format!("assets.from_lovelace({remaining})"));
+                let mut tx_fields = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields.push(format!("      validity_range: {},", range));
+                }
+                tx_fields.push("      inputs: [script_input()],".to_string());
+                tx_fields.push(format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {value},\n          datum: InlineDatum(\n            CustomDatum {{ ..{cont_datum}, {} }},\n          ),\n          reference_script: {cont_ref_script},\n        }},\n      ],", out_updates.join(", ")));
+                tx_fields.join("\n")
+            };
+            let in_datum = format!(
+                "CustomDatum {{ ..test_datum(), {TOTAL_SHARES_FIELD}: test_total_shares }}"
+            );
+            let claim_expr = format!("{CLAIM_ACTION} {{ claimant_share: test_claimant_share }}");
+            let call_params = if params_str.is_empty() {
+                format!("Some({}), {}, test_oref(), tx", in_datum, claim_expr)
+            } else {
+                format!(
+                    "{}, Some({}), {}, test_oref(), tx",
+                    params_str, in_datum, claim_expr
+                )
+            };
+            for (name, payout, remaining) in [
+                (
+                    "claim_pro_rata_valid()",
+                    "test_payout",
+                    "10_000_000 - test_payout",
+                ),
+                (
+                    "claim_over_pro_rata_share_fails() fail",
+                    "test_payout + 1",
+                    "10_000_000 - test_payout - 1",
+                ),
+            ] {
+                cases.push(format!(
+                    "test {name} {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = claim_tx(payout, remaining),
+                ));
+            }
+        }
+
+        // Deposit only: a Deposit may top the script up, but not take lovelace out or swap assets
+        if has_deposit_only {
+            let deposit_tx = |value: &str, spent: &str| {
+                // A deposit counts as a negative withdrawal against the spending limit
+                let out_datum = if has_periodic {
+                    format!("CustomDatum {{ ..{cont_datum}, {PERIOD_SPENT_FIELD}: test_datum().{PERIOD_SPENT_FIELD} {spent} }}")
+                } else {
+                    cont_datum.to_string()
+                };
+                let mut tx_fields = Vec::new();
+                if let Some(line) = &signatories_ok {
+                    tx_fields.push(line.clone());
+                }
+                if let Some(range) = validity_ok {
+                    tx_fields.push(format!("      validity_range: {},", range));
+                }
+                tx_fields.push("      inputs: [script_input()],".to_string());
+                tx_fields.push(format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {value},\n          datum: InlineDatum({out_datum}),\n          reference_script: {cont_ref_script},\n        }},\n      ],"));
+                tx_fields.join("\n")
+            };
+            let call_params = if params_str.is_empty() {
+                format!("Some(test_datum()), {DEPOSIT_ACTION}, test_oref(), tx")
+            } else {
+                format!("{params_str}, Some(test_datum()), {DEPOSIT_ACTION}, test_oref(), tx")
+            };
+            let topped_up = with_nft("assets.from_lovelace(10_000_000 + test_deposit)");
+            for (name, value, spent) in [
+                (
+                    "deposit_adding_funds_valid()",
+                    topped_up.clone(),
+                    "- test_deposit",
+                ),
+                (
+                    "deposit_withdrawing_funds_fails() fail",
+                    with_nft("assets.from_lovelace(10_000_000 - test_deposit)"),
+                    "+ test_deposit",
+                ),
+                (
+                    "deposit_adding_other_assets_fails() fail",
+                    format!("assets.add({topped_up}, #\"ff\", \"dust\", 1)"),
+                    "- test_deposit",
+                ),
+            ] {
+                cases.push(format!(
+                    "test {name} {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = deposit_tx(&value, spent),
+                ));
+            }
+        }
+
+        // Without DatumContinuity the transactions above don't spend the script input the
+        // inline-datum-only preamble looks up
+        if has_inline_only && !has_continuity {
+            for case in cases.iter_mut().chain(no_gov_case.iter_mut()) {
+                *case = case.replace(
+                    "      ..transaction.placeholder,\n",
+                    "      ..transaction.placeholder,\n      inputs: [script_input()],\n",
+                );
+            }
+        }
+
+        // Refundable deposit: the depositor takes the whole deposit back, but only before the
+        // claim deadline (after the inline-datum-only rewrite, as these spend the input already)
+        if has_refund {
+            let with_depositor = |signed: bool| match (&signatories_ok, signed) {
+                (Some(line), true) => Some(line.replace("],", ", test_depositor],")),
+                (None, true) => Some("      extra_signatories: [test_depositor],".to_string()),
+                (line, false) => line.clone(),
+            };
+            // Before the deadline within the other time checks; after it is out of every window
+            let (before_deadline, after_deadline) = if has_bounded_validity {
+                (
+                    bounded_range("test_max_ttl"),
+                    "interval.between(test_claim_deadline, test_claim_deadline + test_max_ttl)"
+                        .to_string(),
+                )
+            } else {
+                let until = valid_until.unwrap_or("test_claim_deadline - 1");
+                let before = match valid_from {
+                    Some(from) => format!("interval.between({from}, {until})"),
+                    None => format!("interval.before({until})"),
+                };
+                (before, "interval.after(test_claim_deadline)".to_string())
+            };
+            let refund_tx = |signed: bool, range: &str, refunded: &str| {
+                let mut tx_fields = Vec::new();
+                if let Some(line) = with_depositor(signed) {
+                    tx_fields.push(line);
+                }
+                tx_fields.push(format!("      validity_range: {},", range));
+                tx_fields.push("      inputs: [script_input()],".to_string());
+                tx_fields.push(format!("      outputs: [\n        Output {{\n          address: Address {{ payment_credential: VerificationKey(test_depositor), stake_credential: None }},\n          value: assets.from_lovelace({refunded}),\n          datum: NoDatum,\n          reference_script: None,\n        }},\n      ],"));
+                tx_fields.join("\n")
+            };
+            let call_params = if params_str.is_empty() {
+                format!("Some(test_datum()), {REFUND_ACTION}, test_oref(), tx")
+            } else {
+                format!("{params_str}, Some(test_datum()), {REFUND_ACTION}, test_oref(), tx")
+            };
+            for (name, signed, range, refunded) in [
+                (
+                    "refund_before_deadline_valid()",
+                    true,
+                    &before_deadline,
+                    "10_000_000",
+                ),
+                (
+                    "refund_after_deadline_fails() fail",
+                    true,
+                    &after_deadline,
+                    "10_000_000",
+                ),
+                (
+                    "refund_without_depositor_signature_fails() fail",
+                    false,
+                    &before_deadline,
+                    "10_000_000",
+                ),
+                (
+                    "refund_short_of_deposit_fails() fail",
+                    true,
+                    &before_deadline,
+                    "10_000_000 - 1",
+                ),
+            ] {
+                cases.push(format!(
+                    "test {name} {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = refund_tx(signed, range, refunded),
+                ));
+            }
+        }
+
+        // Circuit breaker: a normal action is blocked while paused; Unpause must clear the flag
+        let paused_action = redeemer_actions.iter().find(|a| a.name != UNPAUSE_ACTION);
+        if let (true, Some(action)) = (has_pausable, paused_action) {
+            let mut tx_fields = Vec::new();
+            if let Some(line) = &signatories_ok {
+                tx_fields.push(line.clone());
+            }
+            if let Some(range) = validity_ok {
+                tx_fields.push(format!("      validity_range: {},", range));
+            }
+            tx_fields.push("      inputs: [script_input()],".to_string());
+            let outputs_ok = "      outputs: [cont_output_ok()],".to_string();
+            let outputs_still_paused = format!("      outputs: [\n        Output {{\n          address: script_addr(),\n          value: {cont_value},\n          datum: InlineDatum(CustomDatum {{ ..{cont_datum}, {PAUSED_FIELD}: True }}),\n          reference_script: {cont_ref_script},\n        }},\n      ],");
+            let tx_body_ok = [tx_fields.clone(), vec![outputs_ok]].concat().join("\n");
+            let tx_body_still_paused = [tx_fields, vec![outputs_still_paused]].concat().join("\n");
+            let paused_call = |action_expr: &str| {
+                if params_str.is_empty() {
+                    format!(
+                        "Some(test_datum_paused()), {}, test_oref(), tx",
+                        action_expr
+                    )
+                } else {
+                    format!(
+                        "{}, Some(test_datum_paused()), {}, test_oref(), tx",
+                        params_str, action_expr
+                    )
+                }
+            };
+
+            cases.push(format!(
+                "test {action_name}_while_paused_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                action_name = action.name.to_lowercase(),
+                vname = validator_name,
+                tx_body = tx_body_ok,
+                call_params = paused_call(&redeemer_action_expr(action)),
+            ));
+            cases.push(format!(
+                "test unpause_valid() {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                vname = validator_name,
+                tx_body = tx_body_ok,
+                call_params = paused_call(UNPAUSE_ACTION),
+            ));
+            cases.push(format!(
+                "test unpause_without_clearing_flag_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.spend({call_params})\n}}",
+                vname = validator_name,
+                tx_body = tx_body_still_paused,
+                call_params = paused_call(UNPAUSE_ACTION),
+            ));
+        }
+
+        // Every other transaction references an input holding the governance tokens
+        if has_token_gate {
+            for case in &mut cases {
+                *case = case.replace(
+                    "      ..transaction.placeholder,\n",
+                    "      ..transaction.placeholder,\n      reference_inputs: [gov_input()],\n",
+                );
+            }
+            cases.extend(no_gov_case);
+        }
+    } else {
+        // Mint purpose tests
+        let has_burn = features.contains(&Feature::BurnVerification);
+
+        let mint_qty = if has_exact_mint {
+            "test_max_supply"
+        } else {
+            "1"
+        };
+
+        // Keys signing the positive mint: the admin and the min-signers cosigner
+        let mint_signers = |admin: bool, cosigner: bool| {
+            let mut signers = Vec::new();
+            if has_sig && admin {
+                signers.push("test_admin");
+            }
+            if has_min_signers && cosigner {
+                signers.push("test_cosigner");
+            }
+            signers.join(", ")
+        };
+        let signers_ok = mint_signers(true, true);
+
+        // Mint tx body; `signers` sign, the seed UTxO is spent and the CIP-25 metadata
+        // output is present unless a test says otherwise
+        let mint_tx_body = |signers: &str, qty: &str, seed_spent: bool, with_metadata: bool| {
+            let mut tx_fields = Vec::new();
+            if has_sig || has_min_signers {
+                tx_fields.push(format!("      extra_signatories: [{}],", signers));
+            }
+            if has_unique_mint && seed_spent {
+                tx_fields.push("      inputs: [seed_input()],".to_string());
+            }
+            if has_cip25 && with_metadata {
+                tx_fields.push("      outputs: [test_cip25_output()],".to_string());
+            }
+            tx_fields.push(format!(
+                "      mint: assets.from_asset(test_policy, \"token\", {}),",
+                qty
+            ));
+            tx_fields.join("\n")
+        };
+
+        let first_action = redeemer_actions.first();
+        if let Some(action) = first_action {
+            let action_expr = redeemer_action_expr(action);
+            let call_params = if params_str.is_empty() {
+                format!("{}, test_policy, tx", action_expr)
+            } else {
+                format!("{}, {}, test_policy, tx", params_str, action_expr)
+            };
+
+            // Positive: mint
+            cases.push(format!(
+                "test mint_valid() {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.mint({call_params})\n}}",
+                vname = validator_name,
+                tx_body = mint_tx_body(&signers_ok, mint_qty, true, true),
+                call_params = call_params,
+            ));
+
+            // Negative: no sig
+            if has_sig {
+                cases.push(format!(
+                    "test mint_no_signature_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.mint({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = mint_tx_body(&mint_signers(false, true), mint_qty, true, true),
+                    call_params = call_params,
+                ));
+            }
+
+            // Negative: the cosigner is missing, so one signature short of min_signers
+            if has_min_signers {
+                cases.push(format!(
+                    "test mint_too_few_signers_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.mint({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = mint_tx_body(&mint_signers(true, false), mint_qty, true, true),
+                    call_params = call_params,
+                ));
+            }
+
+            // Negative: minting more or less than max_supply
+            if has_exact_mint {
+                for (label, qty) in [
+                    ("over", "test_max_supply + 1"),
+                    ("under", "test_max_supply - 1"),
+                ] {
+                    cases.push(format!(
+                        "test mint_{label}_supply_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.mint({call_params})\n}}",
+                        label = label,
+                        vname = validator_name,
+                        tx_body = mint_tx_body(&signers_ok, qty, true, true),
+                        call_params = call_params,
+                    ));
+                }
+            }
+
+            // Negative: seed UTxO not consumed
+            if has_unique_mint {
+                cases.push(format!(
+                    "test mint_seed_not_spent_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.mint({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = mint_tx_body(&signers_ok, mint_qty, false, true),
+                    call_params = call_params,
+                ));
+            }
+
+            // Negative: no CIP-25 metadata output
+            if has_cip25 {
+                cases.push(format!(
+                    "test mint_without_metadata_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  {vname}.mint({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = mint_tx_body(&signers_ok, mint_qty, true, false),
+                    call_params = call_params,
+                ));
+            }
+
+            // Negative: extra output to the policy's script address with a datum hash
+            if has_inline_only {
+                cases.push(format!(
+                    "test mint_datum_hash_output_fails() fail {{\n  let tx =\n    Transaction {{\n      ..transaction.placeholder,\n{tx_body}\n    }}\n  let tx =\n    Transaction {{ ..tx, outputs: list.concat(tx.outputs, [datum_hash_output()]) }}\n  {vname}.mint({call_params})\n}}",
+                    vname = validator_name,
+                    tx_body = mint_tx_body(&signers_ok, mint_qty, true, true),
+                    call_params = call_params,
+                ));
+            }
+        }
 
         // Burn test
         if has_burn {
-            let burn_action = redeemer_actions
-                .iter()
-                .find(|a| a.name.to_lowercase() == "burn");
+            let burn_action = redeemer_actions.iter().find(|a| a.name == BURN_ACTION);
             if let Some(action) = burn_action {
                 let action_expr = redeemer_action_expr(action);
                 let call_params = if params_str.is_empty() {
@@ -556,14 +2030,93 @@ fn build_tests(
         }
     }
 
+    // Bounded validity: every transaction needs a finite range, and the first positive case is
+    // repeated with a range one wider than max_ttl and with its upper (or lower) end left open
+    if let (true, Some(ok)) = (has_bounded_validity, validity_ok) {
+        let ok_line = format!("      validity_range: {},", ok);
+        for case in &mut cases {
+            if !case.contains("validity_range:") {
+                *case = case.replacen(
+                    "      ..transaction.placeholder,\n",
+                    &format!("      ..transaction.placeholder,\n{ok_line}\n"),
+                    1,
+                );
+            }
+        }
+        // With both bounds constrained the open range also breaks the upper one; it fails either way
+        let unbounded = match (valid_from, valid_until) {
+            (Some(from), _) => format!("interval.after({from})"),
+            (None, Some(until)) => format!("interval.before({until})"),
+            (None, None) => "interval.after(0)".to_string(),
+        };
+        let positive = cases
+            .iter()
+            .find(|c| c.lines().next().is_some_and(|l| l.ends_with("_valid() {")))
+            .cloned();
+        if let Some(positive) = positive {
+            for (suffix, range) in [
+                ("validity_too_wide", bounded_range("test_max_ttl + 1")),
+                ("unbounded_validity", unbounded),
+            ] {
+                cases.push(
+                    positive
+                        .replacen("_valid() {", &format!("_{suffix}_fails() fail {{"), 1)
+                        .replace(&ok_line, &format!("      validity_range: {},", range)),
+                );
+            }
+        }
+    }
+
+    // No reference inputs: the first positive case fails once it references any UTxO
+    if has_no_ref_inputs {
+        let positive = cases
+            .iter()
+            .find(|c| c.lines().next().is_some_and(|l| l.ends_with("_valid() {")))
+            .cloned();
+        if let Some(positive) = positive {
+            cases.push(
+                positive
+                    .replacen("_valid() {", "_with_reference_input_fails() fail {", 1)
+                    .replacen(
+                        "      ..transaction.placeholder,\n",
+                        "      ..transaction.placeholder,\n      reference_inputs: [stray_reference_input()],\n",
+                        1,
+                    ),
+            );
+        }
+    }
+
+    // No minting: the first positive case fails once the transaction also mints a token
+    if has_no_minting {
+        let positive = cases
+            .iter()
+            .find(|c| c.lines().next().is_some_and(|l| l.ends_with("_valid() {")))
+            .cloned();
+        if let Some(positive) = positive {
+            cases.push(
+                positive
+                    .replacen("_valid() {", "_with_mint_fails() fail {", 1)
+                    .replacen(
+                        "      ..transaction.placeholder,\n",
+                        "      ..transaction.placeholder,\n      mint: assets.from_asset(#\"face\", \"stray\", 1),\n",
+                        1,
+                    ),
+            );
+        }
+    }
+
     (helpers, cases)
 }
 
-fn test_value_for_type(
+/// Aiken literal for a datum field in the generated tests (also used by the CBOR preview)
+pub(super) fn test_value_for_type(
     aiken_type: &str,
     name: &str,
     has_timelock: bool,
     deadline_field: &Option<String>,
+    expiry_field: Option<&str>,
+    has_window: bool,
+    has_periodic: bool,
 ) -> String {
     match aiken_type {
         "ByteArray" => {
@@ -573,19 +2126,48 @@ fn test_value_for_type(
                 "#\"aabbccdd\"".to_string()
             }
         }
+        "List<ByteArray>" if name == SIGNERS_FIELD => {
+            format!("[{TEST_SIGNER_A}, {TEST_SIGNER_B}, {TEST_SIGNER_C}]")
+        }
         "Int" => {
             if has_timelock && deadline_field.as_deref() == Some(name) {
                 "test_deadline".to_string()
-            } else if name.contains("amount") || name.contains("balance") || name.contains("total")
+            } else if expiry_field == Some(name) {
+                "test_expiry".to_string()
+            } else if has_window && name == WINDOW_START_FIELD {
+                "test_window_start".to_string()
+            } else if has_window && name == WINDOW_END_FIELD {
+                "test_window_end".to_string()
+            } else if has_periodic && name == PERIOD_START_FIELD {
+                "test_period_start".to_string()
+            } else if name == THRESHOLD_FIELD {
+                "2".to_string()
+            } else if name.contains("amount")
+                || name.contains("balance")
+                || name.contains("total")
+                || name.contains("reserve")
             {
                 "10_000_000".to_string()
             } else {
                 "0".to_string()
             }
         }
+        // Pausable datums start unpaused so the positive path can run
+        "Bool" if name == PAUSED_FIELD => "False".to_string(),
         "Bool" => "True".to_string(),
+        // Any serialisable value upcasts to opaque Data
+        "Data" => "Void".to_string(),
         t if t.starts_with("List<") => "[]".to_string(),
-        _ => "todo".to_string(),
+        t => match tuple_elements(t) {
+            Some(elements) => {
+                let values: Vec<String> = elements
+                    .iter()
+                    .map(|e| test_value_for_type(e, name, false, &None, None, false, false))
+                    .collect();
+                format!("({})", values.join(", "))
+            }
+            None => "todo".to_string(),
+        },
     }
 }
 
@@ -596,24 +2178,58 @@ fn redeemer_action_expr(action: &RedeemerAction) -> String {
         let field_defaults: Vec<String> = action
             .fields
             .iter()
-            .map(|(name, ty)| {
-                let val = match ty.as_str() {
-                    "Int" => "5_000_000".to_string(),
-                    "ByteArray" => "#\"aabb\"".to_string(),
-                    "Bool" => "True".to_string(),
-                    _ => "todo".to_string(),
-                };
-                format!("{}: {}", name, val)
-            })
+            .map(|(name, ty)| format!("{}: {}", name, redeemer_field_value(ty)))
             .collect();
         format!("{} {{ {} }}", action.name, field_defaults.join(", "))
     }
 }
 
-#[cfg(test)]
+pub(super) fn redeemer_field_value(ty: &str) -> String {
+    match ty {
+        "Int" => "5_000_000".to_string(),
+        "ByteArray" => "#\"aabb\"".to_string(),
+        "Bool" => "True".to_string(),
+        "Data" => "Void".to_string(),
+        t if t.starts_with("List<") => "[]".to_string(),
+        t => match tuple_elements(t) {
+            Some(elements) => {
+                let values: Vec<String> =
+                    elements.iter().map(|e| redeemer_field_value(e)).collect();
+                format!("({})", values.join(", "))
+            }
+            None => "todo".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Compose already-resolved spend `features` over a `datum`/`redeemer` spec
+    fn compose_spend(features: &[Feature], datum: &str, redeemer: &str) -> ComposedValidator {
+        let datum_fields = crate::features::types::parse_datum_fields(datum).unwrap();
+        let actions = crate::features::types::parse_redeemer_actions(redeemer).unwrap();
+        compose(
+            features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_validator",
+            &ComposeOptions::default(),
+        )
+        .unwrap()
+    }
+
+    /// The generated test case declared as `test {name}...`
+    fn test_case<'a>(composed: &'a ComposedValidator, name: &str) -> &'a str {
+        composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with(&format!("test {name}")))
+            .unwrap_or_else(|| panic!("missing test {name}"))
+    }
+
     #[test]
     fn test_resolve_adds_dependencies() {
         let features = vec![Feature::ValuePreservation];
@@ -622,6 +2238,71 @@ mod tests {
         assert!(resolved.contains(&Feature::ValuePreservation));
     }
 
+    #[test]
+    fn test_merge_imports_ignores_whitespace() {
+        let imports = merge_imports(HashSet::from([
+            "use cardano/transaction".to_string(),
+            "use cardano/transaction ".to_string(),
+            "use  cardano/transaction".to_string(),
+            "use cardano/transaction.{ Input,Output }".to_string(),
+            "use cardano/transaction .{Output, Transaction}".to_string(),
+        ]));
+        assert_eq!(
+            imports,
+            vec![
+                "use cardano/transaction",
+                "use cardano/transaction.{Input, Output, Transaction}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compose_mint_imports_each_module_once() {
+        let features = resolve_features(
+            &[
+                Feature::SignatureAuth,
+                Feature::BurnVerification,
+                Feature::ExactMintAmount,
+                Feature::UniqueMint,
+                Feature::Cip25Metadata,
+                Feature::InlineDatumOnly,
+                Feature::NoReferenceInputs,
+            ],
+            "mint",
+        )
+        .unwrap();
+        let actions = crate::features::types::parse_redeemer_actions("Mint,Burn").unwrap();
+        let composed = compose(
+            &features,
+            "mint",
+            &[],
+            &actions,
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        // At most one bare and one typed line per module; the bare+typed pair (e.g.
+        // `use cardano/assets` next to `use cardano/assets.{PolicyId}`) is intentional
+        let mut bare = HashSet::new();
+        let mut typed = HashSet::new();
+        for imp in &composed.imports {
+            assert_eq!(imp, imp.trim(), "untrimmed import {imp:?}");
+            assert!(!imp.contains("  "), "unnormalized import {imp:?}");
+            let fresh = match imp.split_once(".{") {
+                Some((module, _)) => typed.insert(module),
+                None => bare.insert(imp.as_str()),
+            };
+            assert!(
+                fresh,
+                "duplicate import for {imp:?}: {:?}",
+                composed.imports
+            );
+        }
+        assert!(bare.contains("use cardano/assets"));
+        assert!(typed.contains("use cardano/assets"));
+    }
+
     #[test]
     fn test_resolve_sorted_by_order() {
         let features = vec![Feature::ValuePreservation, Feature::SignatureAuth];
@@ -670,6 +2351,7 @@ mod tests {
             &datum_fields,
             &actions,
             "test_validator",
+            &ComposeOptions::default(),
         )
         .unwrap();
 
@@ -714,6 +2396,7 @@ mod tests {
             &datum_fields,
             &actions,
             "test_validator",
+            &ComposeOptions::default(),
         )
         .unwrap();
 
@@ -736,9 +2419,1644 @@ mod tests {
                 fields: vec![],
             },
         ];
-        let composed = compose(&features, "mint", &datum_fields, &actions, "test_mint").unwrap();
+        let composed = compose(
+            &features,
+            "mint",
+            &datum_fields,
+            &actions,
+            "test_mint",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
 
         assert!(composed.imports.iter().any(|i| i.contains("PolicyId")));
         assert!(!composed.test_cases.is_empty());
     }
+
+    #[test]
+    fn test_compose_cip25_metadata() {
+        let features = vec![Feature::Cip25Metadata, Feature::ExactMintAmount];
+        let actions = vec![RedeemerAction {
+            name: "Mint".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &features,
+            "mint",
+            &[],
+            &actions,
+            "test_mint",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        // mint_asset_name is shared with exact-mint-amount
+        assert_eq!(
+            composed
+                .validator_params
+                .iter()
+                .filter(|(name, _)| name == "mint_asset_name")
+                .count(),
+            1
+        );
+        assert!(composed
+            .action_checks
+            .iter()
+            .any(|c| c.contains("expect Some(policy_metadata) = pairs.get_first(cip25, 721)")));
+        assert!(composed
+            .imports
+            .contains(&"use cardano/transaction.{InlineDatum, Output, Transaction}".to_string()));
+        let valid = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test mint_valid()"))
+            .unwrap();
+        assert!(valid.contains("outputs: [test_cip25_output()],"));
+        let missing = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test mint_without_metadata_fails() fail"))
+            .unwrap();
+        assert!(!missing.contains("outputs:"));
+    }
+
+    #[test]
+    fn test_compose_exact_mint_amount() {
+        let features = vec![Feature::ExactMintAmount];
+        let actions = vec![RedeemerAction {
+            name: "Mint".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &features,
+            "mint",
+            &[],
+            &actions,
+            "test_mint",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(composed
+            .validator_params
+            .contains(&("max_supply".to_string(), "Int".to_string())));
+        assert!(composed.action_checks.iter().any(|c| c.contains(
+            "expect assets.quantity_of(self.mint, policy_id, mint_asset_name) == max_supply"
+        )));
+        assert!(composed
+            .test_helpers
+            .contains("const test_max_supply: Int = 1_000"));
+        let valid = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test mint_valid()"))
+            .unwrap();
+        assert!(valid.contains("assets.from_asset(test_policy, \"token\", test_max_supply)"));
+        assert!(valid.contains("test_mint.mint(\"token\", test_max_supply, Mint, test_policy, tx)"));
+        assert!(composed
+            .test_cases
+            .iter()
+            .any(|t| t.starts_with("test mint_over_supply_fails() fail")));
+        assert!(composed
+            .test_cases
+            .iter()
+            .any(|t| t.starts_with("test mint_under_supply_fails() fail")));
+    }
+
+    #[test]
+    fn test_compose_unique_mint() {
+        let features = vec![Feature::UniqueMint, Feature::ExactMintAmount];
+        let actions = vec![RedeemerAction {
+            name: "Mint".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &features,
+            "mint",
+            &[],
+            &actions,
+            "test_nft",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            composed.validator_params[0],
+            ("seed_utxo".to_string(), "OutputReference".to_string())
+        );
+        assert!(composed
+            .imports
+            .iter()
+            .any(|i| i.starts_with("use cardano/transaction.{") && i.contains("OutputReference")));
+        assert!(composed.action_checks.iter().any(|c| c
+            .contains("expect list.any(self.inputs, fn(i) { i.output_reference == seed_utxo })")));
+        assert!(composed.test_helpers.contains("fn seed_input() -> Input {"));
+
+        let valid = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test mint_valid()"))
+            .unwrap();
+        assert!(valid.contains("inputs: [seed_input()],"));
+        assert!(valid.contains(
+            "test_nft.mint(test_seed(), \"token\", test_max_supply, Mint, test_policy, tx)"
+        ));
+
+        let unspent = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test mint_seed_not_spent_fails() fail"))
+            .unwrap();
+        assert!(!unspent.contains("seed_input()"));
+        assert!(composed.test_helpers.contains(
+            "transaction_id: #\"0000000000000000000000000000000000000000000000000000000000000002\","
+        ));
+
+        // --seed-utxo replaces the placeholder reference
+        let seed = SeedUtxo {
+            transaction_id: "ab".repeat(32),
+            output_index: 3,
+        };
+        let seeded = compose(
+            &features,
+            "mint",
+            &[],
+            &actions,
+            "test_nft",
+            &ComposeOptions {
+                seed_utxo: Some(&seed),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(seeded.test_helpers.contains(&format!(
+            "    transaction_id: #\"{}\",\n    output_index: 3,",
+            "ab".repeat(32)
+        )));
+    }
+
+    #[test]
+    fn test_compose_monotonic_counter() {
+        let features = resolve_features(&[Feature::MonotonicCounter], "spend").unwrap();
+        let datum_fields = vec![
+            DatumField {
+                name: "owner".to_string(),
+                aiken_type: "ByteArray".to_string(),
+            },
+            DatumField {
+                name: "sequence".to_string(),
+                aiken_type: "Int".to_string(),
+            },
+        ];
+        let actions = vec![RedeemerAction {
+            name: "Step".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_sm",
+            &ComposeOptions {
+                counter_field: Some("sequence"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(composed
+            .action_checks
+            .iter()
+            .any(|c| c.contains("expect out_datum.sequence == datum.sequence + 1")));
+        assert!(composed
+            .test_helpers
+            .contains("CustomDatum { ..test_datum(), sequence: test_datum().sequence + 1 }"));
+        assert!(composed
+            .test_helpers
+            .contains("datum: InlineDatum(test_datum_next()),"));
+        let replay = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test counter_not_advanced_fails() fail"))
+            .unwrap();
+        assert!(replay.contains("datum: InlineDatum(test_datum()),"));
+    }
+
+    #[test]
+    fn test_compose_contributions_for_feature_stack() {
+        let features = resolve_features(
+            &[
+                Feature::SignatureAuth,
+                Feature::ReferenceSafety,
+                Feature::ValuePreservation,
+            ],
+            "spend",
+        )
+        .unwrap();
+        assert_eq!(features.len(), 4);
+        let datum_fields = vec![DatumField {
+            name: "owner".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let actions = vec![RedeemerAction {
+            name: "Update".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_stack",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        let order: Vec<Feature> = composed.contributions.iter().map(|c| c.feature).collect();
+        assert_eq!(order, features);
+
+        let by_feature = |f: Feature| {
+            composed
+                .contributions
+                .iter()
+                .find(|c| c.feature == f)
+                .unwrap()
+        };
+        let sig = by_feature(Feature::SignatureAuth);
+        assert_eq!(
+            sig.params,
+            vec![("admin_pkh".to_string(), "ByteArray".to_string())]
+        );
+        assert!(sig.preamble.is_none());
+        assert!(sig.action_check.is_some());
+
+        let ref_safety = by_feature(Feature::ReferenceSafety);
+        assert_eq!(
+            ref_safety.preamble.as_deref(),
+            Some(feature_spec(Feature::ReferenceSafety).preamble_code)
+        );
+        assert!(ref_safety.action_check.is_none());
+        assert!(ref_safety.imports.is_empty());
+
+        let value = by_feature(Feature::ValuePreservation);
+        assert!(value.preamble.is_some() && value.action_check.is_some());
+
+        // Merged strings are derived from the contributions
+        let preambles: Vec<String> = composed
+            .contributions
+            .iter()
+            .filter_map(|c| c.preamble.clone())
+            .collect();
+        assert_eq!(preambles.len(), 3);
+        assert_eq!(composed.preamble, preambles.join("\n\n"));
+        assert_eq!(composed.action_checks.len(), 2);
+    }
+
+    #[test]
+    fn test_compose_pausable() {
+        let features = resolve_features(&[Feature::Pausable], "spend").unwrap();
+        assert!(features.contains(&Feature::SignatureAuth));
+        assert!(features.contains(&Feature::DatumContinuity));
+
+        let datum_fields =
+            crate::features::types::parse_datum_fields("owner:ByteArray,paused:Bool").unwrap();
+        let actions = crate::features::types::parse_redeemer_actions("Update,Unpause").unwrap();
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_vault",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(composed
+            .preamble
+            .contains("        Unpause -> !out_datum.paused\n        _ -> !datum.paused"));
+        assert!(composed.test_helpers.contains("    paused: False,"));
+        assert!(composed
+            .test_helpers
+            .contains("CustomDatum { ..test_datum(), paused: True }"));
+
+        let names: Vec<&str> = composed
+            .test_cases
+            .iter()
+            .filter_map(|t| t.lines().next())
+            .collect();
+        assert!(names.contains(&"test update_valid() {"));
+        assert!(names.contains(&"test update_while_paused_fails() fail {"));
+        assert!(names.contains(&"test unpause_valid() {"));
+        assert!(names.contains(&"test unpause_without_clearing_flag_fails() fail {"));
+        let unpause = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test unpause_valid()"))
+            .unwrap();
+        assert!(unpause.contains("extra_signatories: [test_admin],"));
+        assert!(unpause.contains("test_admin, Some(test_datum_paused()), Unpause, test_oref(), tx"));
+    }
+
+    #[test]
+    fn test_compose_mint_warns_when_nothing_limits_the_supply() {
+        let actions = crate::features::types::parse_redeemer_actions("Foo").unwrap();
+        let composed = compose(
+            &[Feature::SignatureAuth],
+            "mint",
+            &[],
+            &actions,
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        let codes: Vec<&str> = composed
+            .diagnostics
+            .warnings
+            .iter()
+            .map(|w| w.code.as_str())
+            .collect();
+        assert_eq!(codes, vec!["unconstrained-mint"]);
+        assert!(composed.diagnostics.warnings[0]
+            .message
+            .contains("Mint is unconstrained"));
+
+        let composed = compose(
+            &[Feature::SignatureAuth, Feature::ExactMintAmount],
+            "mint",
+            &[],
+            &actions,
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert!(composed.diagnostics.is_empty());
+
+        // Burn verification with nothing but the burn action
+        let burn_only = crate::features::types::parse_redeemer_actions("Burn").unwrap();
+        let composed = compose(
+            &[Feature::UniqueMint, Feature::BurnVerification],
+            "mint",
+            &[],
+            &burn_only,
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(composed.diagnostics.warnings.len(), 1);
+        assert_eq!(composed.diagnostics.warnings[0].code, "no-mint-action");
+
+        // Burn verification without a Burn action composes, but is flagged as checking nothing
+        let no_burn = crate::features::types::parse_redeemer_actions("Mint,Destroy").unwrap();
+        let composed = compose(
+            &[Feature::UniqueMint, Feature::BurnVerification],
+            "mint",
+            &[],
+            &no_burn,
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(composed.diagnostics.warnings.len(), 1);
+        let warning = &composed.diagnostics.warnings[0];
+        assert_eq!(warning.code, "burn-without-burn-action");
+        assert!(warning.message.contains("found: Mint, Destroy"));
+    }
+
+    #[test]
+    fn test_compose_continuity_only_warns_about_unchecked_actions() {
+        let features = resolve_features(&[Feature::DatumContinuity], "spend").unwrap();
+        let datum_fields = crate::features::types::parse_datum_fields("owner:ByteArray").unwrap();
+        let actions = crate::features::types::parse_redeemer_actions("Update").unwrap();
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_vault",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            composed.action_checks,
+            vec!["    // WARNING: no runtime checks on this action"]
+        );
+        assert_eq!(composed.diagnostics.warnings.len(), 1);
+        let warning = &composed.diagnostics.warnings[0];
+        assert_eq!(warning.code, "unchecked-action");
+        assert!(warning
+            .message
+            .contains("Action 'Update' has no per-action checks"));
+        assert_eq!(warning.location.as_deref(), Some("CustomRedeemer.Update"));
+
+        let features = resolve_features(&[Feature::SignatureAuth], "spend").unwrap();
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_vault",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert!(composed.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_require_reference_script_conflicts_with_reference_safety() {
+        let err = resolve_features(
+            &[Feature::ReferenceSafety, Feature::RequireReferenceScript],
+            "spend",
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("'reference-safety' conflicts with 'require-reference-script'"));
+    }
+
+    #[test]
+    fn test_compose_require_reference_script() {
+        let features = resolve_features(&[Feature::RequireReferenceScript], "spend").unwrap();
+        assert!(features.contains(&Feature::DatumContinuity));
+
+        let datum_fields = crate::features::types::parse_datum_fields("owner:ByteArray").unwrap();
+        let actions = crate::features::types::parse_redeemer_actions("Update").unwrap();
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_vault",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(composed
+            .preamble
+            .contains("expect cont_output.reference_script != None"));
+        // Positive path: the continuing output carries the reference script
+        assert!(composed
+            .test_helpers
+            .contains("    reference_script: Some(#\"deadbeef\"),\n  }"));
+        assert!(composed
+            .test_cases
+            .iter()
+            .any(|t| t.starts_with("test update_valid() {")));
+        let missing = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test reference_script_missing_fails() fail"))
+            .unwrap();
+        assert!(missing.contains("reference_script: None,"));
+    }
+
+    #[test]
+    fn test_compose_signature_threshold() {
+        let features = resolve_features(&[Feature::SignatureThresholdFromDatum], "spend").unwrap();
+        assert_eq!(features, vec![Feature::SignatureThresholdFromDatum]);
+
+        let composed = compose_spend(&features, "signers:List<ByteArray>,threshold:Int", "Spend");
+
+        assert!(composed.validator_params.is_empty());
+        assert!(composed.action_checks[0].contains("list.unique(datum.signers)"));
+        assert!(composed.action_checks[0].contains(") >= datum.threshold"));
+        assert!(composed.test_helpers.contains(
+            "    signers: [#\"a1a1a1a1\", #\"b2b2b2b2\", #\"c3c3c3c3\"],\n    threshold: 2,"
+        ));
+        assert!(test_case(&composed, "spend_valid() {")
+            .contains("extra_signatories: [#\"a1a1a1a1\", #\"b2b2b2b2\"],"));
+        assert!(test_case(&composed, "spend_below_threshold_fails() fail {")
+            .contains("extra_signatories: [#\"a1a1a1a1\"],"));
+    }
+
+    #[test]
+    fn test_compose_inline_datum_only() {
+        let datum_fields = crate::features::types::parse_datum_fields("owner:ByteArray").unwrap();
+        let actions = crate::features::types::parse_redeemer_actions("Claim").unwrap();
+        let features = resolve_features(&[Feature::InlineDatumOnly], "spend").unwrap();
+        assert_eq!(features, vec![Feature::InlineDatumOnly]);
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_vault",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        // Without continuity the preamble finds the own input itself
+        assert!(composed
+            .preamble
+            .contains("let script_credential = own_input.output.address.payment_credential"));
+        assert!(composed.action_checks[0]
+            .contains("_ -> o.address.payment_credential != script_credential"));
+        assert!(composed
+            .test_helpers
+            .contains("    address: script_addr(),\n    value: assets.from_lovelace(2_000_000),\n    datum: DatumHash("));
+        let hashed = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test claim_datum_hash_output_fails() fail {"))
+            .unwrap();
+        assert!(hashed.contains("inputs: [script_input()],"));
+        assert!(hashed.contains("outputs: list.concat(tx.outputs, [datum_hash_output()])"));
+        assert!(composed
+            .test_cases
+            .iter()
+            .all(|t| t.contains("inputs: [script_input()],")));
+
+        // With continuity it reuses own_address
+        let features = resolve_features(
+            &[Feature::DatumContinuity, Feature::InlineDatumOnly],
+            "spend",
+        )
+        .unwrap();
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_vault",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert!(composed
+            .preamble
+            .ends_with("let script_credential = own_address.payment_credential"));
+
+        // Mint checks outputs to the policy's own script credential
+        let actions = crate::features::types::parse_redeemer_actions("Mint").unwrap();
+        let features = resolve_features(&[Feature::InlineDatumOnly], "mint").unwrap();
+        let composed = compose(
+            &features,
+            "mint",
+            &[],
+            &actions,
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert!(composed.preamble.is_empty());
+        assert!(composed.action_checks[0]
+            .contains("_ -> o.address.payment_credential != Script(policy_id)"));
+        assert!(composed.test_helpers.contains(
+            "address: Address { payment_credential: Script(test_policy), stake_credential: None },"
+        ));
+        assert!(composed
+            .test_cases
+            .iter()
+            .any(|t| t.starts_with("test mint_datum_hash_output_fails() fail {")));
+    }
+
+    #[test]
+    fn test_compose_admin_rotation() {
+        let features = resolve_features(&[Feature::AdminRotation], "spend").unwrap();
+        assert!(features.contains(&Feature::SignatureAuth));
+        assert!(features.contains(&Feature::DatumContinuity));
+        assert!(resolve_features(
+            &[Feature::AdminRotation, Feature::MonotonicCounter],
+            "spend"
+        )
+        .is_err());
+
+        let composed = compose_spend(
+            &features,
+            "admin:ByteArray,amount:Int",
+            "RotateAdmin(new_admin:ByteArray),Withdraw",
+        );
+
+        // Signature auth reads the datum admin; no admin_pkh param remains
+        assert!(composed.validator_params.is_empty());
+        assert!(composed.action_checks[0].contains("list.has(self.extra_signatories, datum.admin)"));
+        assert!(composed
+            .preamble
+            .contains("out_datum == CustomDatum { ..datum, admin: new_admin }"));
+
+        assert!(test_case(&composed, "withdraw_valid() {")
+            .contains("Some(test_datum()), Withdraw, test_oref(), tx"));
+        let valid = test_case(&composed, "rotate_admin_valid() {");
+        assert!(valid.contains("extra_signatories: [test_admin],"));
+        assert!(valid.contains("CustomDatum { ..test_datum(), admin: test_new_admin }"));
+        assert!(valid.contains("RotateAdmin { new_admin: test_new_admin }, test_oref(), tx"));
+        assert!(
+            test_case(&composed, "rotate_admin_by_stranger_fails() fail {")
+                .contains("extra_signatories: [test_new_admin],")
+        );
+        assert!(test_case(
+            &composed,
+            "rotate_admin_changing_other_fields_fails() fail {"
+        )
+        .contains("admin: test_new_admin, amount: test_datum().amount + 1 }"));
+        assert!(
+            test_case(&composed, "withdraw_changing_admin_fails() fail {")
+                .contains("Withdraw, test_oref(), tx")
+        );
+    }
+
+    #[test]
+    fn test_compose_swap_fee() {
+        let features = resolve_features(&[Feature::SwapFee], "spend").unwrap();
+        assert!(features.contains(&Feature::DatumContinuity));
+
+        let datum_fields =
+            crate::features::types::parse_datum_fields("reserve_a:Int,reserve_b:Int").unwrap();
+        let actions = crate::features::types::parse_redeemer_actions("Swap").unwrap();
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_pool",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(composed
+            .validator_params
+            .contains(&("fee_bps".to_string(), "Int".to_string())));
+        assert!(composed.action_checks.join("\n").contains(
+            "out_datum.reserve_a * out_datum.reserve_b * 10_000 >= datum.reserve_a * datum.reserve_b * (10_000 + fee_bps)"
+        ));
+        assert!(composed.test_helpers.contains("    reserve_a: 10_000_000,"));
+        assert!(composed.test_helpers.contains(
+            "reserve_a: test_datum().reserve_a + 100_000, reserve_b: test_datum().reserve_b - 60_000"
+        ));
+
+        let valid = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test swap_valid()"))
+            .unwrap();
+        assert!(valid.contains("30, Some(test_datum()), Swap, test_oref(), tx"));
+        let unpaid = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test swap_fee_not_collected_fails() fail"))
+            .unwrap();
+        assert!(unpaid.contains("reserve_b: test_datum().reserve_b - 99_000,"));
+    }
+
+    #[test]
+    fn test_compose_expiry() {
+        let features = resolve_features(&[Feature::TimeLock, Feature::Expiry], "spend").unwrap();
+        let datum_fields = vec![
+            DatumField {
+                name: "deadline".to_string(),
+                aiken_type: "Int".to_string(),
+            },
+            DatumField {
+                name: "offer_end".to_string(),
+                aiken_type: "Int".to_string(),
+            },
+        ];
+        let actions = vec![RedeemerAction {
+            name: "Accept".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_offer",
+            &ComposeOptions {
+                expiry_field: Some("offer_end"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(composed.action_checks.iter().any(|c| c
+            .contains("expect interval.is_entirely_before(self.validity_range, datum.offer_end)")));
+        assert!(composed
+            .test_helpers
+            .contains("const test_expiry: Int = 2_000_000"));
+        assert!(composed
+            .test_helpers
+            .contains("    offer_end: test_expiry,"));
+        assert!(composed
+            .test_helpers
+            .contains("    deadline: test_deadline,"));
+
+        let valid = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test accept_valid()"))
+            .unwrap();
+        assert!(
+            valid.contains("validity_range: interval.between(test_deadline + 1, test_expiry - 1),")
+        );
+        let expired = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test accept_after_expiry_fails() fail"))
+            .unwrap();
+        assert!(expired.contains("validity_range: interval.after(test_expiry + 1),"));
+    }
+
+    #[test]
+    fn test_compose_deadline_window() {
+        let features = resolve_features(&[Feature::DeadlineWindow], "spend").unwrap();
+        let datum_fields = vec![
+            DatumField {
+                name: "start".to_string(),
+                aiken_type: "Int".to_string(),
+            },
+            DatumField {
+                name: "end".to_string(),
+                aiken_type: "Int".to_string(),
+            },
+        ];
+        let actions = vec![RedeemerAction {
+            name: "Bid".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_auction",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(composed.action_checks.iter().any(|c| c
+            .contains("expect interval.is_entirely_after(self.validity_range, datum.start)")
+            && c.contains("expect interval.is_entirely_before(self.validity_range, datum.end)")));
+        assert!(composed
+            .test_helpers
+            .contains("    start: test_window_start,"));
+        assert!(composed.test_helpers.contains("    end: test_window_end,"));
+
+        assert!(test_case(&composed, "bid_valid()").contains(
+            "validity_range: interval.between(test_window_start + 1, test_window_end - 1),"
+        ));
+        assert!(test_case(&composed, "bid_before_window_fails() fail")
+            .contains("validity_range: interval.before(test_window_start - 1),"));
+        assert!(test_case(&composed, "bid_after_window_fails() fail")
+            .contains("validity_range: interval.after(test_window_end + 1),"));
+
+        // Both bounds must be Int datum fields
+        let only_start = &datum_fields[..1];
+        let err = crate::features::types::validate_features_against_types(
+            &features, only_start, &actions, "spend",
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Feature 'deadline-window' requires datum field 'end:Int'"));
+        assert!(resolve_features(&[Feature::DeadlineWindow, Feature::Expiry], "spend").is_err());
+    }
+
+    #[test]
+    fn test_compose_periodic_limit() {
+        let features = resolve_features(&[Feature::PeriodicLimit], "spend").unwrap();
+        assert_eq!(
+            features,
+            vec![Feature::DatumContinuity, Feature::PeriodicLimit]
+        );
+        let composed = compose_spend(
+            &features,
+            "owner:ByteArray,period_start:Int,period_spent:Int",
+            "Withdraw",
+        );
+
+        assert_eq!(
+            &composed.validator_params[..],
+            &[
+                ("period".to_string(), "Int".to_string()),
+                ("limit".to_string(), "Int".to_string())
+            ]
+        );
+        assert!(composed
+            .preamble
+            .contains("expect interval.Finite(now) = self.validity_range.lower_bound.bound_type"));
+        assert!(composed
+            .preamble
+            .contains("let new_period = now >= datum.period_start + period"));
+        assert!(composed
+            .action_checks
+            .join("\n")
+            .contains("expect next_period_spent <= limit"));
+        assert!(composed
+            .test_helpers
+            .contains("    period_start: test_period_start,\n    period_spent: 0,"));
+        assert!(composed.test_helpers.contains(
+            "CustomDatum { ..test_datum(), period_spent: test_datum().period_spent + test_withdrawal }"
+        ));
+        assert!(composed
+            .test_helpers
+            .contains("value: assets.from_lovelace(10_000_000 - test_withdrawal),"));
+
+        // Within the limit: the positive path withdraws test_withdrawal in the first period
+        let valid = test_case(&composed, "withdraw_valid()");
+        assert!(valid.contains("validity_range: interval.after(test_period_start + 1),"));
+        assert!(valid.contains("test_period, test_limit, Some(test_datum()), Withdraw"));
+        // Over the limit, with consistent accounting, still fails
+        let over = test_case(&composed, "withdraw_over_limit_fails() fail");
+        assert!(over.contains("value: assets.from_lovelace(10_000_000 - test_limit - 1),"));
+        assert!(over.contains("period_spent: test_limit + 1 }"));
+        // An exhausted period rejects further withdrawals until a new period resets it
+        let exhausted = test_case(&composed, "withdraw_period_exhausted_fails() fail");
+        assert!(
+            exhausted.contains("Some(CustomDatum { ..test_datum(), period_spent: test_limit })")
+        );
+        let reset = test_case(&composed, "withdraw_new_period_resets_limit()");
+        assert!(!reset.contains(" fail {"));
+        assert!(
+            reset.contains("validity_range: interval.after(test_period_start + test_period + 1),")
+        );
+        assert!(reset.contains(
+            "period_start: test_period_start + test_period + 1, period_spent: test_withdrawal }"
+        ));
+
+        // Withdrawals are impossible under value-preservation
+        assert!(resolve_features(
+            &[Feature::PeriodicLimit, Feature::ValuePreservation],
+            "spend"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_compose_continuation_at_index() {
+        let features = resolve_features(&[Feature::ContinuationAtIndex], "spend").unwrap();
+        assert_eq!(
+            features,
+            vec![Feature::DatumContinuity, Feature::ContinuationAtIndex]
+        );
+        let datum_fields = vec![DatumField {
+            name: "owner".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let actions = vec![RedeemerAction {
+            name: "Update".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_ordered",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            composed.validator_params,
+            vec![("continuation_index".to_string(), "Int".to_string())]
+        );
+        assert!(composed
+            .preamble
+            .contains("expect Some(cont_output) = list.at(self.outputs, continuation_index)"));
+        assert!(!composed.preamble.contains("list.find(self.outputs"));
+        assert_eq!(
+            composed
+                .preamble
+                .matches("Continuing output must sit at the configured index")
+                .count(),
+            1
+        );
+
+        let wrong_index = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test continuation_at_wrong_index_fails() fail"))
+            .unwrap();
+        assert!(wrong_index.contains("        cont_output_ok(),\n      ],"));
+        assert!(wrong_index.contains("test_ordered.spend(0, Some(test_datum()), Update"));
+    }
+
+    #[test]
+    fn test_compose_change_to_self() {
+        let resolved = resolve_features(&[Feature::ChangeToSelf], "spend").unwrap();
+        assert!(resolved.contains(&Feature::DatumContinuity));
+
+        let datum_fields = vec![DatumField {
+            name: "owner".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let actions = vec![RedeemerAction {
+            name: "Rebalance".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &resolved,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_validator",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(composed
+            .validator_params
+            .contains(&("change_recipients".to_string(), "List<Address>".to_string())));
+        let check = composed
+            .action_checks
+            .iter()
+            .find(|c| {
+                c.contains("o.address == own_address || list.has(change_recipients, o.address)")
+            })
+            .expect("change check");
+        assert!(!check.contains("o == cont_output"));
+        assert!(composed
+            .test_helpers
+            .contains("fn test_fee_payer() -> Address {"));
+        let payout = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test payout_and_fee_payer_change_valid() {"))
+            .expect("positive payout case");
+        assert!(payout.contains("address: test_payee(),"));
+        assert!(payout.contains("address: test_fee_payer(),"));
+        assert!(payout.contains("[test_payee(), test_fee_payer()], Some(test_datum())"));
+        assert!(composed
+            .test_cases
+            .iter()
+            .any(|t| t.starts_with("test change_to_other_address_fails() fail")));
+    }
+
+    #[test]
+    fn test_compose_token_gated() {
+        let resolved = resolve_features(&[Feature::TokenGated], "spend").unwrap();
+        assert_eq!(resolved, vec![Feature::TokenGated]);
+
+        let datum_fields = vec![DatumField {
+            name: "owner".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let actions = vec![RedeemerAction {
+            name: "Vote".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &resolved,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_dao",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(composed.imports.contains(&"use cardano/assets".to_string()));
+        assert_eq!(
+            composed.validator_params,
+            vec![
+                ("gov_policy".to_string(), "ByteArray".to_string()),
+                ("gov_asset".to_string(), "ByteArray".to_string()),
+                ("min_tokens".to_string(), "Int".to_string()),
+            ]
+        );
+        assert!(composed.action_checks.iter().any(|c| {
+            c.contains("list.concat(self.inputs, self.reference_inputs)")
+                && c.contains("assets.quantity_of(i.output.value, gov_policy, gov_asset)")
+                && c.contains(") >= min_tokens")
+        }));
+        assert!(composed.test_helpers.contains("fn gov_input() -> Input {"));
+        assert!(composed
+            .test_helpers
+            .contains("assets.from_asset(test_gov_policy, test_gov_asset, test_min_tokens)"));
+
+        let call =
+            "test_dao.spend(test_gov_policy, test_gov_asset, test_min_tokens, Some(test_datum())";
+        let valid = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test vote_valid()"))
+            .unwrap();
+        assert!(valid.contains("      reference_inputs: [gov_input()],\n"));
+        assert!(valid.contains(call));
+        let no_gov = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test vote_without_gov_tokens_fails() fail"))
+            .unwrap();
+        assert!(!no_gov.contains("gov_input()"));
+        assert!(no_gov.contains(call));
+        assert!(composed
+            .test_cases
+            .iter()
+            .filter(|t| !t.starts_with("test vote_without_gov_tokens_fails()"))
+            .all(|t| t.contains("reference_inputs: [gov_input()]")));
+    }
+
+    #[test]
+    fn test_compose_nft_preservation() {
+        let compose_with = |features: &[Feature]| {
+            let resolved = resolve_features(features, "spend").unwrap();
+            compose(
+                &resolved,
+                "spend",
+                &[DatumField {
+                    name: "owner".to_string(),
+                    aiken_type: "ByteArray".to_string(),
+                }],
+                &[RedeemerAction {
+                    name: "Update".to_string(),
+                    fields: vec![],
+                }],
+                "test_vault",
+                &ComposeOptions::default(),
+            )
+            .unwrap()
+        };
+        let names_of = |composed: &ComposedValidator| -> Vec<String> {
+            composed
+                .test_cases
+                .iter()
+                .filter_map(|t| t.lines().next().map(str::to_string))
+                .collect()
+        };
+
+        let composed = compose_with(&[Feature::NftPreservation]);
+        assert!(composed
+            .imports
+            .iter()
+            .any(|i| i.contains("without_lovelace")));
+        assert!(composed.action_checks.iter().any(|c| {
+            c.contains(
+            "expect without_lovelace(cont_output.value) == without_lovelace(own_input.output.value)"
+        )
+        }));
+        // Only the assets are pinned; nothing compares lovelace
+        assert!(!composed
+            .action_checks
+            .iter()
+            .any(|c| c.contains("lovelace_of")));
+        assert!(composed.test_helpers.contains(
+            "      value: assets.add(assets.from_lovelace(10_000_000), test_nft_policy, test_nft_name, 1),"
+        ));
+        let names = names_of(&composed);
+        assert!(names.contains(&"test nft_preserved_with_less_ada() {".to_string()));
+        assert!(
+            names.contains(&"test nft_removed_from_continuing_output_fails() fail {".to_string())
+        );
+        let less_ada = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test nft_preserved_with_less_ada()"))
+            .unwrap();
+        assert!(less_ada.contains(
+            "value: assets.add(assets.from_lovelace(8_000_000), test_nft_policy, test_nft_name, 1),"
+        ));
+
+        // Value preservation forbids the ADA decrease, so that case isn't generated
+        let with_value = compose_with(&[Feature::NftPreservation, Feature::ValuePreservation]);
+        let with_value_names = names_of(&with_value);
+        assert!(!with_value_names
+            .iter()
+            .any(|n| n.starts_with("test nft_preserved_with_less_ada()")));
+        assert!(with_value_names
+            .iter()
+            .any(|n| n.starts_with("test nft_removed_from_continuing_output_fails()")));
+    }
+
+    #[test]
+    fn test_compose_min_signers() {
+        let features =
+            resolve_features(&[Feature::SignatureAuth, Feature::MinSigners], "spend").unwrap();
+        assert_eq!(features, vec![Feature::SignatureAuth, Feature::MinSigners]);
+        let composed = compose_spend(&features, "owner:ByteArray", "Release");
+
+        assert!(composed
+            .validator_params
+            .contains(&("min_signers".to_string(), "Int".to_string())));
+        assert!(composed
+            .action_checks
+            .iter()
+            .any(|c| c.contains("expect list.length(self.extra_signatories) >= min_signers")));
+        // The admin plus the cosigner
+        assert!(composed
+            .test_helpers
+            .contains("const test_min_signers: Int = 2\n"));
+        assert!(test_case(&composed, "release_valid() {")
+            .contains("extra_signatories: [test_admin, test_cosigner],"));
+        assert!(
+            test_case(&composed, "release_too_few_signers_fails() fail {")
+                .contains("extra_signatories: [test_admin],")
+        );
+
+        // Purpose-agnostic: the mint tests sign with the cosigner too
+        let minted = compose(
+            &[Feature::MinSigners],
+            "mint",
+            &[],
+            &crate::features::types::parse_redeemer_actions("Mint").unwrap(),
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert!(minted
+            .test_helpers
+            .contains("const test_min_signers: Int = 1\n"));
+        assert!(
+            test_case(&minted, "mint_valid() {").contains("extra_signatories: [test_cosigner],")
+        );
+        assert!(test_case(&minted, "mint_too_few_signers_fails() fail {")
+            .contains("extra_signatories: [],"));
+    }
+
+    #[test]
+    fn test_compose_bounded_validity() {
+        let features =
+            resolve_features(&[Feature::TimeLock, Feature::BoundedValidity], "spend").unwrap();
+        assert_eq!(features, vec![Feature::TimeLock, Feature::BoundedValidity]);
+        let composed = compose_spend(&features, "owner:ByteArray,lock_until:Int", "Release");
+
+        assert!(composed
+            .validator_params
+            .contains(&("max_ttl".to_string(), "Int".to_string())));
+        assert!(composed.action_checks.iter().any(|c| c
+            .contains("expect interval.Finite(valid_until) = self.validity_range.upper_bound.bound_type\n    expect valid_until - valid_from <= max_ttl")));
+        assert!(composed
+            .test_helpers
+            .contains("const test_max_ttl: Int = 100_000\n"));
+        // Past the deadline, and no wider than max_ttl
+        let bounded = "validity_range: interval.between(test_deadline + 1, test_deadline + 1 + test_max_ttl),";
+        assert!(test_case(&composed, "release_valid() {").contains(bounded));
+        assert!(test_case(&composed, "no_datum_fails() fail {").contains(bounded));
+        assert!(test_case(&composed, "release_validity_too_wide_fails() fail {").contains(
+            "validity_range: interval.between(test_deadline + 1, test_deadline + 1 + test_max_ttl + 1),"
+        ));
+        assert!(
+            test_case(&composed, "release_unbounded_validity_fails() fail {")
+                .contains("validity_range: interval.after(test_deadline + 1),")
+        );
+
+        // Purpose-agnostic: mints and burns carry a bounded range too
+        let minted = compose(
+            &[Feature::BoundedValidity, Feature::BurnVerification],
+            "mint",
+            &[],
+            &crate::features::types::parse_redeemer_actions("Mint,Burn").unwrap(),
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        let bounded = "validity_range: interval.between(0, test_max_ttl),";
+        assert!(test_case(&minted, "mint_valid() {").contains(bounded));
+        assert!(test_case(&minted, "burn_valid() {").contains(bounded));
+        assert!(test_case(&minted, "mint_validity_too_wide_fails() fail {")
+            .contains("validity_range: interval.between(0, test_max_ttl + 1),"));
+        assert!(test_case(&minted, "mint_unbounded_validity_fails() fail {")
+            .contains("validity_range: interval.after(0),"));
+    }
+
+    #[test]
+    fn test_compose_deposit_only() {
+        let features = resolve_features(&[Feature::DepositOnly], "spend").unwrap();
+        assert_eq!(
+            features,
+            vec![Feature::DatumContinuity, Feature::DepositOnly]
+        );
+
+        let composed = compose_spend(&features, "owner:ByteArray", "Deposit,Withdraw(amount:Int)");
+
+        assert!(composed.preamble.contains(
+            "Deposit ->\n          and {\n            lovelace_of(cont_output.value) >= lovelace_of(own_input.output.value),"
+        ));
+        assert!(composed.preamble.contains("        _ -> True\n"));
+        assert!(composed
+            .imports
+            .contains(&"use cardano/assets.{lovelace_of, without_lovelace}".to_string()));
+
+        // The shared tests run Withdraw, which deposit-only leaves alone
+        assert!(test_case(&composed, "withdraw_valid() {")
+            .contains("Withdraw { amount: 5_000_000 }, test_oref(), tx"));
+        let valid = test_case(&composed, "deposit_adding_funds_valid() {");
+        assert!(valid.contains("value: assets.from_lovelace(10_000_000 + test_deposit),"));
+        assert!(valid.contains("Some(test_datum()), Deposit, test_oref(), tx"));
+        assert!(
+            test_case(&composed, "deposit_withdrawing_funds_fails() fail {")
+                .contains("value: assets.from_lovelace(10_000_000 - test_deposit),")
+        );
+        assert!(
+            test_case(&composed, "deposit_adding_other_assets_fails() fail {").contains(
+                "assets.add(assets.from_lovelace(10_000_000 + test_deposit), #\"ff\", \"dust\", 1)"
+            )
+        );
+    }
+
+    #[test]
+    fn test_compose_no_reference_inputs() {
+        let features = resolve_features(
+            &[Feature::SignatureAuth, Feature::NoReferenceInputs],
+            "spend",
+        )
+        .unwrap();
+        assert_eq!(
+            features,
+            vec![Feature::SignatureAuth, Feature::NoReferenceInputs]
+        );
+        // Token gating counts referenced holdings, so it can't be combined
+        let err = resolve_features(&[Feature::NoReferenceInputs, Feature::TokenGated], "spend")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("conflicts with"), "{err}");
+        assert!(resolve_features(
+            &[Feature::NoReferenceInputs, Feature::RequireReferenceScript],
+            "spend"
+        )
+        .is_err());
+
+        let composed = compose_spend(&features, "owner:ByteArray", "Withdraw");
+        assert!(composed
+            .action_checks
+            .iter()
+            .any(|c| c.contains("expect self.reference_inputs == []")));
+        assert!(composed
+            .test_helpers
+            .contains("fn stray_reference_input() -> Input {"));
+        assert!(!test_case(&composed, "withdraw_valid() {").contains("reference_inputs"));
+        let referenced = test_case(&composed, "withdraw_with_reference_input_fails() fail {");
+        assert!(referenced.contains(
+            "      ..transaction.placeholder,\n      reference_inputs: [stray_reference_input()],\n      extra_signatories: [test_admin],\n"
+        ));
+
+        // Purpose-agnostic: a mint with a reference input fails too
+        let minted = compose(
+            &[Feature::NoReferenceInputs],
+            "mint",
+            &[],
+            &crate::features::types::parse_redeemer_actions("Mint").unwrap(),
+            "test_policy",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert!(minted.test_cases.iter().any(|t| t
+            .starts_with("test mint_with_reference_input_fails() fail {")
+            && t.contains("reference_inputs: [stray_reference_input()],")));
+    }
+
+    #[test]
+    fn test_compose_no_minting() {
+        let features =
+            resolve_features(&[Feature::SignatureAuth, Feature::NoMinting], "spend").unwrap();
+        assert_eq!(features, vec![Feature::SignatureAuth, Feature::NoMinting]);
+        // Spend-only: a minting policy mints by definition
+        assert!(resolve_features(&[Feature::NoMinting], "mint").is_err());
+
+        let composed = compose_spend(&features, "owner:ByteArray", "Withdraw");
+        assert!(composed.imports.iter().any(|i| i == "use cardano/assets"));
+        assert!(composed
+            .action_checks
+            .iter()
+            .any(|c| c.contains("expect self.mint == assets.zero")));
+        assert!(!test_case(&composed, "withdraw_valid() {").contains("mint:"));
+        let minted = test_case(&composed, "withdraw_with_mint_fails() fail {");
+        assert!(minted.contains(
+            "      ..transaction.placeholder,\n      mint: assets.from_asset(#\"face\", \"stray\", 1),\n      extra_signatories: [test_admin],\n"
+        ));
+    }
+
+    #[test]
+    fn test_compose_refundable_deposit() {
+        let features = resolve_features(
+            &[Feature::SignatureAuth, Feature::RefundableDeposit],
+            "spend",
+        )
+        .unwrap();
+        assert_eq!(
+            features,
+            vec![Feature::SignatureAuth, Feature::RefundableDeposit]
+        );
+        // A refund closes the position, so nothing may require a continuing output
+        assert!(resolve_features(
+            &[Feature::RefundableDeposit, Feature::DatumContinuity],
+            "spend"
+        )
+        .is_err());
+
+        let composed = compose_spend(
+            &features,
+            "depositor:ByteArray,claim_deadline:Int",
+            "Claim,Refund",
+        );
+
+        assert!(composed.preamble.contains(
+            "Refund -> {\n          expect Some(deposit_input) =\n            list.find(self.inputs, fn(i) { i.output_reference == own_ref })\n          and {\n            list.has(self.extra_signatories, datum.depositor),\n            interval.is_entirely_before(self.validity_range, datum.claim_deadline),"
+        ));
+        assert!(composed
+            .preamble
+            .contains("VerificationKey(datum.depositor)"));
+        assert!(composed
+            .imports
+            .contains(&"use cardano/address.{Address, Script, VerificationKey}".to_string()));
+
+        // The shared tests run Claim, which refundable-deposit leaves alone
+        assert!(test_case(&composed, "claim_valid() {")
+            .contains("Some(test_datum()), Claim, test_oref(), tx"));
+        let before = test_case(&composed, "refund_before_deadline_valid() {");
+        assert!(before.contains("extra_signatories: [test_admin, test_depositor],"));
+        assert!(before.contains("validity_range: interval.before(test_claim_deadline - 1),"));
+        assert!(before.contains("payment_credential: VerificationKey(test_depositor)"));
+        assert!(before.contains("Some(test_datum()), Refund, test_oref(), tx"));
+        let after = test_case(&composed, "refund_after_deadline_fails() fail {");
+        assert!(after.contains("validity_range: interval.after(test_claim_deadline),"));
+        assert!(test_case(
+            &composed,
+            "refund_without_depositor_signature_fails() fail {"
+        )
+        .contains("extra_signatories: [test_admin],"));
+        assert!(
+            test_case(&composed, "refund_short_of_deposit_fails() fail {")
+                .contains("value: assets.from_lovelace(10_000_000 - 1),")
+        );
+        assert!(composed.test_helpers.contains(
+            "    depositor: test_depositor,\n    claim_deadline: test_claim_deadline,\n"
+        ));
+    }
+
+    #[test]
+    fn test_compose_proportional_payout() {
+        let features = resolve_features(&[Feature::ProportionalPayout], "spend").unwrap();
+        assert_eq!(
+            features,
+            vec![Feature::DatumContinuity, Feature::ProportionalPayout]
+        );
+        assert!(resolve_features(
+            &[Feature::ProportionalPayout, Feature::ValuePreservation],
+            "spend"
+        )
+        .is_err());
+
+        let composed = compose_spend(
+            &features,
+            "owner:ByteArray,total_pool:Int,total_shares:Int",
+            "Claim(claimant_share:Int),Deposit",
+        );
+
+        assert!(composed
+            .preamble
+            .contains("let entitled = datum.total_pool * claimant_share / datum.total_shares"));
+        assert!(composed.preamble.contains(
+            "lovelace_of(cont_output.value) >= lovelace_of(own_input.output.value) - entitled,"
+        ));
+        assert!(composed
+            .preamble
+            .contains("out_datum.total_shares == datum.total_shares - claimant_share,"));
+        assert!(composed
+            .test_helpers
+            .contains("const test_payout: Int = 2_857_142\n"));
+
+        // The shared tests run Deposit, which must leave the pool accounting alone
+        assert!(test_case(&composed, "deposit_valid() {").contains("Deposit, test_oref(), tx"));
+        let valid = test_case(&composed, "claim_pro_rata_valid() {");
+        assert!(valid.contains("value: assets.from_lovelace(10_000_000 - test_payout),"));
+        assert!(valid.contains(
+            "total_pool: test_datum().total_pool - test_payout, total_shares: test_total_shares - test_claimant_share"
+        ));
+        assert!(valid.contains(
+            "Some(CustomDatum { ..test_datum(), total_shares: test_total_shares }), Claim { claimant_share: test_claimant_share }, test_oref(), tx"
+        ));
+        assert!(
+            test_case(&composed, "claim_over_pro_rata_share_fails() fail {")
+                .contains("value: assets.from_lovelace(10_000_000 - test_payout - 1),")
+        );
+    }
+
+    #[test]
+    fn test_compose_asset_whitelist() {
+        let resolved = resolve_features(&[Feature::AssetWhitelist], "spend").unwrap();
+        assert!(resolved.contains(&Feature::DatumContinuity));
+
+        let datum_fields = vec![DatumField {
+            name: "owner".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let actions = vec![RedeemerAction {
+            name: "Deposit".to_string(),
+            fields: vec![],
+        }];
+        let composed = compose(
+            &resolved,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_treasury",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(composed
+            .imports
+            .contains(&"use cardano/assets.{without_lovelace}".to_string()));
+        assert!(composed
+            .validator_params
+            .contains(&("whitelist_policy".to_string(), "ByteArray".to_string())));
+        assert!(composed.action_checks.iter().any(|c| {
+            c.contains("assets.flatten(without_lovelace(cont_output.value))")
+                && c.contains("policy == whitelist_policy && name == whitelist_asset")
+        }));
+        let dust = composed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test foreign_token_in_continuing_output_fails() fail"))
+            .unwrap();
+        assert!(dust.contains("        Output {\n          ..cont_output_ok(),\n"));
+        assert!(dust.contains("assets.from_asset(#\"ff\", \"dust\", 1)"));
+        assert!(dust.contains("test_treasury.spend(#\"00\", #\"00\", Some(test_datum())"));
+    }
+
+    #[test]
+    fn test_compose_explain_tags_blocks_with_provenance() {
+        let features = vec![
+            Feature::SignatureAuth,
+            Feature::DatumContinuity,
+            Feature::ReferenceSafety,
+        ];
+        let datum_fields = vec![DatumField {
+            name: "admin".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let actions = vec![RedeemerAction {
+            name: "Execute".to_string(),
+            fields: vec![],
+        }];
+        let explained = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_validator",
+            &ComposeOptions {
+                explain: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(explained.preamble.contains(
+            "// [feature: datum-continuity] Find continuing output and validate datum preservation"
+        ));
+        assert!(explained
+            .preamble
+            .contains("// [feature: reference-safety] Reject reference script injection"));
+        assert!(explained.action_checks[0].starts_with(
+            "    // [feature: signature-auth] Require a specific signer in extra_signatories\n"
+        ));
+
+        let plain = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_validator",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert!(!plain.preamble.contains("[feature:"));
+    }
+
+    #[test]
+    fn test_compose_trace_precedes_each_block() {
+        let features = vec![Feature::SignatureAuth, Feature::DatumContinuity];
+        let datum_fields = vec![DatumField {
+            name: "admin".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let actions = vec![RedeemerAction {
+            name: "Execute".to_string(),
+            fields: vec![],
+        }];
+        let traced = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_validator",
+            &ComposeOptions {
+                trace: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(traced.preamble.starts_with(
+            "    trace @\"datum-continuity: Find continuing output and validate datum preservation\"\n"
+        ));
+        assert!(traced.action_checks[0].starts_with(
+            "    trace @\"signature-auth: Require a specific signer in extra_signatories\"\n"
+        ));
+
+        let plain = compose(
+            &features,
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_validator",
+            &ComposeOptions::default(),
+        )
+        .unwrap();
+        assert!(!plain.preamble.contains("trace @"));
+        assert!(plain.action_checks.iter().all(|c| !c.contains("trace @")));
+    }
+
+    #[test]
+    fn test_compose_property_tests_fuzz_value_preservation() {
+        let features = resolve_features(
+            &[Feature::SignatureAuth, Feature::ValuePreservation],
+            "spend",
+        )
+        .unwrap();
+        let datum_fields = vec![DatumField {
+            name: "owner".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let actions = vec![RedeemerAction {
+            name: "Withdraw".to_string(),
+            fields: vec![],
+        }];
+        let compose_with = |property_tests: bool| {
+            compose(
+                &features,
+                "spend",
+                &datum_fields,
+                &actions,
+                "test_vault",
+                &ComposeOptions {
+                    property_tests,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let fuzzed = compose_with(true);
+        assert!(fuzzed.imports.contains(&"use aiken/fuzz".to_string()));
+        let preserved = fuzzed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test withdraw_value_preserved_property(\n"))
+            .expect("missing value-preserved property test");
+        assert!(preserved.contains("  amounts via fuzz.both(fuzz.int_between(2_000_000, 1_000_000_000_000), fuzz.int_between(0, 1_000_000_000_000)),\n) {\n"));
+        assert!(preserved.contains("value: assets.from_lovelace(lovelace + surplus)"));
+        let decreased = fuzzed
+            .test_cases
+            .iter()
+            .find(|t| t.starts_with("test withdraw_value_decreased_property(\n"))
+            .expect("missing value-decreased property test");
+        assert!(decreased.contains("\n) fail {\n  let (lovelace, shortfall) = amounts\n"));
+        assert!(decreased.contains("value: assets.from_lovelace(lovelace - shortfall)"));
+
+        // Off by default, and nothing to fuzz without value preservation
+        let plain = compose_with(false);
+        assert!(plain.test_cases.iter().all(|t| !t.contains(" via fuzz.")));
+        assert!(!plain.imports.contains(&"use aiken/fuzz".to_string()));
+        let unpreserved = compose(
+            &[Feature::SignatureAuth],
+            "spend",
+            &datum_fields,
+            &actions,
+            "test_vault",
+            &ComposeOptions {
+                property_tests: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(!unpreserved.imports.contains(&"use aiken/fuzz".to_string()));
+    }
+
+    #[test]
+    fn test_redeemer_action_expr_tuple_field() {
+        let action = RedeemerAction {
+            name: "Withdraw".to_string(),
+            fields: vec![
+                ("pair".to_string(), "(ByteArray, Int)".to_string()),
+                ("amount".to_string(), "Int".to_string()),
+            ],
+        };
+        assert_eq!(
+            redeemer_action_expr(&action),
+            "Withdraw { pair: (#\"aabb\", 5_000_000), amount: 5_000_000 }"
+        );
+    }
+
+    #[test]
+    fn preview_tests_lists_composed_cases() {
+        let options = GenerateOptions::custom(
+            "myorg",
+            "my-validator",
+            "spend",
+            crate::features::types::parse_datum_fields("owner:ByteArray").unwrap(),
+            crate::features::types::parse_redeemer_actions("Execute").unwrap(),
+            vec!["signature-auth".to_string()],
+        );
+        let tests = preview_tests(&options).unwrap();
+        let names: Vec<&str> = tests.iter().filter_map(|t| t.lines().next()).collect();
+        assert!(names.contains(&"test execute_valid() {"));
+        assert!(names.contains(&"test no_datum_fails() fail {"));
+        assert!(names.iter().any(|n| n.ends_with("fail {")));
+
+        let escrow = GenerateOptions::escrow("myorg", "my-escrow");
+        assert!(preview_tests(&escrow).unwrap().is_empty());
+    }
 }
@@ -1,3 +1,4 @@
+pub mod cbor;
 pub mod compose;
 pub mod types;
 
@@ -12,16 +13,108 @@ pub enum Feature {
     SignatureAuth,
     /// Enforce validity_range before/after a deadline field
     TimeLock,
+    /// Require validity_range entirely before an expiry field (offers that expire).
+    /// Conflicts with TimeLock only when both bind the same datum field.
+    Expiry,
+    /// Require validity_range entirely within the `[datum.start, datum.end]` window
+    /// (timed auctions, voting periods). Conflicts with TimeLock and Expiry, which it subsumes.
+    DeadlineWindow,
     /// Find continuing output and validate datum preservation
     DatumContinuity,
     /// Verify lovelace math (input vs output) — requires DatumContinuity
     ValuePreservation,
+    /// Require the continuing output to carry exactly the own input's non-ADA assets, so held
+    /// NFTs never leave while ADA may vary — requires DatumContinuity. The asset-only half of
+    /// ValuePreservation, which also forbids an ADA decrease.
+    NftPreservation,
     /// Reject reference script injection on continuing output — requires DatumContinuity
     ReferenceSafety,
+    /// Require a reference script on the continuing output (script-reference deployments) —
+    /// requires DatumContinuity. Conflicts with ReferenceSafety.
+    RequireReferenceScript,
     /// Check all minted quantities are negative (mint-purpose only)
     BurnVerification,
     /// Enforce a minimum lovelace floor on continuing output — requires DatumContinuity
     BoundedOperations,
+    /// Require every output besides the continuing one to return to the script address or to a
+    /// `change_recipients` address (payees, the fee payer's change) — requires DatumContinuity
+    ChangeToSelf,
+    /// Require a single mint of exactly `max_supply` (mint-purpose only).
+    /// Typically combined with a consumed-UTxO uniqueness check so the policy mints once.
+    ExactMintAmount,
+    /// Require a seed UTxO to be consumed so the policy can only mint once (mint-purpose only)
+    UniqueMint,
+    /// Require an Int counter datum field (nonce/sequence/version) to advance by one — requires DatumContinuity.
+    /// Conflicts with any immutability check covering the counter field.
+    MonotonicCounter,
+    /// Require the continuing output at a fixed index in `self.outputs` — requires DatumContinuity.
+    /// Replaces the address search in the continuity preamble.
+    ContinuationAtIndex,
+    /// Circuit breaker: while the `paused: Bool` datum field is set, only `Unpause` may run,
+    /// and it must clear the flag — requires SignatureAuth (admin gate) and DatumContinuity
+    Pausable,
+    /// Require the `reserve_a * reserve_b` product to grow by at least `fee_bps` basis points,
+    /// so every swap leaves its fee in the pool — requires DatumContinuity
+    SwapFee,
+    /// Require CIP-25 `721` metadata for `mint_asset_name` (mint-purpose only).
+    /// Plutus can't see transaction metadata, so the `721` map is committed as the
+    /// inline datum of the first output instead.
+    Cip25Metadata,
+    /// Keep the admin in the `admin: ByteArray` datum field and let a `RotateAdmin { new_admin }`
+    /// action, signed by the current admin, replace it — requires SignatureAuth (which then reads
+    /// `datum.admin` instead of the `admin_pkh` param) and DatumContinuity
+    AdminRotation,
+    /// Reject any output to this script's payment credential that carries a datum hash instead
+    /// of an inline datum (datum hiding). Works for spend and mint (`Script(policy_id)`).
+    /// Overlaps with DatumContinuity, which only checks the continuing output, so it stays useful
+    /// alongside it.
+    InlineDatumOnly,
+    /// Require at least `datum.threshold` of the `datum.signers: List<ByteArray>` keys in
+    /// extra_signatories, so the signer set can change with the datum (spend-only)
+    SignatureThresholdFromDatum,
+    /// Allow only lovelace and the `(whitelist_policy, whitelist_asset)` token on the continuing
+    /// output, so nobody can lock foreign tokens (dust) into the script — requires DatumContinuity
+    AssetWhitelist,
+    /// Cap the net lovelace withdrawn within a rolling `period` at `limit`, tracked in the
+    /// `period_start`/`period_spent` datum fields; the total resets once `period` has passed
+    /// since `period_start` — requires DatumContinuity. Conflicts with ValuePreservation,
+    /// which forbids withdrawals.
+    PeriodicLimit,
+    /// Require the spent and referenced inputs to carry at least `min_tokens` of the
+    /// `(gov_policy, gov_asset)` governance token, proving the actor holds it (spend-only)
+    TokenGated,
+    /// Cap a `Claim { claimant_share }` payout at the floored pro-rata share
+    /// `total_pool * claimant_share / total_shares` of the datum pool, and burn the claimed shares
+    /// from the datum; other actions keep the pool accounting — requires DatumContinuity.
+    /// The share comes from the redeemer, so another feature must authenticate the claimant.
+    ProportionalPayout,
+    /// Require at least `min_signers` keys in extra_signatories, whoever they are. A coarse gate,
+    /// unlike SignatureThresholdFromDatum, which counts only the datum's signer keys; with
+    /// SignatureAuth it means "the admin plus at least N signers in total".
+    MinSigners,
+    /// Let a `Deposit` only add lovelace to the script and leave every other asset as it was;
+    /// other actions are unaffected — requires DatumContinuity. Unlike ValuePreservation, which
+    /// holds every action to the same rule, this scopes it to the deposit path.
+    DepositOnly,
+    /// Require both ends of the validity range to be finite and at most `max_ttl` apart, so no
+    /// transaction carries an open-ended or absurdly wide time window. Time checks reading one
+    /// bound (timelock, expiry, periodic-limit) then also pin the other.
+    BoundedValidity,
+    /// Let a field-less `Refund` return the whole deposit to the `depositor: ByteArray` datum
+    /// field, signed by that depositor and entirely before the `claim_deadline: Int` datum field;
+    /// other actions are unaffected (spend-only). The signature and expiry checks are scoped to
+    /// `Refund` rather than pulled in as SignatureAuth and Expiry, which gate every action.
+    /// Conflicts with DatumContinuity: a refund closes the position.
+    RefundableDeposit,
+    /// Require `reference_inputs` to be empty, so no referenced UTxO (e.g. a manipulable price or
+    /// config output) can influence the transaction. Conflicts with TokenGated, which counts
+    /// referenced holdings, and RequireReferenceScript, whose deployment is spent through a
+    /// reference input.
+    NoReferenceInputs,
+    /// Require the spending transaction to mint and burn nothing (`self.mint` is empty), so the
+    /// spend can't be bundled with a mint it might interact with (spend-only). This rules out any
+    /// mint coordination, but kaido's spend validators don't coordinate minting on their own.
+    NoMinting,
 }
 
 impl FromStr for Feature {
@@ -31,11 +124,61 @@ impl FromStr for Feature {
         match s.to_lowercase().replace('-', "_").as_str() {
             "sig" | "signature" | "signature_auth" => Ok(Feature::SignatureAuth),
             "timelock" | "time_lock" => Ok(Feature::TimeLock),
+            "expiry" | "expires" | "expiration" => Ok(Feature::Expiry),
+            "deadline_window" | "window" | "time_window" => Ok(Feature::DeadlineWindow),
             "datum_continuity" | "datum" | "continuity" => Ok(Feature::DatumContinuity),
             "value_preservation" | "value" | "preservation" => Ok(Feature::ValuePreservation),
+            "nft_preservation" | "asset_preservation" | "preserve_nft" | "keep_nft" => {
+                Ok(Feature::NftPreservation)
+            }
             "reference_safety" | "ref_safety" | "refsafety" => Ok(Feature::ReferenceSafety),
+            "require_reference_script" | "require_ref_script" | "ref_script" => {
+                Ok(Feature::RequireReferenceScript)
+            }
             "burn" | "burn_verification" => Ok(Feature::BurnVerification),
             "bounded" | "bounded_operations" | "floor" => Ok(Feature::BoundedOperations),
+            "change_to_self" | "change" => Ok(Feature::ChangeToSelf),
+            "exact_mint_amount" | "exact_mint" | "max_supply" | "fixed_supply" => {
+                Ok(Feature::ExactMintAmount)
+            }
+            "unique_mint" | "unique" | "one_shot" | "seed_utxo" => Ok(Feature::UniqueMint),
+            "monotonic_counter" | "counter" | "nonce" => Ok(Feature::MonotonicCounter),
+            "continuation_at_index" | "cont_index" | "output_index" => {
+                Ok(Feature::ContinuationAtIndex)
+            }
+            "pausable" | "pause" | "circuit_breaker" | "emergency_stop" => Ok(Feature::Pausable),
+            "swap_fee" | "pool_fee" | "fee" => Ok(Feature::SwapFee),
+            "cip25_metadata" | "cip25" | "metadata" | "enforce_metadata" => {
+                Ok(Feature::Cip25Metadata)
+            }
+            "admin_rotation" | "rotate_admin" | "key_rotation" => Ok(Feature::AdminRotation),
+            "inline_datum_only" | "inline_datum" | "no_datum_hash" => Ok(Feature::InlineDatumOnly),
+            "signature_threshold"
+            | "signature_threshold_from_datum"
+            | "datum_signers"
+            | "datum_multisig" => Ok(Feature::SignatureThresholdFromDatum),
+            "asset_whitelist" | "whitelist" | "allowed_assets" | "token_whitelist" => {
+                Ok(Feature::AssetWhitelist)
+            }
+            "periodic_limit" | "spending_limit" | "withdrawal_limit" | "rate_limit" => {
+                Ok(Feature::PeriodicLimit)
+            }
+            "token_gated" | "token_gate" | "governance_token" | "gov_token" => {
+                Ok(Feature::TokenGated)
+            }
+            "proportional_payout" | "pro_rata" | "prorata" | "pro_rata_payout" => {
+                Ok(Feature::ProportionalPayout)
+            }
+            "min_signers" | "min_signatures" | "signer_count" => Ok(Feature::MinSigners),
+            "deposit_only" | "deposits_only" | "add_only" => Ok(Feature::DepositOnly),
+            "bounded_validity" | "max_validity_range" | "max_ttl" | "ttl" => {
+                Ok(Feature::BoundedValidity)
+            }
+            "refundable_deposit" | "refundable" | "refund" => Ok(Feature::RefundableDeposit),
+            "no_reference_inputs" | "no_ref_inputs" | "no_references" => {
+                Ok(Feature::NoReferenceInputs)
+            }
+            "no_minting" | "no_mint" | "forbid_mint" => Ok(Feature::NoMinting),
             _ => Err(()),
         }
     }
@@ -47,11 +190,36 @@ impl Feature {
         &[
             Feature::SignatureAuth,
             Feature::TimeLock,
+            Feature::Expiry,
+            Feature::DeadlineWindow,
             Feature::DatumContinuity,
             Feature::ValuePreservation,
+            Feature::NftPreservation,
             Feature::ReferenceSafety,
+            Feature::RequireReferenceScript,
             Feature::BurnVerification,
             Feature::BoundedOperations,
+            Feature::ChangeToSelf,
+            Feature::ExactMintAmount,
+            Feature::UniqueMint,
+            Feature::MonotonicCounter,
+            Feature::ContinuationAtIndex,
+            Feature::Pausable,
+            Feature::SwapFee,
+            Feature::Cip25Metadata,
+            Feature::AdminRotation,
+            Feature::InlineDatumOnly,
+            Feature::SignatureThresholdFromDatum,
+            Feature::AssetWhitelist,
+            Feature::PeriodicLimit,
+            Feature::TokenGated,
+            Feature::ProportionalPayout,
+            Feature::MinSigners,
+            Feature::DepositOnly,
+            Feature::BoundedValidity,
+            Feature::RefundableDeposit,
+            Feature::NoReferenceInputs,
+            Feature::NoMinting,
         ]
     }
 
@@ -60,11 +228,36 @@ impl Feature {
         match self {
             Feature::SignatureAuth => "signature-auth",
             Feature::TimeLock => "timelock",
+            Feature::Expiry => "expiry",
+            Feature::DeadlineWindow => "deadline-window",
             Feature::DatumContinuity => "datum-continuity",
             Feature::ValuePreservation => "value-preservation",
+            Feature::NftPreservation => "nft-preservation",
             Feature::ReferenceSafety => "reference-safety",
+            Feature::RequireReferenceScript => "require-reference-script",
             Feature::BurnVerification => "burn-verification",
             Feature::BoundedOperations => "bounded-operations",
+            Feature::ChangeToSelf => "change-to-self",
+            Feature::ExactMintAmount => "exact-mint-amount",
+            Feature::UniqueMint => "unique-mint",
+            Feature::MonotonicCounter => "monotonic-counter",
+            Feature::ContinuationAtIndex => "continuation-at-index",
+            Feature::Pausable => "pausable",
+            Feature::SwapFee => "swap-fee",
+            Feature::Cip25Metadata => "cip25-metadata",
+            Feature::AdminRotation => "admin-rotation",
+            Feature::InlineDatumOnly => "inline-datum-only",
+            Feature::SignatureThresholdFromDatum => "signature-threshold",
+            Feature::AssetWhitelist => "asset-whitelist",
+            Feature::PeriodicLimit => "periodic-limit",
+            Feature::TokenGated => "token-gated",
+            Feature::ProportionalPayout => "proportional-payout",
+            Feature::MinSigners => "min-signers",
+            Feature::DepositOnly => "deposit-only",
+            Feature::BoundedValidity => "bounded-validity",
+            Feature::RefundableDeposit => "refundable-deposit",
+            Feature::NoReferenceInputs => "no-reference-inputs",
+            Feature::NoMinting => "no-minting",
         }
     }
 
@@ -73,11 +266,60 @@ impl Feature {
         match self {
             Feature::SignatureAuth => "Require a specific signer in extra_signatories",
             Feature::TimeLock => "Enforce validity_range before/after a deadline field",
+            Feature::Expiry => "Require spending before an expiry field (time-limited offers)",
+            Feature::DeadlineWindow => "Require spending between the datum start and end times",
             Feature::DatumContinuity => "Find continuing output and validate datum preservation",
             Feature::ValuePreservation => "Verify lovelace math (input >= output)",
+            Feature::NftPreservation => {
+                "Keep every non-ADA asset on the continuing output (ADA may vary)"
+            }
             Feature::ReferenceSafety => "Reject reference script injection on continuing output",
+            Feature::RequireReferenceScript => {
+                "Require a reference script on the continuing output"
+            }
             Feature::BurnVerification => "Check all minted quantities are negative (mint-only)",
             Feature::BoundedOperations => "Enforce minimum lovelace floor on continuing output",
+            Feature::ChangeToSelf => {
+                "Require other outputs to return to the script or to change_recipients"
+            }
+            Feature::ExactMintAmount => "Mint exactly max_supply in a single mint (mint-only)",
+            Feature::UniqueMint => {
+                "Require a seed UTxO to be spent so minting happens once (mint-only)"
+            }
+            Feature::MonotonicCounter => "Require a datum counter to advance by one on every spend",
+            Feature::ContinuationAtIndex => {
+                "Require the continuing output at a fixed index in the outputs"
+            }
+            Feature::Pausable => {
+                "Block every action but an admin Unpause while the datum is paused"
+            }
+            Feature::SwapFee => "Require the reserve product to grow by the fee_bps swap fee",
+            Feature::Cip25Metadata => {
+                "Require a CIP-25 721 metadata entry for the minted asset (mint-only)"
+            }
+            Feature::AdminRotation => "Store the admin in the datum and let RotateAdmin replace it",
+            Feature::InlineDatumOnly => {
+                "Reject outputs to the script address that use a datum hash instead of inline"
+            }
+            Feature::SignatureThresholdFromDatum => {
+                "Require datum.threshold of the datum.signers keys to sign"
+            }
+            Feature::AssetWhitelist => {
+                "Allow only lovelace and one whitelisted token on the continuing output"
+            }
+            Feature::PeriodicLimit => "Cap the lovelace withdrawn per rolling period at a limit",
+            Feature::TokenGated => "Require min_tokens of a governance token in the inputs",
+            Feature::ProportionalPayout => {
+                "Cap a Claim payout at the floored pro-rata share of the pool"
+            }
+            Feature::MinSigners => "Require at least min_signers signatures, from any keys",
+            Feature::DepositOnly => "Let a Deposit only add lovelace, never remove value",
+            Feature::BoundedValidity => "Require a finite validity range at most max_ttl wide",
+            Feature::RefundableDeposit => {
+                "Let the depositor Refund the whole deposit before claim_deadline"
+            }
+            Feature::NoReferenceInputs => "Reject transactions that carry any reference input",
+            Feature::NoMinting => "Reject spends whose transaction mints or burns anything",
         }
     }
 
@@ -85,12 +327,42 @@ impl Feature {
     pub fn order(&self) -> u8 {
         match self {
             Feature::SignatureAuth => 0,
+            // Shares a slot with SignatureAuth; both are independent signature checks
+            Feature::SignatureThresholdFromDatum => 0,
+            // Also only gate who may act
+            Feature::TokenGated | Feature::MinSigners => 0,
+            // Rejects the transaction before any other check reads it
+            Feature::NoReferenceInputs | Feature::NoMinting => 0,
             Feature::TimeLock => 1,
-            Feature::DatumContinuity => 2,
-            Feature::ReferenceSafety => 3,
-            Feature::ValuePreservation => 4,
-            Feature::BoundedOperations => 5,
-            Feature::BurnVerification => 6,
+            // Shares a slot with TimeLock; both only read the validity range
+            Feature::BoundedValidity => 1,
+            Feature::Expiry => 2,
+            // Conflicts with Expiry, so it can share the slot
+            Feature::DeadlineWindow => 2,
+            // An expiry check scoped to Refund; it reads no other feature's bindings
+            Feature::RefundableDeposit => 2,
+            Feature::DatumContinuity => 3,
+            Feature::ContinuationAtIndex => 4,
+            // After DatumContinuity, whose own_address it reuses
+            Feature::InlineDatumOnly => 4,
+            Feature::Pausable => 5,
+            // Shares a slot with Pausable; the two preambles don't interact
+            Feature::AdminRotation => 5,
+            // Mutually exclusive, so they can share a slot
+            Feature::ReferenceSafety | Feature::RequireReferenceScript => 6,
+            Feature::ValuePreservation => 7,
+            // Share a slot with ValuePreservation; all only read cont_output.value
+            Feature::AssetWhitelist | Feature::NftPreservation | Feature::DepositOnly => 7,
+            Feature::BoundedOperations => 8,
+            Feature::ChangeToSelf => 9,
+            Feature::MonotonicCounter => 10,
+            // Share a slot with MonotonicCounter; each only advances its own datum fields
+            Feature::PeriodicLimit | Feature::ProportionalPayout => 10,
+            Feature::SwapFee => 11,
+            Feature::UniqueMint => 12,
+            Feature::ExactMintAmount => 13,
+            Feature::Cip25Metadata => 14,
+            Feature::BurnVerification => 15,
         }
     }
 
@@ -111,6 +383,8 @@ pub struct FeatureSpec {
     pub imports: Vec<&'static str>,
     /// Datum fields this feature requires (name, aiken_type)
     pub required_datum_fields: Vec<(&'static str, &'static str)>,
+    /// Redeemer fields this feature reads (name, aiken_type); some action must declare each
+    pub required_redeemer_fields: Vec<(&'static str, &'static str)>,
     /// Validator parameters this feature adds (name, aiken_type)
     pub validator_params: Vec<(&'static str, &'static str)>,
     /// Code emitted before the redeemer match (in validator body, after datum extract)
@@ -133,6 +407,7 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
                 "use aiken/collection/list",
             ],
             required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
             validator_params: vec![("admin_pkh", "ByteArray")],
             preamble_code: "",
             per_action_code: "    // Admin must sign the transaction\n    expect list.has(self.extra_signatories, admin_pkh)",
@@ -146,6 +421,7 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
                 "use aiken/interval",
             ],
             required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
             validator_params: vec![],
             preamble_code: "",
             per_action_code: "    // Validity range must be entirely after the deadline\n    expect interval.is_entirely_after(self.validity_range, datum.deadline)",
@@ -154,6 +430,34 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
             purpose: Some("spend"),
         },
 
+        Feature::Expiry => FeatureSpec {
+            imports: vec![
+                "use aiken/interval",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: "",
+            per_action_code: "    // Validity range must be entirely before the expiry\n    expect interval.is_entirely_before(self.validity_range, datum.expiry)",
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::DeadlineWindow => FeatureSpec {
+            imports: vec![
+                "use aiken/interval",
+            ],
+            required_datum_fields: vec![("start", "Int"), ("end", "Int")],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: "",
+            per_action_code: "    // Validity range must lie entirely within the [start, end] window\n    expect interval.is_entirely_after(self.validity_range, datum.start)\n    expect interval.is_entirely_before(self.validity_range, datum.end)",
+            depends_on: vec![],
+            conflicts_with: vec![Feature::TimeLock, Feature::Expiry],
+            purpose: Some("spend"),
+        },
+
         Feature::DatumContinuity => FeatureSpec {
             imports: vec![
                 "use aiken/collection/list",
@@ -162,6 +466,7 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
                 "use cardano/transaction.{InlineDatum, Input, Output}",
             ],
             required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
             validator_params: vec![],
             preamble_code: concat!(
                 "    // Find own input to get address\n",
@@ -188,6 +493,7 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
                 "use cardano/assets.{lovelace_of, without_lovelace}",
             ],
             required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
             validator_params: vec![],
             preamble_code: concat!(
                 "    let input_lovelace = lovelace_of(own_input.output.value)\n",
@@ -201,14 +507,41 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
             purpose: Some("spend"),
         },
 
+        Feature::NftPreservation => FeatureSpec {
+            imports: vec![
+                "use cardano/assets.{without_lovelace}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: "",
+            per_action_code: "    // Preserve every non-ADA asset (NFTs); ADA may change\n    expect without_lovelace(cont_output.value) == without_lovelace(own_input.output.value)",
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
         Feature::ReferenceSafety => FeatureSpec {
             imports: vec![],
             required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
             validator_params: vec![],
             preamble_code: "    // Reference script injection protection\n    expect cont_output.reference_script == None",
             per_action_code: "",
             depends_on: vec![Feature::DatumContinuity],
-            conflicts_with: vec![],
+            conflicts_with: vec![Feature::RequireReferenceScript],
+            purpose: Some("spend"),
+        },
+
+        Feature::RequireReferenceScript => FeatureSpec {
+            imports: vec![],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: "    // Continuing output must carry a reference script (script-reference deployment)\n    expect cont_output.reference_script != None",
+            per_action_code: "",
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![Feature::ReferenceSafety],
             purpose: Some("spend"),
         },
 
@@ -218,6 +551,7 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
                 "use cardano/assets",
             ],
             required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
             validator_params: vec![],
             preamble_code: "",
             per_action_code: "",
@@ -231,6 +565,7 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
                 "use cardano/assets.{lovelace_of}",
             ],
             required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
             validator_params: vec![("min_lovelace", "Int")],
             preamble_code: "",
             per_action_code: "    // Enforce minimum lovelace floor on continuing output\n    expect lovelace_of(cont_output.value) >= min_lovelace",
@@ -238,9 +573,542 @@ pub fn feature_spec(f: Feature) -> FeatureSpec {
             conflicts_with: vec![],
             purpose: Some("spend"),
         },
+
+        Feature::ChangeToSelf => FeatureSpec {
+            // The generated tests pay allowlisted wallets through plain NoDatum outputs
+            imports: vec![
+                "use aiken/collection/list",
+                "use cardano/address",
+                "use cardano/address.{Address}",
+                "use cardano/transaction.{NoDatum, Output}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("change_recipients", "List<Address>")],
+            preamble_code: "",
+            per_action_code: "    // Value only goes back to the script (continuing output included) or to an\n    // allowlisted recipient (no change leakage to other parties)\n    expect\n      list.all(\n        self.outputs,\n        fn(o) {\n          o.address == own_address || list.has(change_recipients, o.address)\n        },\n      )",
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::MonotonicCounter => FeatureSpec {
+            imports: vec![],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: "",
+            per_action_code: "    // Counter must advance by exactly one (replay protection)\n    expect out_datum.counter_field == datum.counter_field + 1",
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::ContinuationAtIndex => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("continuation_index", "Int")],
+            // Spliced into the DatumContinuity preamble in place of the address search
+            preamble_code: concat!(
+                "    // Continuing output must sit at the configured index\n",
+                "    expect Some(cont_output) = list.at(self.outputs, continuation_index)\n",
+                "    expect cont_output.address == own_address",
+            ),
+            per_action_code: "",
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::Pausable => FeatureSpec {
+            imports: vec![],
+            required_datum_fields: vec![("paused", "Bool")],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: concat!(
+                "    // Circuit breaker: while paused only Unpause may run, and it must clear the flag\n",
+                "    expect\n",
+                "      when redeemer is {\n",
+                "        Unpause -> !out_datum.paused\n",
+                "        _ -> !datum.paused\n",
+                "      }",
+            ),
+            per_action_code: "",
+            depends_on: vec![Feature::SignatureAuth, Feature::DatumContinuity],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::SwapFee => FeatureSpec {
+            imports: vec![],
+            required_datum_fields: vec![("reserve_a", "Int"), ("reserve_b", "Int")],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("fee_bps", "Int")],
+            preamble_code: "",
+            per_action_code: "    // Swap fee: reserve product must grow by at least fee_bps / 10_000 (fee stays in the pool)\n    expect out_datum.reserve_a * out_datum.reserve_b * 10_000 >= datum.reserve_a * datum.reserve_b * (10_000 + fee_bps)",
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::AdminRotation => FeatureSpec {
+            imports: vec![],
+            required_datum_fields: vec![("admin", "ByteArray")],
+            required_redeemer_fields: vec![("new_admin", "ByteArray")],
+            validator_params: vec![],
+            preamble_code: concat!(
+                "    // Admin rotation: RotateAdmin changes only the admin; other actions keep it\n",
+                "    expect\n",
+                "      when redeemer is {\n",
+                "        RotateAdmin { new_admin } ->\n",
+                "          out_datum == CustomDatum { ..datum, admin: new_admin }\n",
+                "        _ -> out_datum.admin == datum.admin\n",
+                "      }",
+            ),
+            per_action_code: "",
+            depends_on: vec![Feature::SignatureAuth, Feature::DatumContinuity],
+            // Those rewrite datum fields on every action, which RotateAdmin must preserve
+            conflicts_with: vec![
+                Feature::MonotonicCounter,
+                Feature::SwapFee,
+                Feature::PeriodicLimit,
+            ],
+            purpose: Some("spend"),
+        },
+
+        Feature::ExactMintAmount => FeatureSpec {
+            imports: vec![
+                "use cardano/assets",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("mint_asset_name", "ByteArray"), ("max_supply", "Int")],
+            preamble_code: "",
+            per_action_code: "    // Entire fixed supply must be minted at once\n    expect assets.quantity_of(self.mint, policy_id, mint_asset_name) == max_supply",
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: Some("mint"),
+        },
+
+        Feature::Cip25Metadata => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+                "use aiken/collection/pairs",
+                "use cardano/address",
+                "use cardano/transaction.{InlineDatum, Output}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("mint_asset_name", "ByteArray")],
+            preamble_code: "",
+            // Plutus V3 has no access to auxiliary data, so the metadata is carried on-chain
+            per_action_code: concat!(
+                "    // CIP-25: the first output's inline datum is the {721: {policy: {asset: metadata}}} map\n",
+                "    expect Some(metadata_output) = list.head(self.outputs)\n",
+                "    expect InlineDatum(raw_metadata) = metadata_output.datum\n",
+                "    expect cip25: Pairs<Int, Pairs<ByteArray, Pairs<ByteArray, Data>>> = raw_metadata\n",
+                "    expect Some(policy_metadata) = pairs.get_first(cip25, 721)\n",
+                "    expect Some(asset_metadata) = pairs.get_first(policy_metadata, policy_id)\n",
+                "    expect pairs.has_key(asset_metadata, mint_asset_name)",
+            ),
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: Some("mint"),
+        },
+
+        Feature::UniqueMint => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+                "use cardano/address",
+                "use cardano/transaction.{Input, NoDatum, Output, OutputReference}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("seed_utxo", "OutputReference")],
+            preamble_code: "",
+            per_action_code: "    // Seed UTxO must be consumed (it can only be spent once)\n    expect list.any(self.inputs, fn(i) { i.output_reference == seed_utxo })",
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: Some("mint"),
+        },
+
+        Feature::MinSigners => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("min_signers", "Int")],
+            preamble_code: "",
+            per_action_code: "    // At least min_signers keys must sign (any keys)\n    expect list.length(self.extra_signatories) >= min_signers",
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: None,
+        },
+
+        Feature::BoundedValidity => FeatureSpec {
+            imports: vec![
+                "use aiken/interval",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("max_ttl", "Int")],
+            preamble_code: "",
+            per_action_code: concat!(
+                "    // Validity range must be finite on both ends and at most max_ttl wide\n",
+                "    expect interval.Finite(valid_from) = self.validity_range.lower_bound.bound_type\n",
+                "    expect interval.Finite(valid_until) = self.validity_range.upper_bound.bound_type\n",
+                "    expect valid_until - valid_from <= max_ttl",
+            ),
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: None,
+        },
+
+        Feature::SignatureThresholdFromDatum => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+            ],
+            required_datum_fields: vec![("signers", "List<ByteArray>"), ("threshold", "Int")],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: "",
+            // Duplicate keys in the datum count once
+            per_action_code: concat!(
+                "    // At least `threshold` of the datum signers must sign\n",
+                "    expect\n",
+                "      list.count(\n",
+                "        list.unique(datum.signers),\n",
+                "        fn(signer) { list.has(self.extra_signatories, signer) },\n",
+                "      ) >= datum.threshold",
+            ),
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::InlineDatumOnly => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+                "use cardano/address.{Address, Script}",
+                "use cardano/transaction.{DatumHash, InlineDatum, Output}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            // Spend only; mint compares against Script(policy_id) (see compose::contribution)
+            preamble_code: concat!(
+                "    // This script's payment credential\n",
+                "    expect Some(own_input) =\n",
+                "      list.find(self.inputs, fn(i) { i.output_reference == own_ref })\n",
+                "    let script_credential = own_input.output.address.payment_credential",
+            ),
+            per_action_code: concat!(
+                "    // Outputs to this script must carry an inline datum (no datum hashes)\n",
+                "    expect\n",
+                "      list.all(\n",
+                "        self.outputs,\n",
+                "        fn(o) {\n",
+                "          when o.datum is {\n",
+                "            InlineDatum(_) -> True\n",
+                "            _ -> o.address.payment_credential != script_credential\n",
+                "          }\n",
+                "        },\n",
+                "      )",
+            ),
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: None,
+        },
+
+        Feature::AssetWhitelist => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+                "use cardano/assets",
+                "use cardano/assets.{without_lovelace}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("whitelist_policy", "ByteArray"), ("whitelist_asset", "ByteArray")],
+            preamble_code: "",
+            per_action_code: concat!(
+                "    // Continuing output holds only lovelace and the whitelisted token (no smuggled assets)\n",
+                "    expect\n",
+                "      list.all(\n",
+                "        assets.flatten(without_lovelace(cont_output.value)),\n",
+                "        fn(asset) {\n",
+                "          let (policy, name, _) = asset\n",
+                "          policy == whitelist_policy && name == whitelist_asset\n",
+                "        },\n",
+                "      )",
+            ),
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::PeriodicLimit => FeatureSpec {
+            imports: vec![
+                "use aiken/interval",
+                "use cardano/assets.{lovelace_of}",
+            ],
+            required_datum_fields: vec![("period_start", "Int"), ("period_spent", "Int")],
+            required_redeemer_fields: vec![],
+            validator_params: vec![("period", "Int"), ("limit", "Int")],
+            // `now` is the validity range's lower bound, so it can't be later than the real time
+            preamble_code: concat!(
+                "    // Spending limit: net lovelace leaving the script counts against the current period\n",
+                "    expect interval.Finite(now) = self.validity_range.lower_bound.bound_type\n",
+                "    let withdrawn =\n",
+                "      lovelace_of(own_input.output.value) - lovelace_of(cont_output.value)\n",
+                "    let new_period = now >= datum.period_start + period\n",
+                "    let next_period_start =\n",
+                "      if new_period {\n",
+                "        now\n",
+                "      } else {\n",
+                "        datum.period_start\n",
+                "      }\n",
+                "    let next_period_spent =\n",
+                "      if new_period {\n",
+                "        withdrawn\n",
+                "      } else {\n",
+                "        datum.period_spent + withdrawn\n",
+                "      }",
+            ),
+            per_action_code: concat!(
+                "    // Period total must stay under the limit and be carried into the continuing datum\n",
+                "    expect next_period_spent <= limit\n",
+                "    expect out_datum.period_start == next_period_start\n",
+                "    expect out_datum.period_spent == next_period_spent",
+            ),
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![Feature::ValuePreservation],
+            purpose: Some("spend"),
+        },
+
+        Feature::TokenGated => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+                "use cardano/address",
+                "use cardano/assets",
+                "use cardano/transaction.{Input, NoDatum, Output}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![
+                ("gov_policy", "ByteArray"),
+                ("gov_asset", "ByteArray"),
+                ("min_tokens", "Int"),
+            ],
+            preamble_code: "",
+            // Reference inputs count too, so holders can prove ownership without spending
+            per_action_code: concat!(
+                "    // Actor must hold at least min_tokens governance tokens (spent or referenced)\n",
+                "    expect\n",
+                "      list.foldl(\n",
+                "        list.concat(self.inputs, self.reference_inputs),\n",
+                "        0,\n",
+                "        fn(i, total) {\n",
+                "          total + assets.quantity_of(i.output.value, gov_policy, gov_asset)\n",
+                "        },\n",
+                "      ) >= min_tokens",
+            ),
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::ProportionalPayout => FeatureSpec {
+            imports: vec![
+                "use cardano/assets.{lovelace_of}",
+            ],
+            required_datum_fields: vec![("total_pool", "Int"), ("total_shares", "Int")],
+            required_redeemer_fields: vec![("claimant_share", "Int")],
+            validator_params: vec![],
+            // Integer division floors, so the dust stays in the pool for the remaining shares
+            preamble_code: concat!(
+                "    // Proportional payout: a Claim takes at most its floored share of the pool and\n",
+                "    // burns the claimed shares; other actions keep the pool accounting\n",
+                "    expect\n",
+                "      when redeemer is {\n",
+                "        Claim { claimant_share } -> {\n",
+                "          let entitled = datum.total_pool * claimant_share / datum.total_shares\n",
+                "          and {\n",
+                "            claimant_share > 0,\n",
+                "            claimant_share <= datum.total_shares,\n",
+                "            lovelace_of(cont_output.value) >= lovelace_of(own_input.output.value) - entitled,\n",
+                "            out_datum.total_pool == datum.total_pool - entitled,\n",
+                "            out_datum.total_shares == datum.total_shares - claimant_share,\n",
+                "          }\n",
+                "        }\n",
+                "        _ ->\n",
+                "          out_datum.total_pool == datum.total_pool && out_datum.total_shares == datum.total_shares\n",
+                "      }",
+            ),
+            per_action_code: "",
+            depends_on: vec![Feature::DatumContinuity],
+            // ValuePreservation forbids the ADA decrease a claim pays out
+            conflicts_with: vec![Feature::ValuePreservation],
+            purpose: Some("spend"),
+        },
+
+        Feature::DepositOnly => FeatureSpec {
+            imports: vec![
+                "use cardano/assets.{lovelace_of, without_lovelace}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: concat!(
+                "    // Deposit only: a Deposit may add lovelace but remove nothing and leave the other\n",
+                "    // assets as they were; other actions are not restricted here\n",
+                "    expect\n",
+                "      when redeemer is {\n",
+                "        Deposit ->\n",
+                "          and {\n",
+                "            lovelace_of(cont_output.value) >= lovelace_of(own_input.output.value),\n",
+                "            without_lovelace(cont_output.value) == without_lovelace(own_input.output.value),\n",
+                "          }\n",
+                "        _ -> True\n",
+                "      }",
+            ),
+            per_action_code: "",
+            depends_on: vec![Feature::DatumContinuity],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+
+        Feature::RefundableDeposit => FeatureSpec {
+            imports: vec![
+                "use aiken/collection/list",
+                "use aiken/interval",
+                "use cardano/address.{Address, Script, VerificationKey}",
+                "use cardano/assets",
+                "use cardano/assets.{lovelace_of}",
+                "use cardano/transaction.{InlineDatum, Input, NoDatum, Output}",
+            ],
+            required_datum_fields: vec![("depositor", "ByteArray"), ("claim_deadline", "Int")],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: concat!(
+                "    // Refundable deposit: before claim_deadline the depositor may take the whole\n",
+                "    // deposit back; other actions are not restricted here\n",
+                "    expect\n",
+                "      when redeemer is {\n",
+                "        Refund -> {\n",
+                "          expect Some(deposit_input) =\n",
+                "            list.find(self.inputs, fn(i) { i.output_reference == own_ref })\n",
+                "          and {\n",
+                "            list.has(self.extra_signatories, datum.depositor),\n",
+                "            interval.is_entirely_before(self.validity_range, datum.claim_deadline),\n",
+                "            list.any(\n",
+                "              self.outputs,\n",
+                "              fn(o) {\n",
+                "                and {\n",
+                "                  o.address.payment_credential == VerificationKey(datum.depositor),\n",
+                "                  lovelace_of(o.value) >= lovelace_of(deposit_input.output.value),\n",
+                "                }\n",
+                "              },\n",
+                "            ),\n",
+                "          }\n",
+                "        }\n",
+                "        _ -> True\n",
+                "      }",
+            ),
+            per_action_code: "",
+            depends_on: vec![],
+            // A refund pays the deposit out instead of continuing the position
+            conflicts_with: vec![Feature::DatumContinuity],
+            purpose: Some("spend"),
+        },
+
+        Feature::NoReferenceInputs => FeatureSpec {
+            // The stray reference input the generated negative test injects needs these
+            imports: vec![
+                "use cardano/address",
+                "use cardano/assets",
+                "use cardano/transaction.{Input, NoDatum, Output, OutputReference}",
+            ],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: "",
+            per_action_code: "    // No reference input may influence this transaction\n    expect self.reference_inputs == []",
+            depends_on: vec![],
+            conflicts_with: vec![Feature::TokenGated, Feature::RequireReferenceScript],
+            purpose: None,
+        },
+
+        Feature::NoMinting => FeatureSpec {
+            imports: vec!["use cardano/assets"],
+            required_datum_fields: vec![],
+            required_redeemer_fields: vec![],
+            validator_params: vec![],
+            preamble_code: "",
+            per_action_code: "    // Nothing may be minted or burned alongside this spend\n    expect self.mint == assets.zero",
+            depends_on: vec![],
+            conflicts_with: vec![],
+            purpose: Some("spend"),
+        },
+    }
+}
+
+/// How one feature relates to another when both are selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    Compatible,
+    /// The first feature pulls in the second
+    DependsOn,
+    /// The second feature pulls in the first
+    RequiredBy,
+    Conflicts,
+}
+
+impl Compatibility {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Compatibility::Compatible => "compatible",
+            Compatibility::DependsOn => "depends_on",
+            Compatibility::RequiredBy => "required_by",
+            Compatibility::Conflicts => "conflicts",
+        }
+    }
+}
+
+/// Relation of `a` to `b` from the direct `depends_on`/`conflicts_with` of their specs
+/// (a conflict declared on either side wins)
+pub fn compatibility(a: Feature, b: Feature) -> Compatibility {
+    let (spec_a, spec_b) = (feature_spec(a), feature_spec(b));
+    if spec_a.conflicts_with.contains(&b) || spec_b.conflicts_with.contains(&a) {
+        Compatibility::Conflicts
+    } else if spec_a.depends_on.contains(&b) {
+        Compatibility::DependsOn
+    } else if spec_b.depends_on.contains(&a) {
+        Compatibility::RequiredBy
+    } else {
+        Compatibility::Compatible
     }
 }
 
+/// `{feature: {other: relation}}` for every pair of distinct features
+pub fn feature_matrix() -> serde_json::Value {
+    let matrix: serde_json::Map<String, serde_json::Value> = Feature::all()
+        .iter()
+        .map(|a| {
+            let row: serde_json::Map<String, serde_json::Value> = Feature::all()
+                .iter()
+                .filter(|b| *b != a)
+                .map(|b| (b.name().to_string(), compatibility(*a, *b).name().into()))
+                .collect();
+            (a.name().to_string(), row.into())
+        })
+        .collect();
+    matrix.into()
+}
+
 /// Parse a list of feature strings from CLI into Feature enums
 pub fn parse_features(input: &[String]) -> Result<Vec<Feature>> {
     let mut features = Vec::new();
@@ -268,6 +1136,33 @@ pub fn parse_features(input: &[String]) -> Result<Vec<Feature>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_feature_matrix_relations() {
+        let matrix = feature_matrix();
+        assert_eq!(
+            matrix["value-preservation"]["datum-continuity"],
+            "depends_on"
+        );
+        assert_eq!(
+            matrix["datum-continuity"]["value-preservation"],
+            "required_by"
+        );
+        assert_eq!(matrix["signature-auth"]["timelock"], "compatible");
+        assert!(matrix["timelock"].get("timelock").is_none());
+
+        // Every declared conflict shows up as a conflict in both directions
+        for f in Feature::all() {
+            for other in feature_spec(*f).conflicts_with {
+                assert_eq!(matrix[f.name()][other.name()], "conflicts");
+                assert_eq!(matrix[other.name()][f.name()], "conflicts");
+            }
+        }
+        assert_eq!(
+            matrix["reference-safety"]["require-reference-script"],
+            "conflicts"
+        );
+    }
+
     #[test]
     fn test_parse_feature_aliases() {
         assert_eq!("sig".parse::<Feature>(), Ok(Feature::SignatureAuth));
@@ -278,6 +1173,12 @@ mod tests {
         );
         assert_eq!("timelock".parse::<Feature>(), Ok(Feature::TimeLock));
         assert_eq!("time-lock".parse::<Feature>(), Ok(Feature::TimeLock));
+        assert_eq!("expiry".parse::<Feature>(), Ok(Feature::Expiry));
+        assert_eq!("window".parse::<Feature>(), Ok(Feature::DeadlineWindow));
+        assert_eq!(
+            "deadline-window".parse::<Feature>(),
+            Ok(Feature::DeadlineWindow)
+        );
         assert_eq!(
             "datum-continuity".parse::<Feature>(),
             Ok(Feature::DatumContinuity)
@@ -287,10 +1188,76 @@ mod tests {
         assert_eq!("burn".parse::<Feature>(), Ok(Feature::BurnVerification));
         assert_eq!("bounded".parse::<Feature>(), Ok(Feature::BoundedOperations));
         assert_eq!("floor".parse::<Feature>(), Ok(Feature::BoundedOperations));
+        assert_eq!("change".parse::<Feature>(), Ok(Feature::ChangeToSelf));
+        assert_eq!(
+            "change-to-self".parse::<Feature>(),
+            Ok(Feature::ChangeToSelf)
+        );
+        assert_eq!(
+            "exact-mint-amount".parse::<Feature>(),
+            Ok(Feature::ExactMintAmount)
+        );
+        assert_eq!(
+            "max-supply".parse::<Feature>(),
+            Ok(Feature::ExactMintAmount)
+        );
+        assert_eq!("one-shot".parse::<Feature>(), Ok(Feature::UniqueMint));
+        assert_eq!("nonce".parse::<Feature>(), Ok(Feature::MonotonicCounter));
+        assert_eq!(
+            "spending-limit".parse::<Feature>(),
+            Ok(Feature::PeriodicLimit)
+        );
+        assert_eq!(
+            "periodic_limit".parse::<Feature>(),
+            Ok(Feature::PeriodicLimit)
+        );
+        assert_eq!(
+            "cont-index".parse::<Feature>(),
+            Ok(Feature::ContinuationAtIndex)
+        );
         assert_eq!(
             "ref-safety".parse::<Feature>(),
             Ok(Feature::ReferenceSafety)
         );
+        assert_eq!("circuit-breaker".parse::<Feature>(), Ok(Feature::Pausable));
+        assert_eq!("pool-fee".parse::<Feature>(), Ok(Feature::SwapFee));
+        assert_eq!("cip25".parse::<Feature>(), Ok(Feature::Cip25Metadata));
+        assert_eq!(
+            "rotate-admin".parse::<Feature>(),
+            Ok(Feature::AdminRotation)
+        );
+        assert_eq!(
+            "datum-multisig".parse::<Feature>(),
+            Ok(Feature::SignatureThresholdFromDatum)
+        );
+        assert_eq!(
+            "no-datum-hash".parse::<Feature>(),
+            Ok(Feature::InlineDatumOnly)
+        );
+        assert_eq!(
+            "ref-script".parse::<Feature>(),
+            Ok(Feature::RequireReferenceScript)
+        );
+        assert_eq!("whitelist".parse::<Feature>(), Ok(Feature::AssetWhitelist));
+        assert_eq!("gov-token".parse::<Feature>(), Ok(Feature::TokenGated));
+        assert_eq!("token_gated".parse::<Feature>(), Ok(Feature::TokenGated));
+        assert_eq!("keep-nft".parse::<Feature>(), Ok(Feature::NftPreservation));
+        assert_eq!(
+            "pro-rata".parse::<Feature>(),
+            Ok(Feature::ProportionalPayout)
+        );
+        assert_eq!("min-signers".parse::<Feature>(), Ok(Feature::MinSigners));
+        assert_eq!(
+            "max-validity-range".parse::<Feature>(),
+            Ok(Feature::BoundedValidity)
+        );
+        assert_eq!("add-only".parse::<Feature>(), Ok(Feature::DepositOnly));
+        assert_eq!("refund".parse::<Feature>(), Ok(Feature::RefundableDeposit));
+        assert_eq!(
+            "no-ref-inputs".parse::<Feature>(),
+            Ok(Feature::NoReferenceInputs)
+        );
+        assert_eq!("no-mint".parse::<Feature>(), Ok(Feature::NoMinting));
         assert!("unknown".parse::<Feature>().is_err());
     }
 
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use super::Feature;
+use super::compose::find_deadline_field;
+use super::{feature_spec, Feature};
 use crate::error::{KaidoError, Result};
 
 /// A field in a custom datum type
@@ -10,6 +11,58 @@ pub struct DatumField {
     pub aiken_type: String,
 }
 
+/// A `<tx hash>#<index>` UTxO reference, e.g. the seed `unique-mint` consumes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedUtxo {
+    /// 64 lowercase hex chars
+    pub transaction_id: String,
+    pub output_index: u64,
+}
+
+/// Parse `TXHASH#INDEX`: a 64-hex-char transaction hash and a decimal output index
+pub fn parse_seed_utxo(input: &str) -> Result<SeedUtxo> {
+    let invalid = |reason: &str| {
+        KaidoError::InvalidOption(format!(
+            "Invalid seed UTxO '{}': {} (expected <64 hex tx hash>#<index>)",
+            input, reason
+        ))
+    };
+    let (hash, index) = input
+        .trim()
+        .split_once('#')
+        .ok_or_else(|| invalid("missing '#'"))?;
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid("transaction hash must be 64 hex characters"));
+    }
+    if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid("output index must be a number"));
+    }
+    let output_index = index
+        .parse()
+        .map_err(|_| invalid("output index is too large"))?;
+    Ok(SeedUtxo {
+        transaction_id: hash.to_ascii_lowercase(),
+        output_index,
+    })
+}
+
+/// Resolve `--seed-utxo` for the generated one-shot mint test. Errors if it's given without
+/// `unique-mint`, which is the only feature that reads it. None falls back to a placeholder.
+pub fn resolve_seed_utxo(
+    features: &[Feature],
+    seed_utxo: Option<&str>,
+) -> Result<Option<SeedUtxo>> {
+    let Some(seed) = seed_utxo else {
+        return Ok(None);
+    };
+    if !features.contains(&Feature::UniqueMint) {
+        return Err(KaidoError::InvalidOption(
+            "seed_utxo requires feature 'unique-mint'".to_string(),
+        ));
+    }
+    parse_seed_utxo(seed).map(Some)
+}
+
 /// A redeemer action (variant in the redeemer enum)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RedeemerAction {
@@ -18,7 +71,126 @@ pub struct RedeemerAction {
     pub fields: Vec<(String, String)>,
 }
 
-const VALID_TYPES: &[&str] = &["ByteArray", "Int", "Bool", "List<ByteArray>", "List<Int>"];
+/// Redeemer action the custom mint template gives the burn-only branch
+pub const BURN_ACTION: &str = "Burn";
+
+/// Redeemer action `pausable` lets through while the datum is paused
+pub const UNPAUSE_ACTION: &str = "Unpause";
+
+/// Bool datum field `pausable` reads
+pub const PAUSED_FIELD: &str = "paused";
+
+/// Redeemer action `admin-rotation` lets replace the datum admin
+pub const ROTATE_ADMIN_ACTION: &str = "RotateAdmin";
+
+/// ByteArray datum field `admin-rotation` keeps the admin key in
+pub const ADMIN_FIELD: &str = "admin";
+
+/// `List<ByteArray>` datum field `signature-threshold` reads the signer set from
+pub const SIGNERS_FIELD: &str = "signers";
+
+/// Int datum field `signature-threshold` reads the required signature count from
+pub const THRESHOLD_FIELD: &str = "threshold";
+
+/// Int datum fields `deadline-window` reads the window bounds from
+pub const WINDOW_START_FIELD: &str = "start";
+pub const WINDOW_END_FIELD: &str = "end";
+
+/// Type names the custom template emits for the datum and redeemer
+pub const DATUM_TYPE: &str = "CustomDatum";
+pub const REDEEMER_TYPE: &str = "CustomRedeemer";
+
+/// Int datum fields `periodic-limit` tracks the current period and its spent total in
+pub const PERIOD_START_FIELD: &str = "period_start";
+pub const PERIOD_SPENT_FIELD: &str = "period_spent";
+
+/// Redeemer action `proportional-payout` pays out from, with its `claimant_share: Int` field
+pub const CLAIM_ACTION: &str = "Claim";
+
+/// Int datum fields `proportional-payout` computes the pro-rata share from
+pub const TOTAL_POOL_FIELD: &str = "total_pool";
+pub const TOTAL_SHARES_FIELD: &str = "total_shares";
+
+/// Field-less redeemer action `deposit-only` restricts to adding funds
+pub const DEPOSIT_ACTION: &str = "Deposit";
+
+/// Field-less redeemer action `refundable-deposit` returns the deposit through
+pub const REFUND_ACTION: &str = "Refund";
+
+/// ByteArray and Int datum fields `refundable-deposit` reads the depositor key and the refund
+/// cut-off from
+pub const DEPOSITOR_FIELD: &str = "depositor";
+pub const CLAIM_DEADLINE_FIELD: &str = "claim_deadline";
+
+/// Field types accepted in datum and redeemer definitions (plus tuples of these).
+/// `Data` is opaque (e.g. CIP-68 `extra`) and never satisfies a feature's Int-field requirement.
+pub const VALID_TYPES: &[&str] = &[
+    "ByteArray",
+    "Int",
+    "Bool",
+    "Data",
+    "List<ByteArray>",
+    "List<Int>",
+];
+
+/// Whether a type is supported: one of VALID_TYPES, a tuple of them, e.g. `(ByteArray, Int)`,
+/// or a list of such tuples, e.g. `List<(ByteArray, Int)>`.
+fn is_supported_type(ty: &str) -> bool {
+    if VALID_TYPES.contains(&ty) {
+        return true;
+    }
+    if let Some(inner) = ty.strip_prefix("List<").and_then(|t| t.strip_suffix('>')) {
+        return tuple_elements(inner).is_some() && is_supported_type(inner);
+    }
+    match tuple_elements(ty) {
+        Some(elements) => elements.len() >= 2 && elements.iter().all(|e| is_supported_type(e)),
+        None => false,
+    }
+}
+
+/// Element types of a tuple type like `(ByteArray, Int)`, or None if not a tuple.
+pub fn tuple_elements(ty: &str) -> Option<Vec<String>> {
+    let inner = ty.trim().strip_prefix('(')?.strip_suffix(')')?;
+    Some(split_top_level(inner))
+}
+
+fn supported_types_hint() -> String {
+    format!(
+        "{}, tuples of these (e.g., (ByteArray, Int)), or lists of tuples",
+        VALID_TYPES.join(", ")
+    )
+}
+
+/// Split on commas that are not nested inside `()` or `<>`.
+/// e.g. "pair:(ByteArray, Int), amount:Int" => ["pair:(ByteArray, Int)", "amount:Int"]
+pub fn split_top_level(input: &str) -> Vec<String> {
+    let mut depth: i32 = 0;
+    let mut current = String::new();
+    let mut parts = Vec::new();
+
+    for ch in input.chars() {
+        match ch {
+            '(' | '<' => {
+                depth += 1;
+                current.push(ch);
+            }
+            ')' | '>' => {
+                depth -= 1;
+                current.push(ch);
+            }
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(ch),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts
+}
 
 /// Parse datum field definitions from a CLI string.
 /// Format: "owner:ByteArray,amount:Int,deadline:Int"
@@ -29,8 +201,8 @@ pub fn parse_datum_fields(input: &str) -> Result<Vec<DatumField>> {
     }
 
     let mut fields = Vec::new();
-    for part in input.split(',') {
-        let part = part.trim();
+    for part in split_top_level(input) {
+        let part = part.as_str();
         let (name, ty) = part.split_once(':').ok_or_else(|| {
             KaidoError::InvalidOption(format!(
                 "Invalid datum field '{}'. Expected format: name:Type (e.g., owner:ByteArray)",
@@ -50,11 +222,11 @@ pub fn parse_datum_fields(input: &str) -> Result<Vec<DatumField>> {
         }
 
         // Validate the type
-        if !VALID_TYPES.contains(&ty.as_str()) {
+        if !is_supported_type(&ty) {
             return Err(KaidoError::InvalidOption(format!(
                 "Unsupported type '{}'. Supported: {}",
                 ty,
-                VALID_TYPES.join(", ")
+                supported_types_hint()
             )));
         }
 
@@ -79,39 +251,22 @@ pub fn parse_redeemer_actions(input: &str) -> Result<Vec<RedeemerAction>> {
 
     let mut actions = Vec::new();
 
-    // Split on commas not inside parentheses
-    let mut depth = 0;
-    let mut current = String::new();
-    let mut parts = Vec::new();
-
-    for ch in input.chars() {
-        match ch {
-            '(' => {
-                depth += 1;
-                current.push(ch);
-            }
-            ')' => {
-                depth -= 1;
-                current.push(ch);
-            }
-            ',' if depth == 0 => {
-                parts.push(current.trim().to_string());
-                current.clear();
-            }
-            _ => current.push(ch),
-        }
-    }
-    if !current.trim().is_empty() {
-        parts.push(current.trim().to_string());
-    }
-
-    for part in parts {
+    // Split on commas not inside parentheses or angle brackets
+    for part in split_top_level(input) {
         let (name, fields) = if let Some(paren_start) = part.find('(') {
             let name = part[..paren_start].trim().to_string();
-            let fields_str = part[paren_start + 1..part.len() - 1].trim();
+            let fields_str = part[paren_start + 1..]
+                .strip_suffix(')')
+                .ok_or_else(|| {
+                    KaidoError::InvalidOption(format!(
+                        "Redeemer action '{}' is missing a closing ')'",
+                        part.trim()
+                    ))
+                })?
+                .trim();
             let mut fields = Vec::new();
-            for field_part in fields_str.split(',') {
-                let field_part = field_part.trim();
+            for field_part in split_top_level(fields_str) {
+                let field_part = field_part.as_str();
                 let (fname, ftype) = field_part.split_once(':').ok_or_else(|| {
                     KaidoError::InvalidOption(format!(
                         "Invalid field '{}' in redeemer action '{}'. Expected name:Type",
@@ -119,11 +274,11 @@ pub fn parse_redeemer_actions(input: &str) -> Result<Vec<RedeemerAction>> {
                     ))
                 })?;
                 let ftype = ftype.trim().to_string();
-                if !VALID_TYPES.contains(&ftype.as_str()) {
+                if !is_supported_type(&ftype) {
                     return Err(KaidoError::InvalidOption(format!(
                         "Unsupported type '{}' in redeemer field. Supported: {}",
                         ftype,
-                        VALID_TYPES.join(", ")
+                        supported_types_hint()
                     )));
                 }
                 fields.push((fname.trim().to_string(), ftype));
@@ -147,12 +302,152 @@ pub fn parse_redeemer_actions(input: &str) -> Result<Vec<RedeemerAction>> {
     Ok(actions)
 }
 
+/// Resolve the Int datum field `monotonic-counter` advances: the explicit override if given,
+/// otherwise the first field named nonce/sequence/version. Returns None when the feature is off.
+pub fn resolve_counter_field(
+    features: &[Feature],
+    datum_fields: &[DatumField],
+    counter_field: Option<&str>,
+) -> Result<Option<String>> {
+    if !features.contains(&Feature::MonotonicCounter) {
+        return Ok(None);
+    }
+
+    let is_int_field = |name: &str| {
+        datum_fields
+            .iter()
+            .any(|f| f.name == name && f.aiken_type == "Int")
+    };
+
+    if let Some(name) = counter_field {
+        if !is_int_field(name) {
+            return Err(KaidoError::InvalidOption(format!(
+                "Counter field '{}' must be an Int field in datum",
+                name
+            )));
+        }
+        return Ok(Some(name.to_string()));
+    }
+
+    ["nonce", "sequence", "version"]
+        .iter()
+        .find(|name| is_int_field(name))
+        .map(|name| Some(name.to_string()))
+        .ok_or_else(|| {
+            KaidoError::InvalidOption(
+                "Feature 'monotonic-counter' requires an Int datum field named nonce, sequence, or version (or --counter-field)"
+                    .to_string(),
+            )
+        })
+}
+
+/// Resolve the Int datum field `expiry` binds: the explicit override if given, otherwise a
+/// field named `expiry`. Errors if `timelock` would bind the same field. None when the feature is off.
+pub fn resolve_expiry_field(
+    features: &[Feature],
+    datum_fields: &[DatumField],
+    expiry_field: Option<&str>,
+) -> Result<Option<String>> {
+    if !features.contains(&Feature::Expiry) {
+        return Ok(None);
+    }
+
+    let name = expiry_field.unwrap_or("expiry");
+    if !datum_fields
+        .iter()
+        .any(|f| f.name == name && f.aiken_type == "Int")
+    {
+        return Err(KaidoError::InvalidOption(match expiry_field {
+            Some(_) => format!("Expiry field '{}' must be an Int field in datum", name),
+            None => "Feature 'expiry' requires an Int datum field named expiry (or --expiry-field)"
+                .to_string(),
+        }));
+    }
+
+    if features.contains(&Feature::TimeLock)
+        && find_deadline_field(datum_fields).as_deref() == Some(name)
+    {
+        return Err(KaidoError::InvalidOption(format!(
+            "Features 'timelock' and 'expiry' both bind datum field '{}'; add a separate deadline field",
+            name
+        )));
+    }
+
+    Ok(Some(name.to_string()))
+}
+
 /// Validate that selected features are compatible with the provided datum fields.
 pub fn validate_features_against_types(
     features: &[Feature],
     datum_fields: &[DatumField],
+    redeemer_actions: &[RedeemerAction],
     purpose: &str,
 ) -> Result<()> {
+    // An action constructor named like a generated type collides with it in the types module
+    if let Some(action) = redeemer_actions
+        .iter()
+        .find(|a| a.name == DATUM_TYPE || a.name == REDEEMER_TYPE)
+    {
+        return Err(KaidoError::InvalidOption(format!(
+            "Redeemer action '{}' collides with the generated type of the same name",
+            action.name
+        )));
+    }
+
+    // Datum fields must not reuse a validator parameter the features inject (e.g. admin_pkh);
+    // compared case-insensitively so `Admin_pkh` is rejected as well
+    for f in features {
+        for (param, _) in feature_spec(*f).validator_params {
+            if let Some(field) = datum_fields
+                .iter()
+                .find(|d| d.name.eq_ignore_ascii_case(param))
+            {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Datum field '{}' collides with the '{}' validator parameter of feature '{}'",
+                    field.name,
+                    param,
+                    f.name()
+                )));
+            }
+        }
+    }
+
+    // Fields a feature reads by name, e.g. pausable's `paused: Bool`
+    for f in features {
+        for (name, ty) in feature_spec(*f).required_datum_fields {
+            if !datum_fields
+                .iter()
+                .any(|d| d.name == name && d.aiken_type == ty)
+            {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Feature '{}' requires datum field '{}:{}'",
+                    f.name(),
+                    name,
+                    ty
+                )));
+            }
+        }
+    }
+
+    // Redeemer fields a feature's check binds by name, e.g. admin-rotation's `new_admin`;
+    // without one the generated check would reference a variable no branch introduces
+    for f in features {
+        for (name, ty) in feature_spec(*f).required_redeemer_fields {
+            if !redeemer_actions
+                .iter()
+                .flat_map(|a| &a.fields)
+                .any(|(field, field_ty)| field == name && field_ty == ty)
+            {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Feature '{}' requires a redeemer field '{}:{}' in at least one action",
+                    f.name(),
+                    name,
+                    ty
+                )));
+            }
+        }
+    }
+
     // TimeLock needs an Int field for the deadline
     if features.contains(&Feature::TimeLock) {
         let has_int_field = datum_fields.iter().any(|f| f.aiken_type == "Int");
@@ -178,6 +473,120 @@ pub fn validate_features_against_types(
         ));
     }
 
+    // Pausable lets only a field-less Unpause through while paused; another action must exist to pause
+    if features.contains(&Feature::Pausable) {
+        match redeemer_actions.iter().find(|a| a.name == UNPAUSE_ACTION) {
+            Some(action) if action.fields.is_empty() => {}
+            Some(_) => {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Feature 'pausable' requires the '{}' action to have no fields",
+                    UNPAUSE_ACTION
+                )));
+            }
+            None => {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Feature 'pausable' requires a redeemer action named '{}' (e.g., Update,Unpause)",
+                    UNPAUSE_ACTION
+                )));
+            }
+        }
+        if redeemer_actions.len() < 2 {
+            return Err(KaidoError::InvalidOption(format!(
+                "Feature 'pausable' requires at least one action besides '{}'",
+                UNPAUSE_ACTION
+            )));
+        }
+    }
+
+    // AdminRotation destructures `RotateAdmin { new_admin }`; another action must use the admin
+    if features.contains(&Feature::AdminRotation) {
+        let rotate = redeemer_actions
+            .iter()
+            .find(|a| a.name == ROTATE_ADMIN_ACTION);
+        let expected = [("new_admin".to_string(), "ByteArray".to_string())];
+        if rotate.is_none_or(|a| a.fields != expected) {
+            return Err(KaidoError::InvalidOption(format!(
+                "Feature 'admin-rotation' requires a redeemer action '{}(new_admin:ByteArray)'",
+                ROTATE_ADMIN_ACTION
+            )));
+        }
+        if redeemer_actions.len() < 2 {
+            return Err(KaidoError::InvalidOption(format!(
+                "Feature 'admin-rotation' requires at least one action besides '{}'",
+                ROTATE_ADMIN_ACTION
+            )));
+        }
+    }
+
+    // ProportionalPayout destructures `Claim { claimant_share }` in a `when` that also needs
+    // another arm, for actions that must leave the pool accounting alone
+    if features.contains(&Feature::ProportionalPayout) {
+        let claim = redeemer_actions.iter().find(|a| a.name == CLAIM_ACTION);
+        let expected = [("claimant_share".to_string(), "Int".to_string())];
+        if claim.is_none_or(|a| a.fields != expected) {
+            return Err(KaidoError::InvalidOption(format!(
+                "Feature 'proportional-payout' requires a redeemer action '{}(claimant_share:Int)'",
+                CLAIM_ACTION
+            )));
+        }
+        if redeemer_actions.len() < 2 {
+            return Err(KaidoError::InvalidOption(format!(
+                "Feature 'proportional-payout' requires at least one action besides '{}'",
+                CLAIM_ACTION
+            )));
+        }
+    }
+
+    // DepositOnly matches a field-less `Deposit`; the `_` arm needs another action to be reachable
+    if features.contains(&Feature::DepositOnly) {
+        match redeemer_actions.iter().find(|a| a.name == DEPOSIT_ACTION) {
+            Some(action) if action.fields.is_empty() => {}
+            Some(_) => {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Feature 'deposit-only' requires the '{}' action to have no fields",
+                    DEPOSIT_ACTION
+                )));
+            }
+            None => {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Feature 'deposit-only' requires a redeemer action named '{}' (e.g., Deposit,Withdraw)",
+                    DEPOSIT_ACTION
+                )));
+            }
+        }
+        if redeemer_actions.len() < 2 {
+            return Err(KaidoError::InvalidOption(format!(
+                "Feature 'deposit-only' requires at least one action besides '{}'",
+                DEPOSIT_ACTION
+            )));
+        }
+    }
+
+    // RefundableDeposit matches a field-less `Refund`; the deposit must be claimable another way
+    if features.contains(&Feature::RefundableDeposit) {
+        match redeemer_actions.iter().find(|a| a.name == REFUND_ACTION) {
+            Some(action) if action.fields.is_empty() => {}
+            Some(_) => {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Feature 'refundable-deposit' requires the '{}' action to have no fields",
+                    REFUND_ACTION
+                )));
+            }
+            None => {
+                return Err(KaidoError::InvalidOption(format!(
+                    "Feature 'refundable-deposit' requires a redeemer action named '{}' (e.g., Claim,Refund)",
+                    REFUND_ACTION
+                )));
+            }
+        }
+        if redeemer_actions.len() < 2 {
+            return Err(KaidoError::InvalidOption(format!(
+                "Feature 'refundable-deposit' requires at least one action besides '{}'",
+                REFUND_ACTION
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -185,6 +594,39 @@ pub fn validate_features_against_types(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_seed_utxo() {
+        let hash = "AB".repeat(32);
+        assert_eq!(
+            parse_seed_utxo(&format!("{hash}#2")).unwrap(),
+            SeedUtxo {
+                transaction_id: "ab".repeat(32),
+                output_index: 2,
+            }
+        );
+
+        for bad in [
+            hash.clone(),
+            format!("{}#0", &hash[..62]),
+            format!("{}zz#0", &hash[..62]),
+            format!("{hash}#"),
+            format!("{hash}#-1"),
+            format!("{hash}#1x"),
+            format!("{hash}#99999999999999999999999"),
+        ] {
+            let err = parse_seed_utxo(&bad).unwrap_err();
+            assert!(
+                err.to_string().contains("Invalid seed UTxO"),
+                "{bad}: {err}"
+            );
+        }
+
+        assert!(resolve_seed_utxo(&[], None).unwrap().is_none());
+        let err =
+            resolve_seed_utxo(&[Feature::ExactMintAmount], Some(&format!("{hash}#0"))).unwrap_err();
+        assert!(err.to_string().contains("requires feature 'unique-mint'"));
+    }
+
     #[test]
     fn test_parse_datum_simple() {
         let fields = parse_datum_fields("owner:ByteArray,amount:Int").unwrap();
@@ -245,6 +687,101 @@ mod tests {
         assert_eq!(actions[1].fields[0].1, "Int");
     }
 
+    #[test]
+    fn test_split_top_level_respects_brackets() {
+        let parts = split_top_level("pair:(ByteArray, Int), xs:List<Int>, amount:Int");
+        assert_eq!(
+            parts,
+            vec!["pair:(ByteArray, Int)", "xs:List<Int>", "amount:Int"]
+        );
+    }
+
+    #[test]
+    fn test_parse_redeemer_tuple_and_scalar_fields() {
+        let actions =
+            parse_redeemer_actions("Claim,Withdraw(pair:(ByteArray, Int), amount:Int)").unwrap();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[1].name, "Withdraw");
+        assert_eq!(actions[1].fields.len(), 2);
+        assert_eq!(actions[1].fields[0].0, "pair");
+        assert_eq!(actions[1].fields[0].1, "(ByteArray, Int)");
+        assert_eq!(actions[1].fields[1].0, "amount");
+        assert_eq!(actions[1].fields[1].1, "Int");
+    }
+
+    #[test]
+    fn test_parse_redeemer_unclosed_fields_fails() {
+        for input in ["Claim(", "Claim(amount:Int", "Claim(amount:Int),Cancel("] {
+            let err = parse_redeemer_actions(input).unwrap_err();
+            assert!(
+                err.to_string().contains("missing a closing ')'"),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_redeemer_tuple_with_unsupported_element_fails() {
+        let err = parse_redeemer_actions("Withdraw(pair:(ByteArray, String))").unwrap_err();
+        assert!(err.to_string().contains("(ByteArray, String)"));
+    }
+
+    #[test]
+    fn test_parse_datum_list_of_tuples() {
+        let fields =
+            parse_datum_fields("admin:ByteArray,recipients:List<(ByteArray, Int)>,expiry:Int")
+                .unwrap();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[1].name, "recipients");
+        assert_eq!(fields[1].aiken_type, "List<(ByteArray, Int)>");
+        assert!(parse_datum_fields("xs:List<(ByteArray, String)>").is_err());
+        assert!(parse_datum_fields("xs:List<Bool>").is_err());
+    }
+
+    #[test]
+    fn test_resolve_counter_field() {
+        let fields = parse_datum_fields("owner:ByteArray,sequence:Int,round:Int").unwrap();
+        let features = vec![Feature::DatumContinuity, Feature::MonotonicCounter];
+
+        assert_eq!(resolve_counter_field(&[], &fields, None).unwrap(), None);
+        assert_eq!(
+            resolve_counter_field(&features, &fields, None).unwrap(),
+            Some("sequence".to_string())
+        );
+        assert_eq!(
+            resolve_counter_field(&features, &fields, Some("round")).unwrap(),
+            Some("round".to_string())
+        );
+        assert!(resolve_counter_field(&features, &fields, Some("owner")).is_err());
+
+        let no_counter = parse_datum_fields("owner:ByteArray,amount:Int").unwrap();
+        assert!(resolve_counter_field(&features, &no_counter, None).is_err());
+    }
+
+    #[test]
+    fn test_resolve_expiry_field() {
+        let fields = parse_datum_fields("owner:ByteArray,deadline:Int,expiry:Int").unwrap();
+
+        assert_eq!(resolve_expiry_field(&[], &fields, None).unwrap(), None);
+        assert_eq!(
+            resolve_expiry_field(&[Feature::Expiry], &fields, None).unwrap(),
+            Some("expiry".to_string())
+        );
+        // TimeLock binds `deadline`, so the two features don't collide
+        let both = vec![Feature::TimeLock, Feature::Expiry];
+        assert_eq!(
+            resolve_expiry_field(&both, &fields, None).unwrap(),
+            Some("expiry".to_string())
+        );
+        let err = resolve_expiry_field(&both, &fields, Some("deadline")).unwrap_err();
+        assert!(err.to_string().contains("both bind datum field 'deadline'"));
+
+        let no_expiry = parse_datum_fields("owner:ByteArray,amount:Int").unwrap();
+        assert!(resolve_expiry_field(&[Feature::Expiry], &no_expiry, None).is_err());
+        assert!(resolve_expiry_field(&[Feature::Expiry], &fields, Some("owner")).is_err());
+    }
+
     #[test]
     fn test_parse_redeemer_empty() {
         let result = parse_redeemer_actions("");
@@ -264,7 +801,7 @@ mod tests {
             name: "owner".to_string(),
             aiken_type: "ByteArray".to_string(),
         }];
-        assert!(validate_features_against_types(&features, &fields, "spend").is_err());
+        assert!(validate_features_against_types(&features, &fields, &[], "spend").is_err());
 
         let fields_ok = vec![
             DatumField {
@@ -276,13 +813,218 @@ mod tests {
                 aiken_type: "Int".to_string(),
             },
         ];
-        assert!(validate_features_against_types(&features, &fields_ok, "spend").is_ok());
+        assert!(validate_features_against_types(&features, &fields_ok, &[], "spend").is_ok());
+    }
+
+    #[test]
+    fn test_data_field_is_not_an_int() {
+        let fields = parse_datum_fields("owner:ByteArray,deadline:Data").unwrap();
+        assert_eq!(fields[1].aiken_type, "Data");
+        let err = validate_features_against_types(&[Feature::TimeLock], &fields, &[], "spend")
+            .unwrap_err();
+        assert!(err.to_string().contains("requires at least one Int field"));
+    }
+
+    #[test]
+    fn test_validate_rejects_names_colliding_with_generated_code() {
+        let fields = vec![DatumField {
+            name: "Admin_pkh".to_string(),
+            aiken_type: "ByteArray".to_string(),
+        }];
+        let err = validate_features_against_types(&[Feature::SignatureAuth], &fields, &[], "spend")
+            .unwrap_err();
+        assert!(err.to_string().contains(
+            "Datum field 'Admin_pkh' collides with the 'admin_pkh' validator parameter of feature 'signature-auth'"
+        ));
+        // Only parameters of the selected features are reserved
+        assert!(validate_features_against_types(&[], &fields, &[], "spend").is_ok());
+
+        let actions = parse_redeemer_actions("Update,CustomRedeemer").unwrap();
+        let err = validate_features_against_types(&[], &[], &actions, "spend").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Redeemer action 'CustomRedeemer' collides with the generated type"));
+        let actions = parse_redeemer_actions("CustomDatum").unwrap();
+        assert!(validate_features_against_types(&[], &[], &actions, "spend").is_err());
     }
 
     #[test]
     fn test_validate_burn_needs_mint_purpose() {
         let features = vec![Feature::BurnVerification];
-        assert!(validate_features_against_types(&features, &[], "spend").is_err());
-        assert!(validate_features_against_types(&features, &[], "mint").is_ok());
+        let actions = parse_redeemer_actions("Mint,Burn").unwrap();
+        assert!(validate_features_against_types(&features, &[], &actions, "spend").is_err());
+        assert!(validate_features_against_types(&features, &[], &actions, "mint").is_ok());
+    }
+
+    #[test]
+    fn test_validate_burn_without_burn_action_is_allowed() {
+        // Only warned about by compose (`burn-without-burn-action`)
+        let features = vec![Feature::BurnVerification];
+        let actions = parse_redeemer_actions("Mint,Destroy").unwrap();
+        assert!(validate_features_against_types(&features, &[], &actions, "mint").is_ok());
+    }
+
+    #[test]
+    fn test_validate_pausable_needs_paused_field_and_unpause() {
+        let features = vec![
+            Feature::SignatureAuth,
+            Feature::DatumContinuity,
+            Feature::Pausable,
+        ];
+        let fields = parse_datum_fields("owner:ByteArray,paused:Bool").unwrap();
+        let actions = parse_redeemer_actions("Update,Unpause").unwrap();
+        assert!(validate_features_against_types(&features, &fields, &actions, "spend").is_ok());
+
+        let err = |fields: &str, actions: &str| {
+            validate_features_against_types(
+                &features,
+                &parse_datum_fields(fields).unwrap(),
+                &parse_redeemer_actions(actions).unwrap(),
+                "spend",
+            )
+            .unwrap_err()
+            .to_string()
+        };
+        assert!(err("owner:ByteArray,paused:Int", "Update,Unpause")
+            .contains("requires datum field 'paused:Bool'"));
+        assert!(err("paused:Bool", "Update,Resume").contains("redeemer action named 'Unpause'"));
+        assert!(err("paused:Bool", "Update,Unpause(reason:ByteArray)").contains("no fields"));
+        assert!(err("paused:Bool", "Unpause").contains("besides 'Unpause'"));
+    }
+
+    #[test]
+    fn test_validate_admin_rotation_needs_admin_field_and_rotate_action() {
+        let features = vec![
+            Feature::SignatureAuth,
+            Feature::DatumContinuity,
+            Feature::AdminRotation,
+        ];
+        let err = |fields: &str, actions: &str| {
+            validate_features_against_types(
+                &features,
+                &parse_datum_fields(fields).unwrap(),
+                &parse_redeemer_actions(actions).unwrap(),
+                "spend",
+            )
+            .map_err(|e| e.to_string())
+        };
+        assert!(err("admin:ByteArray", "Update,RotateAdmin(new_admin:ByteArray)").is_ok());
+        assert!(
+            err("owner:ByteArray", "Update,RotateAdmin(new_admin:ByteArray)")
+                .unwrap_err()
+                .contains("requires datum field 'admin:ByteArray'")
+        );
+        assert!(err("admin:ByteArray", "Update,RotateAdmin")
+            .unwrap_err()
+            .contains("requires a redeemer field 'new_admin:ByteArray' in at least one action"));
+        assert!(
+            err("admin:ByteArray", "Update(new_admin:ByteArray),RotateAdmin")
+                .unwrap_err()
+                .contains("'RotateAdmin(new_admin:ByteArray)'")
+        );
+        assert!(err("admin:ByteArray", "RotateAdmin(new_admin:ByteArray)")
+            .unwrap_err()
+            .contains("besides 'RotateAdmin'"));
+    }
+
+    #[test]
+    fn test_validate_proportional_payout_needs_pool_fields_and_claim_action() {
+        let features = vec![Feature::DatumContinuity, Feature::ProportionalPayout];
+        let err = |fields: &str, actions: &str| {
+            validate_features_against_types(
+                &features,
+                &parse_datum_fields(fields).unwrap(),
+                &parse_redeemer_actions(actions).unwrap(),
+                "spend",
+            )
+            .map_err(|e| e.to_string())
+        };
+        let pool = "total_pool:Int,total_shares:Int";
+        assert!(err(pool, "Deposit,Claim(claimant_share:Int)").is_ok());
+        assert!(err("total_pool:Int", "Deposit,Claim(claimant_share:Int)")
+            .unwrap_err()
+            .contains("requires datum field 'total_shares:Int'"));
+        assert!(err(pool, "Deposit,Claim(share:Int)")
+            .unwrap_err()
+            .contains("requires a redeemer field 'claimant_share:Int' in at least one action"));
+        assert!(err(pool, "Deposit(claimant_share:Int),Claim")
+            .unwrap_err()
+            .contains("'Claim(claimant_share:Int)'"));
+        // The field must also have the type the check expects
+        assert!(err(pool, "Deposit,Claim(claimant_share:ByteArray)")
+            .unwrap_err()
+            .contains("redeemer field 'claimant_share:Int'"));
+        assert!(err(pool, "Claim(claimant_share:Int)")
+            .unwrap_err()
+            .contains("besides 'Claim'"));
+    }
+
+    #[test]
+    fn test_validate_deposit_only_needs_fieldless_deposit_and_another_action() {
+        let features = vec![Feature::DatumContinuity, Feature::DepositOnly];
+        let err = |actions: &str| {
+            validate_features_against_types(
+                &features,
+                &parse_datum_fields("owner:ByteArray").unwrap(),
+                &parse_redeemer_actions(actions).unwrap(),
+                "spend",
+            )
+            .map_err(|e| e.to_string())
+        };
+        assert!(err("Deposit,Withdraw(amount:Int)").is_ok());
+        assert!(err("Add,Withdraw")
+            .unwrap_err()
+            .contains("redeemer action named 'Deposit'"));
+        assert!(err("Deposit(amount:Int),Withdraw")
+            .unwrap_err()
+            .contains("no fields"));
+        assert!(err("Deposit").unwrap_err().contains("besides 'Deposit'"));
+    }
+
+    #[test]
+    fn test_validate_refundable_deposit_needs_depositor_deadline_and_refund() {
+        let err = |fields: &str, actions: &str| {
+            validate_features_against_types(
+                &[Feature::RefundableDeposit],
+                &parse_datum_fields(fields).unwrap(),
+                &parse_redeemer_actions(actions).unwrap(),
+                "spend",
+            )
+            .map_err(|e| e.to_string())
+        };
+        let fields = "depositor:ByteArray,claim_deadline:Int";
+        assert!(err(fields, "Claim,Refund").is_ok());
+        assert!(err("depositor:ByteArray", "Claim,Refund")
+            .unwrap_err()
+            .contains("requires datum field 'claim_deadline:Int'"));
+        assert!(err(fields, "Claim,Cancel")
+            .unwrap_err()
+            .contains("redeemer action named 'Refund'"));
+        assert!(err(fields, "Claim,Refund(amount:Int)")
+            .unwrap_err()
+            .contains("no fields"));
+        assert!(err(fields, "Refund")
+            .unwrap_err()
+            .contains("besides 'Refund'"));
+    }
+
+    #[test]
+    fn test_validate_signature_threshold_needs_signers_and_threshold() {
+        let check = |fields: &str| {
+            validate_features_against_types(
+                &[Feature::SignatureThresholdFromDatum],
+                &parse_datum_fields(fields).unwrap(),
+                &parse_redeemer_actions("Spend").unwrap(),
+                "spend",
+            )
+            .map_err(|e| e.to_string())
+        };
+        assert!(check("signers:List<ByteArray>,threshold:Int").is_ok());
+        assert!(check("owner:ByteArray,threshold:Int")
+            .unwrap_err()
+            .contains("requires datum field 'signers:List<ByteArray>'"));
+        assert!(check("signers:List<ByteArray>,threshold:Data")
+            .unwrap_err()
+            .contains("requires datum field 'threshold:Int'"));
     }
 }
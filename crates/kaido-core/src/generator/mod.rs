@@ -1,3 +1,10 @@
+mod options;
 mod render;
 
-pub use render::{GeneratedFile, ProjectGenerator, RenderResult};
+pub use options::{
+    build_options, ignored_options, validate_custom_extras, validate_options, OptionExtras,
+};
+pub use render::{
+    recorded_kaido_version, FileKind, GenerationMeta, GeneratedFile, PlannedFile,
+    ProjectGenerator, RenderResult, ValidatorParam, ValidatorParams,
+};
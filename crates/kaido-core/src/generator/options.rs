@@ -0,0 +1,547 @@
+use crate::diagnostics::Diagnostics;
+use crate::error::{KaidoError, Result};
+use crate::features;
+use crate::templates::{CiProvider, GenerateOptions, Network, Template};
+
+/// Template-specific and project options for `build_options`.
+/// Fields a template doesn't use are ignored; unset fields fall back to template defaults.
+#[derive(Debug, Clone, Default)]
+pub struct OptionExtras {
+    // --- Simple Mint options ---
+    /// Token display name (defaults to the project name)
+    pub token_name: Option<String>,
+    /// On-chain asset name (defaults to the uppercased token name)
+    pub asset_name: Option<String>,
+    pub time_lock: bool,
+
+    // --- Vesting options ---
+    pub cancellable: bool,
+    pub partial_claim: bool,
+
+    // --- NFT Marketplace options ---
+    pub royalty: bool,
+
+    // --- Custom template options ---
+    /// Feature names or aliases, e.g. ["sig", "timelock"]
+    pub features: Vec<String>,
+    /// Datum fields, e.g. "owner:ByteArray,deadline:Int"
+    pub datum: Option<String>,
+    /// Redeemer actions, e.g. "Claim,Withdraw(amount:Int)"
+    pub redeemer: Option<String>,
+    /// "spend" (default) or "mint"
+    pub purpose: Option<String>,
+    pub counter_field: Option<String>,
+    pub expiry_field: Option<String>,
+    /// "<tx hash>#<index>" the unique-mint test consumes
+    pub seed_utxo: Option<String>,
+    pub explain: bool,
+    pub trace: bool,
+    pub property_tests: bool,
+    pub single_file: bool,
+
+    // --- Project options ---
+    pub validator_name: Option<String>,
+    pub stdlib_version: Option<String>,
+    pub emit_lock: bool,
+    /// "mainnet", "preprod" or "preview" (default)
+    pub network: Option<String>,
+    /// "github" to emit a CI workflow
+    pub emit_ci: Option<String>,
+}
+
+/// Build validated `GenerateOptions` for a template.
+/// Shared by the CLI, the MCP server and the WASM API so every entry point parses identically.
+pub fn build_options(
+    template: Template,
+    namespace: &str,
+    project_name: &str,
+    extras: &OptionExtras,
+) -> Result<GenerateOptions> {
+    GenerateOptions::validate_namespace_and_project(namespace, project_name)
+        .map_err(KaidoError::InvalidOption)?;
+
+    let mut options = match template {
+        Template::SimpleMint => {
+            let token_name = extras.token_name.as_deref().unwrap_or(project_name);
+            let default_an = token_name.to_uppercase().replace(' ', "_");
+            let asset_name = extras.asset_name.as_deref().unwrap_or(&default_an);
+            GenerateOptions::simple_mint(
+                namespace,
+                project_name,
+                token_name,
+                asset_name,
+                extras.time_lock,
+            )
+        }
+        Template::Vesting => GenerateOptions::vesting(
+            namespace,
+            project_name,
+            extras.cancellable,
+            extras.partial_claim,
+        ),
+        Template::Escrow => GenerateOptions::escrow(namespace, project_name),
+        Template::MultisigTreasury => GenerateOptions::multisig_treasury(namespace, project_name),
+        Template::NftMarketplace => {
+            GenerateOptions::nft_marketplace(namespace, project_name, extras.royalty)
+        }
+        Template::StakingPool => GenerateOptions::staking_pool(namespace, project_name),
+        Template::OracleSettlement => GenerateOptions::oracle_settlement(namespace, project_name),
+        Template::ReferralSystem => GenerateOptions::referral_system(namespace, project_name),
+        Template::DexPool => GenerateOptions::dex_pool(namespace, project_name),
+        Template::LendingPool => GenerateOptions::lending_pool(namespace, project_name),
+        Template::DaoGovernance => GenerateOptions::dao_governance(namespace, project_name),
+        Template::StreamingPayments => GenerateOptions::streaming_payments(namespace, project_name),
+        Template::HashTimelock => GenerateOptions::hash_timelock(namespace, project_name),
+        Template::Payroll => GenerateOptions::payroll(namespace, project_name),
+        Template::Faucet => GenerateOptions::faucet(namespace, project_name),
+        Template::MerkleAllowlistMint => {
+            GenerateOptions::merkle_allowlist_mint(namespace, project_name)
+        }
+        Template::ZkGate => GenerateOptions::zk_gate(namespace, project_name),
+        Template::TokenLock => GenerateOptions::token_lock(namespace, project_name),
+        Template::DutchAuction => GenerateOptions::dutch_auction(namespace, project_name),
+        Template::OracleFeed => GenerateOptions::oracle_feed(namespace, project_name),
+        Template::Custom => custom_options(namespace, project_name, extras)?,
+    };
+
+    options.explain = extras.explain;
+    options.trace = extras.trace;
+    options.property_tests = extras.property_tests;
+    options.single_file = extras.single_file;
+    options.emit_lock = extras.emit_lock;
+
+    if let Some(version) = &extras.stdlib_version {
+        GenerateOptions::validate_stdlib_version(version).map_err(KaidoError::InvalidOption)?;
+        options.stdlib_version = version.clone();
+    }
+
+    if let Some(name) = &extras.validator_name {
+        GenerateOptions::validate_validator_name(name).map_err(KaidoError::InvalidOption)?;
+        options.validator_name = name.clone();
+    }
+
+    if let Some(network) = &extras.network {
+        options.network = parse_network(network)?;
+    }
+
+    if let Some(provider) = &extras.emit_ci {
+        options.emit_ci = Some(parse_ci_provider(provider)?);
+    }
+
+    Ok(options)
+}
+
+fn parse_network(network: &str) -> Result<Network> {
+    network.parse().map_err(|_| {
+        KaidoError::InvalidOption(format!(
+            "Unknown network '{}'. Must be 'mainnet', 'preprod' or 'preview'",
+            network
+        ))
+    })
+}
+
+fn parse_ci_provider(provider: &str) -> Result<CiProvider> {
+    provider.parse().map_err(|_| {
+        KaidoError::InvalidOption(format!(
+            "Unknown CI provider '{}'. Must be 'github'",
+            provider
+        ))
+    })
+}
+
+/// Run the checks `build_options` does without stopping at the first failure.
+/// Returns every error found; empty when the options are valid. Nothing is rendered.
+pub fn validate_options(
+    template: Template,
+    namespace: &str,
+    project_name: &str,
+    extras: &OptionExtras,
+) -> Vec<KaidoError> {
+    let mut errors = Vec::new();
+
+    if let Err(e) = GenerateOptions::validate_namespace_and_project(namespace, project_name) {
+        errors.push(KaidoError::InvalidOption(e));
+    }
+    if let Some(version) = &extras.stdlib_version {
+        if let Err(e) = GenerateOptions::validate_stdlib_version(version) {
+            errors.push(KaidoError::InvalidOption(e));
+        }
+    }
+    if let Some(name) = &extras.validator_name {
+        if let Err(e) = GenerateOptions::validate_validator_name(name) {
+            errors.push(KaidoError::InvalidOption(e));
+        }
+    }
+    if let Some(Err(e)) = extras.network.as_deref().map(parse_network) {
+        errors.push(e);
+    }
+    if let Some(Err(e)) = extras.emit_ci.as_deref().map(parse_ci_provider) {
+        errors.push(e);
+    }
+    if template == Template::Custom {
+        errors.extend(validate_custom_extras(extras));
+    }
+
+    errors
+}
+
+/// Warn about each template-specific option set in `extras` that `template` does not accept
+/// (per `Template::applicable_options`), e.g. `cancellable` with simple_mint, which
+/// `build_options` would otherwise drop silently
+pub fn ignored_options(template: Template, extras: &OptionExtras) -> Diagnostics {
+    let set = [
+        ("token_name", extras.token_name.is_some()),
+        ("asset_name", extras.asset_name.is_some()),
+        ("time_lock", extras.time_lock),
+        ("cancellable", extras.cancellable),
+        ("partial_claim", extras.partial_claim),
+        ("royalty", extras.royalty),
+        ("features", !extras.features.is_empty()),
+        ("datum", extras.datum.is_some()),
+        ("redeemer", extras.redeemer.is_some()),
+        // "spend" is the default, so only another purpose counts as set
+        (
+            "purpose",
+            extras.purpose.as_deref().is_some_and(|p| p != "spend"),
+        ),
+        ("counter_field", extras.counter_field.is_some()),
+        ("expiry_field", extras.expiry_field.is_some()),
+        ("seed_utxo", extras.seed_utxo.is_some()),
+        ("explain", extras.explain),
+        ("trace", extras.trace),
+        ("property_tests", extras.property_tests),
+        ("single_file", extras.single_file),
+    ];
+
+    let mut diagnostics = Diagnostics::default();
+    for (name, _) in set.into_iter().filter(|(_, is_set)| *is_set) {
+        if template.applicable_options().iter().any(|o| o.name == name) {
+            continue;
+        }
+        let accepting: Vec<&str> = Template::all()
+            .iter()
+            .filter(|t| t.applicable_options().iter().any(|o| o.name == name))
+            .map(|t| t.slug())
+            .collect();
+        diagnostics.warn(
+            "ignored-option",
+            format!(
+                "{} is ignored for the {} template (it applies to: {})",
+                name,
+                template.slug(),
+                accepting.join(", ")
+            ),
+            Some(name.to_string()),
+        );
+    }
+    diagnostics
+}
+
+/// Collect every custom-template error: purpose, features, datum, redeemer and
+/// their cross-checks (an unparseable part is skipped in the cross-checks)
+pub fn validate_custom_extras(extras: &OptionExtras) -> Vec<KaidoError> {
+    let mut errors = Vec::new();
+    let purpose = extras.purpose.as_deref().unwrap_or("spend");
+
+    if purpose != "spend" && purpose != "mint" {
+        errors.push(KaidoError::InvalidOption(format!(
+            "Invalid purpose '{}'. Must be 'spend' or 'mint'",
+            purpose
+        )));
+    }
+
+    let resolved = features::parse_features(&extras.features)
+        .and_then(|parsed| features::compose::resolve_features(&parsed, purpose))
+        .map_err(|e| errors.push(e))
+        .ok();
+
+    let datum_fields = match &extras.datum {
+        Some(d) => features::types::parse_datum_fields(d)
+            .map_err(|e| errors.push(e))
+            .unwrap_or_default(),
+        None if purpose == "spend" => {
+            errors.push(KaidoError::InvalidOption(
+                "datum is required for spend-purpose custom validators".to_string(),
+            ));
+            vec![]
+        }
+        None => vec![],
+    };
+
+    let redeemer_actions = match &extras.redeemer {
+        Some(r) => features::types::parse_redeemer_actions(r)
+            .map_err(|e| errors.push(e))
+            .unwrap_or_default(),
+        None => {
+            errors.push(KaidoError::InvalidOption(
+                "redeemer is required for custom validators".to_string(),
+            ));
+            vec![]
+        }
+    };
+
+    if let Some(resolved) = resolved {
+        let checks = [
+            features::types::validate_features_against_types(
+                &resolved,
+                &datum_fields,
+                &redeemer_actions,
+                purpose,
+            ),
+            features::types::resolve_counter_field(
+                &resolved,
+                &datum_fields,
+                extras.counter_field.as_deref(),
+            )
+            .map(|_| ()),
+            features::types::resolve_expiry_field(
+                &resolved,
+                &datum_fields,
+                extras.expiry_field.as_deref(),
+            )
+            .map(|_| ()),
+            features::types::resolve_seed_utxo(&resolved, extras.seed_utxo.as_deref()).map(|_| ()),
+        ];
+        errors.extend(checks.into_iter().filter_map(|r| r.err()));
+    }
+
+    errors
+}
+
+fn custom_options(
+    namespace: &str,
+    project_name: &str,
+    extras: &OptionExtras,
+) -> Result<GenerateOptions> {
+    let purpose = extras.purpose.as_deref().unwrap_or("spend");
+    if purpose != "spend" && purpose != "mint" {
+        return Err(KaidoError::InvalidOption(format!(
+            "Invalid purpose '{}'. Must be 'spend' or 'mint'",
+            purpose
+        )));
+    }
+
+    let parsed = features::parse_features(&extras.features)?;
+    let resolved = features::compose::resolve_features(&parsed, purpose)?;
+    let names: Vec<String> = resolved.iter().map(|f| f.name().to_string()).collect();
+
+    let datum_fields = if let Some(d) = &extras.datum {
+        features::types::parse_datum_fields(d)?
+    } else if purpose == "spend" {
+        return Err(KaidoError::InvalidOption(
+            "datum is required for spend-purpose custom validators".to_string(),
+        ));
+    } else {
+        vec![]
+    };
+
+    let redeemer_actions = if let Some(r) = &extras.redeemer {
+        features::types::parse_redeemer_actions(r)?
+    } else {
+        return Err(KaidoError::InvalidOption(
+            "redeemer is required for custom validators".to_string(),
+        ));
+    };
+
+    features::types::validate_features_against_types(
+        &resolved,
+        &datum_fields,
+        &redeemer_actions,
+        purpose,
+    )?;
+    let counter_field = features::types::resolve_counter_field(
+        &resolved,
+        &datum_fields,
+        extras.counter_field.as_deref(),
+    )?;
+    let expiry_field = features::types::resolve_expiry_field(
+        &resolved,
+        &datum_fields,
+        extras.expiry_field.as_deref(),
+    )?;
+    let seed_utxo = features::types::resolve_seed_utxo(&resolved, extras.seed_utxo.as_deref())?;
+
+    let mut options = GenerateOptions::custom(
+        namespace,
+        project_name,
+        purpose,
+        datum_fields,
+        redeemer_actions,
+        names,
+    );
+    options.counter_field = counter_field;
+    options.expiry_field = expiry_field;
+    options.seed_utxo = seed_utxo;
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_extras() -> OptionExtras {
+        OptionExtras {
+            features: vec!["value".to_string(), "sig".to_string()],
+            datum: Some("owner:ByteArray,nonce:Int".to_string()),
+            redeemer: Some("Update,Close".to_string()),
+            ..OptionExtras::default()
+        }
+    }
+
+    #[test]
+    fn ignored_options_warns_about_flags_the_template_does_not_accept() {
+        let extras = OptionExtras {
+            cancellable: true,
+            time_lock: true,
+            purpose: Some("spend".to_string()),
+            ..OptionExtras::default()
+        };
+        let ignored = ignored_options(Template::SimpleMint, &extras);
+        let messages: Vec<&str> = ignored
+            .warnings
+            .iter()
+            .map(|w| w.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec!["cancellable is ignored for the simple_mint template (it applies to: vesting)"]
+        );
+        assert_eq!(ignored.warnings[0].code, "ignored-option");
+
+        assert!(ignored_options(Template::Vesting, &extras)
+            .warnings
+            .iter()
+            .all(|w| w.location.as_deref() == Some("time_lock")));
+        assert!(ignored_options(Template::Custom, &custom_extras()).is_empty());
+
+        // Token lock honours the output options of the custom template it is built on
+        let token_lock = OptionExtras {
+            explain: true,
+            trace: true,
+            single_file: true,
+            ..Default::default()
+        };
+        assert!(ignored_options(Template::TokenLock, &token_lock).is_empty());
+        let fixed = ignored_options(Template::TokenLock, &custom_extras());
+        assert!(fixed
+            .warnings
+            .iter()
+            .any(|w| w.location.as_deref() == Some("features")));
+    }
+
+    #[test]
+    fn custom_resolves_features_and_types() {
+        let options =
+            build_options(Template::Custom, "myorg", "my-validator", &custom_extras()).unwrap();
+        assert_eq!(options.purpose, "spend");
+        assert_eq!(
+            options.feature_names,
+            vec!["signature-auth", "datum-continuity", "value-preservation"]
+        );
+        assert_eq!(options.datum_fields.len(), 2);
+        assert_eq!(options.redeemer_actions.len(), 2);
+        assert_eq!(options.validator_name, "my_validator_validator");
+        assert_eq!(options.counter_field, None);
+    }
+
+    #[test]
+    fn custom_resolves_counter_field() {
+        let extras = OptionExtras {
+            features: vec!["monotonic-counter".to_string()],
+            ..custom_extras()
+        };
+        let options = build_options(Template::Custom, "myorg", "my-sm", &extras).unwrap();
+        assert_eq!(options.counter_field.as_deref(), Some("nonce"));
+    }
+
+    #[test]
+    fn custom_rejects_invalid_inputs() {
+        let err = |extras: OptionExtras| {
+            build_options(Template::Custom, "myorg", "x", &extras)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(err(OptionExtras {
+            purpose: Some("withdraw".to_string()),
+            ..custom_extras()
+        })
+        .contains("Invalid purpose 'withdraw'"));
+        assert!(err(OptionExtras {
+            datum: None,
+            ..custom_extras()
+        })
+        .contains("datum is required"));
+        assert!(err(OptionExtras {
+            redeemer: None,
+            ..custom_extras()
+        })
+        .contains("redeemer is required"));
+        assert!(err(OptionExtras {
+            features: vec!["burn".to_string()],
+            ..custom_extras()
+        })
+        .contains("requires purpose 'mint'"));
+        assert!(err(OptionExtras {
+            features: vec!["unknown".to_string()],
+            ..custom_extras()
+        })
+        .contains("Unknown feature 'unknown'"));
+    }
+
+    #[test]
+    fn validate_options_collects_every_error() {
+        let extras = OptionExtras {
+            features: vec!["bogus".to_string()],
+            datum: Some("owner:Strng".to_string()),
+            validator_name: Some("Bad-Name".to_string()),
+            ..custom_extras()
+        };
+        let errors: Vec<String> = validate_options(Template::Custom, "../x", "x", &extras)
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.contains("Unknown feature 'bogus'")));
+        assert!(errors.iter().any(|e| e.contains("Strng")));
+
+        assert!(validate_options(Template::Custom, "myorg", "x", &custom_extras()).is_empty());
+        // Custom-only options don't apply to other templates
+        assert_eq!(
+            validate_options(Template::Escrow, "myorg", "x", &extras).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn project_options_are_validated() {
+        let extras = OptionExtras {
+            validator_name: Some("Bad-Name".to_string()),
+            ..OptionExtras::default()
+        };
+        assert!(build_options(Template::Escrow, "myorg", "x", &extras).is_err());
+        let extras = OptionExtras {
+            stdlib_version: Some("main".to_string()),
+            ..OptionExtras::default()
+        };
+        assert!(build_options(Template::Escrow, "myorg", "x", &extras).is_err());
+        assert!(build_options(Template::Escrow, "../x", "x", &OptionExtras::default()).is_err());
+
+        let extras = OptionExtras {
+            network: Some("testnet".to_string()),
+            ..OptionExtras::default()
+        };
+        assert!(build_options(Template::Escrow, "myorg", "x", &extras).is_err());
+        assert_eq!(
+            validate_options(Template::Escrow, "myorg", "x", &extras).len(),
+            1
+        );
+        let extras = OptionExtras {
+            network: Some("mainnet".to_string()),
+            ..OptionExtras::default()
+        };
+        let options = build_options(Template::Escrow, "myorg", "x", &extras).unwrap();
+        assert_eq!(options.network, Network::Mainnet);
+        let options = build_options(Template::Escrow, "myorg", "x", &OptionExtras::default());
+        assert_eq!(options.unwrap().network, Network::Preview);
+    }
+}
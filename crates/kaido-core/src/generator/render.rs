@@ -1,10 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
 use tera::{Context, Tera};
 
+use crate::diagnostics::Diagnostics;
 use crate::error::{KaidoError, Result};
 use crate::features;
 use crate::features::compose;
-use crate::templates::{GenerateOptions, Template};
+use crate::templates::{CiProvider, GenerateOptions, SdkLang, Template, AIKEN_VERSION, FUZZ_VERSION, KAIDO_VERSION};
 
 /// A single generated file (path relative to project root + content)
 #[derive(Debug, Clone)]
@@ -15,6 +19,29 @@ pub struct GeneratedFile {
     pub content: String,
 }
 
+/// What a generated file is, for file-tree previews
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileKind {
+    /// aiken.toml, .aikido.toml, .editorconfig, aiken.lock
+    Config,
+    /// CI workflow
+    Ci,
+    /// lib/ types module
+    Types,
+    /// Other lib/ helper module
+    Lib,
+    /// validators/ module
+    Validator,
+}
+
+/// A file `render` would produce, without its content
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub kind: FileKind,
+}
+
 /// Result of rendering templates — contains all files to write
 #[derive(Debug, Clone)]
 pub struct RenderResult {
@@ -22,6 +49,82 @@ pub struct RenderResult {
     pub files: Vec<GeneratedFile>,
     /// Template that was used
     pub template: Template,
+    /// Non-fatal findings to show the user (e.g. custom actions with no checks)
+    pub diagnostics: Diagnostics,
+    /// What was generated, for tooling to report without re-deriving it
+    pub meta: GenerationMeta,
+}
+
+impl RenderResult {
+    /// Build a result with `files` sorted by path, so output order is stable across runs
+    fn sorted(
+        mut files: Vec<GeneratedFile>,
+        template: Template,
+        diagnostics: Diagnostics,
+        meta: GenerationMeta,
+    ) -> Self {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Self {
+            files,
+            template,
+            diagnostics,
+            meta,
+        }
+    }
+}
+
+/// Metadata about a render, filled in while rendering
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GenerationMeta {
+    /// kaido release that rendered the files
+    pub kaido_version: String,
+    /// Seconds since the Unix epoch at render time (None on wasm32, which has no clock)
+    pub generated_at: Option<u64>,
+    /// Features the custom validator composes, in composition order (empty for built-in templates)
+    pub resolved_features: Vec<String>,
+    /// (name, type) of each validator parameter, in application order; a parameter several
+    /// validators share is listed once. Empty for SDK renders, which emit no validator.
+    pub validator_params: Vec<(String, String)>,
+}
+
+impl GenerationMeta {
+    fn new(resolved_features: Vec<String>, validator_params: Vec<(String, String)>) -> Self {
+        Self {
+            kaido_version: KAIDO_VERSION.to_string(),
+            generated_at: unix_now(),
+            resolved_features,
+            validator_params,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_now() -> Option<u64> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+// SystemTime::now() panics on wasm32-unknown-unknown
+#[cfg(target_arch = "wasm32")]
+fn unix_now() -> Option<u64> {
+    None
+}
+
+/// A parameter a generated validator must be applied with before it can be deployed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorParam {
+    pub name: String,
+    pub aiken_type: String,
+    /// What the parameter controls, when known
+    pub description: Option<String>,
+}
+
+/// The parameters of one generated validator, in the order `aiken blueprint apply` takes them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorParams {
+    /// Path of the validator module, e.g. `validators/my_token.ak`
+    pub path: String,
+    pub params: Vec<ValidatorParam>,
 }
 
 /// Generates complete Aiken projects from templates (pure computation, no I/O)
@@ -43,6 +146,18 @@ impl ProjectGenerator {
             "base/.aikido.toml",
             include_str!("../../../../templates/base/aikido.toml.tera"),
         )?;
+        tera.add_raw_template(
+            "base/aiken.lock",
+            include_str!("../../../../templates/base/aiken.lock.tera"),
+        )?;
+        tera.add_raw_template(
+            "base/.editorconfig",
+            include_str!("../../../../templates/base/editorconfig.tera"),
+        )?;
+        tera.add_raw_template(
+            "base/github_ci.yml",
+            include_str!("../../../../templates/base/github_ci.yml.tera"),
+        )?;
 
         // Register simple_mint templates
         tera.add_raw_template(
@@ -123,6 +238,10 @@ impl ProjectGenerator {
             "sdk_base/tsconfig.json",
             include_str!("../../../../templates/sdk_base/tsconfig.json.tera"),
         )?;
+        tera.add_raw_template(
+            "sdk_base/address.ts",
+            include_str!("../../../../templates/sdk_base/address.ts.tera"),
+        )?;
 
         // SDK templates — simple_mint
         tera.add_raw_template("simple_mint/sdk/types.ts", include_str!("../../../../templates/simple_mint/sdk/types.ts.tera"))?;
@@ -166,6 +285,18 @@ impl ProjectGenerator {
         tera.add_raw_template("oracle_settlement/sdk/client.ts", include_str!("../../../../templates/oracle_settlement/sdk/client.ts.tera"))?;
         tera.add_raw_template("oracle_settlement/sdk/index.ts", include_str!("../../../../templates/oracle_settlement/sdk/index.ts.tera"))?;
 
+        // Python SDK templates
+        tera.add_raw_template(
+            "sdk_py_base/pyproject.toml",
+            include_str!("../../../../templates/sdk_py_base/pyproject.toml.tera"),
+        )?;
+        tera.add_raw_template("simple_mint/sdk_py/types.py", include_str!("../../../../templates/simple_mint/sdk_py/types.py.tera"))?;
+        tera.add_raw_template("simple_mint/sdk_py/serialization.py", include_str!("../../../../templates/simple_mint/sdk_py/serialization.py.tera"))?;
+        tera.add_raw_template("simple_mint/sdk_py/__init__.py", include_str!("../../../../templates/simple_mint/sdk_py/__init__.py.tera"))?;
+        tera.add_raw_template("vesting/sdk_py/types.py", include_str!("../../../../templates/vesting/sdk_py/types.py.tera"))?;
+        tera.add_raw_template("vesting/sdk_py/serialization.py", include_str!("../../../../templates/vesting/sdk_py/serialization.py.tera"))?;
+        tera.add_raw_template("vesting/sdk_py/__init__.py", include_str!("../../../../templates/vesting/sdk_py/__init__.py.tera"))?;
+
         // Register dex_pool templates
         tera.add_raw_template(
             "dex_pool/types.ak",
@@ -206,6 +337,80 @@ impl ProjectGenerator {
             include_str!("../../../../templates/streaming_payments/validator.ak.tera"),
         )?;
 
+        // Register hash_timelock templates
+        tera.add_raw_template(
+            "hash_timelock/types.ak",
+            include_str!("../../../../templates/hash_timelock/types.ak.tera"),
+        )?;
+        tera.add_raw_template(
+            "hash_timelock/validator.ak",
+            include_str!("../../../../templates/hash_timelock/validator.ak.tera"),
+        )?;
+
+        // Register payroll templates
+        tera.add_raw_template(
+            "payroll/types.ak",
+            include_str!("../../../../templates/payroll/types.ak.tera"),
+        )?;
+        tera.add_raw_template(
+            "payroll/validator.ak",
+            include_str!("../../../../templates/payroll/validator.ak.tera"),
+        )?;
+
+        // Register faucet templates
+        tera.add_raw_template(
+            "faucet/types.ak",
+            include_str!("../../../../templates/faucet/types.ak.tera"),
+        )?;
+        tera.add_raw_template(
+            "faucet/validator.ak",
+            include_str!("../../../../templates/faucet/validator.ak.tera"),
+        )?;
+
+        // Register merkle_allowlist_mint templates
+        tera.add_raw_template(
+            "merkle_allowlist_mint/types.ak",
+            include_str!("../../../../templates/merkle_allowlist_mint/types.ak.tera"),
+        )?;
+        tera.add_raw_template(
+            "merkle_allowlist_mint/validation.ak",
+            include_str!("../../../../templates/merkle_allowlist_mint/validation.ak.tera"),
+        )?;
+        tera.add_raw_template(
+            "merkle_allowlist_mint/validator.ak",
+            include_str!("../../../../templates/merkle_allowlist_mint/validator.ak.tera"),
+        )?;
+
+        // Register zk_gate templates
+        tera.add_raw_template(
+            "zk_gate/types.ak",
+            include_str!("../../../../templates/zk_gate/types.ak.tera"),
+        )?;
+        tera.add_raw_template(
+            "zk_gate/validator.ak",
+            include_str!("../../../../templates/zk_gate/validator.ak.tera"),
+        )?;
+
+        // Register dutch_auction templates
+        tera.add_raw_template(
+            "dutch_auction/types.ak",
+            include_str!("../../../../templates/dutch_auction/types.ak.tera"),
+        )?;
+        tera.add_raw_template(
+            "dutch_auction/validator.ak",
+            include_str!("../../../../templates/dutch_auction/validator.ak.tera"),
+        )?;
+
+        // Register oracle_feed templates
+        tera.add_raw_template(
+            "oracle_feed/types.ak",
+            include_str!("../../../../templates/oracle_feed/types.ak.tera"),
+        )?;
+        tera.add_raw_template(
+            "oracle_feed/validator.ak",
+            include_str!("../../../../templates/oracle_feed/validator.ak.tera"),
+        )?;
+
         // Register referral_system templates
         tera.add_raw_template(
             "referral_system/types.ak",
@@ -243,6 +448,95 @@ impl ProjectGenerator {
         Ok(Self { tera })
     }
 
+    /// Create a generator whose embedded templates are replaced by `<name>.tera` files under
+    /// `dir`, e.g. `dir/simple_mint/validator.ak.tera` overrides `simple_mint/validator.ak`.
+    /// Templates the directory doesn't provide stay embedded. Every file must override an
+    /// embedded template, so a misspelled path is an error rather than silently ignored.
+    pub fn new_with_overrides(dir: &Path) -> Result<Self> {
+        let mut gen = Self::new()?;
+        if !dir.is_dir() {
+            return Err(KaidoError::InvalidOption(format!(
+                "Template directory '{}' does not exist or is not a directory",
+                dir.display()
+            )));
+        }
+
+        let embedded: HashSet<String> = gen.tera.get_template_names().map(String::from).collect();
+        let mut overrides = Vec::new();
+        for rel in template_files(dir, Path::new(""))? {
+            let name = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            match name.strip_suffix(".tera") {
+                Some(template) if embedded.contains(template) => {
+                    overrides.push((template.to_string(), fs::read_to_string(dir.join(&rel))?));
+                }
+                _ => {
+                    return Err(KaidoError::InvalidOption(format!(
+                        "'{}' in template directory '{}' does not override a built-in template \
+                         (expected e.g. base/aiken.toml.tera or simple_mint/validator.ak.tera)",
+                        name,
+                        dir.display()
+                    )));
+                }
+            }
+        }
+        gen.tera.add_raw_templates(overrides)?;
+        Ok(gen)
+    }
+
+    /// Paths (sorted, as in `render`) and kinds of the files `render` produces for these
+    /// options, from each template's file layout and without rendering anything
+    pub fn render_paths(options: &GenerateOptions) -> Vec<PlannedFile> {
+        let lib_prefix = format!("lib/{}/{}", options.namespace, options.module_name);
+        let mut files = vec![("aiken.toml".to_string(), FileKind::Config)];
+        files.push((".aikido.toml".to_string(), FileKind::Config));
+        files.push((".editorconfig".to_string(), FileKind::Config));
+        if options.emit_lock {
+            files.push(("aiken.lock".to_string(), FileKind::Config));
+        }
+        if let Some(CiProvider::GitHub) = options.emit_ci {
+            files.push((".github/workflows/aiken.yml".to_string(), FileKind::Ci));
+        }
+
+        if !(options.template.is_composed() && options.single_file) {
+            files.push((format!("{}/types.ak", lib_prefix), FileKind::Types));
+        }
+        if matches!(options.template, Template::ReferralSystem | Template::MerkleAllowlistMint) {
+            files.push((format!("{}/validation.ak", lib_prefix), FileKind::Lib));
+        }
+        if options.template == Template::ReferralSystem {
+            for suffix in ["mint", "treasury"] {
+                files.push((
+                    format!("validators/{}_{}.ak", options.validator_name, suffix),
+                    FileKind::Validator,
+                ));
+            }
+        } else {
+            files.push((
+                format!("validators/{}.ak", options.validator_name),
+                FileKind::Validator,
+            ));
+        }
+
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        files
+            .into_iter()
+            .map(|(path, kind)| PlannedFile { path, kind })
+            .collect()
+    }
+
+    /// Render only `.aikido.toml`, for adding kaido's aikido configuration to an existing project.
+    /// The template takes no project options, so it needs no `GenerateOptions`.
+    pub fn render_aikido_config(&self) -> Result<GeneratedFile> {
+        Ok(GeneratedFile {
+            path: ".aikido.toml".to_string(),
+            content: self.tera.render("base/.aikido.toml", &Context::new())?,
+        })
+    }
+
     /// Render a complete Aiken project (pure computation, returns file contents)
     pub fn render(&self, options: &GenerateOptions) -> Result<RenderResult> {
         let ctx = self.build_context(options);
@@ -257,14 +551,28 @@ impl ProjectGenerator {
             path: "aiken.toml".to_string(),
             content: aiken_toml,
         });
-        let aikido_toml = self.tera.render("base/.aikido.toml", &ctx)?;
+        files.push(self.render_aikido_config()?);
         files.push(GeneratedFile {
-            path: ".aikido.toml".to_string(),
-            content: aikido_toml,
+            path: ".editorconfig".to_string(),
+            content: self.tera.render("base/.editorconfig", &ctx)?,
         });
+        if options.emit_lock {
+            let aiken_lock = self.tera.render("base/aiken.lock", &ctx)?;
+            files.push(GeneratedFile {
+                path: "aiken.lock".to_string(),
+                content: aiken_lock,
+            });
+        }
+        if let Some(CiProvider::GitHub) = options.emit_ci {
+            let workflow = self.tera.render("base/github_ci.yml", &ctx)?;
+            files.push(GeneratedFile {
+                path: ".github/workflows/aiken.yml".to_string(),
+                content: workflow,
+            });
+        }
 
-        // Custom templates handle their own rendering
-        if options.template == Template::Custom {
+        // Composed templates (custom, token lock) render through compose
+        if options.template.is_composed() {
             return self.render_custom(options, &lib_prefix, &files);
         }
 
@@ -278,15 +586,17 @@ impl ProjectGenerator {
             content: types_content,
         });
 
-        // Template-specific extra files
-        if options.template == Template::ReferralSystem {
-            // Validation helpers (lib file)
+        // Validation helpers (lib file)
+        if matches!(options.template, Template::ReferralSystem | Template::MerkleAllowlistMint) {
             let validation_content = self.tera.render(&format!("{}/validation.ak", slug), &ctx)?;
             files.push(GeneratedFile {
                 path: format!("{}/validation.ak", lib_prefix),
                 content: validation_content,
             });
+        }
 
+        // Template-specific extra files
+        if options.template == Template::ReferralSystem {
             // Mint validator
             let mint_content = self.tera.render(&format!("{}/mint_validator.ak", slug), &ctx)?;
             files.push(GeneratedFile {
@@ -309,10 +619,67 @@ impl ProjectGenerator {
             });
         }
 
-        Ok(RenderResult {
-            files,
-            template: options.template,
-        })
+        ensure_declarations(&files)?;
+        let param_descriptions: Vec<(String, String)> = options
+            .template
+            .param_descriptions()
+            .iter()
+            .map(|(name, description)| (name.to_string(), description.to_string()))
+            .collect();
+        add_validator_headers(
+            &mut files,
+            options.template,
+            &options.security_properties(),
+            &param_descriptions,
+        );
+        let mut validator_params: Vec<(String, String)> = Vec::new();
+        for file in files.iter().filter(|f| f.path.starts_with("validators/")) {
+            for param in declared_params(&file.content) {
+                if !validator_params.iter().any(|(name, _)| *name == param.0) {
+                    validator_params.push(param);
+                }
+            }
+        }
+        let meta = GenerationMeta::new(Vec::new(), validator_params);
+        Ok(RenderResult::sorted(files, options.template, Diagnostics::default(), meta))
+    }
+
+    /// The parameters of every validator `options` renders, in declaration order: the composed
+    /// parameters for custom, the template's known parameters for built-ins
+    pub fn validator_params(&self, options: &GenerateOptions) -> Result<Vec<ValidatorParams>> {
+        let descriptions: Vec<(String, String)> = if options.template.is_composed() {
+            compose::compose_options(options)?.param_descriptions()
+        } else {
+            options
+                .template
+                .param_descriptions()
+                .iter()
+                .map(|(name, description)| (name.to_string(), description.to_string()))
+                .collect()
+        };
+        let result = self.render(options)?;
+        Ok(result
+            .files
+            .iter()
+            .filter(|f| f.path.starts_with("validators/"))
+            .map(|file| ValidatorParams {
+                path: file.path.clone(),
+                params: declared_params(&file.content)
+                    .into_iter()
+                    .map(|(name, aiken_type)| {
+                        let description = descriptions
+                            .iter()
+                            .find(|(n, _)| *n == name)
+                            .map(|(_, d)| d.clone());
+                        ValidatorParam {
+                            name,
+                            aiken_type,
+                            description,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect())
     }
 
     /// Render TypeScript SDK files
@@ -341,6 +708,13 @@ impl ProjectGenerator {
             content: tsconfig,
         });
 
+        // Shared script hash/address derivation every client imports
+        let address = self.tera.render("sdk_base/address.ts", &ctx)?;
+        files.push(GeneratedFile {
+            path: "sdk/src/address.ts".to_string(),
+            content: address,
+        });
+
         // Render template-specific SDK files
         for file in &["types.ts", "serialization.ts", "client.ts", "index.ts"] {
             let template_name = format!("{}/sdk/{}", slug, file);
@@ -351,10 +725,57 @@ impl ProjectGenerator {
             });
         }
 
-        Ok(RenderResult {
+        Ok(RenderResult::sorted(
             files,
-            template: options.template,
-        })
+            options.template,
+            Diagnostics::default(),
+            GenerationMeta::new(Vec::new(), Vec::new()),
+        ))
+    }
+
+    /// Render Python (pycardano) SDK files
+    pub fn render_sdk_py(&self, options: &GenerateOptions) -> Result<RenderResult> {
+        if !options.template.supports_sdk_lang(SdkLang::Python) {
+            return Err(KaidoError::InvalidOption(format!(
+                "Python SDK is not available for '{}' template yet",
+                options.template.slug()
+            )));
+        }
+
+        let ctx = self.build_context(options);
+        let slug = options.template.slug();
+        let mut files = Vec::new();
+
+        let pyproject = self.tera.render("sdk_py_base/pyproject.toml", &ctx)?;
+        files.push(GeneratedFile {
+            path: "sdk_py/pyproject.toml".to_string(),
+            content: pyproject,
+        });
+
+        let package_dir = format!("sdk_py/src/{}_sdk", options.module_name);
+        for file in &["types.py", "serialization.py", "__init__.py"] {
+            let template_name = format!("{}/sdk_py/{}", slug, file);
+            let content = self.tera.render(&template_name, &ctx)?;
+            files.push(GeneratedFile {
+                path: format!("{}/{}", package_dir, file),
+                content,
+            });
+        }
+
+        Ok(RenderResult::sorted(
+            files,
+            options.template,
+            Diagnostics::default(),
+            GenerationMeta::new(Vec::new(), Vec::new()),
+        ))
+    }
+
+    /// Render SDK files for the requested language
+    pub fn render_sdk_lang(&self, options: &GenerateOptions, lang: SdkLang) -> Result<RenderResult> {
+        match lang {
+            SdkLang::TypeScript => self.render_sdk(options),
+            SdkLang::Python => self.render_sdk_py(options),
+        }
     }
 
     /// Render a custom composable validator project
@@ -378,6 +799,7 @@ impl ProjectGenerator {
             &options.datum_fields,
             &options.redeemer_actions,
             &options.validator_name,
+            &compose::ComposeOptions::from(options),
         )?;
 
         // Build context
@@ -433,6 +855,7 @@ impl ProjectGenerator {
             type_names.join(", ")
         );
         ctx.insert("types_import", &types_import);
+        ctx.insert("single_file", &options.single_file);
 
         // Composed params
         let params_str: Vec<String> = composed.validator_params.iter()
@@ -450,12 +873,16 @@ impl ProjectGenerator {
         ctx.insert("composed_test_helpers", &composed.test_helpers);
         ctx.insert("composed_test_cases", &composed.test_cases);
 
-        // Render types file
+        // Render types file, or inline the types in place of the import
         let types_content = self.tera.render("custom/types.ak", &ctx)?;
-        files.push(GeneratedFile {
-            path: format!("{}/types.ak", lib_prefix),
-            content: types_content,
-        });
+        if options.single_file {
+            ctx.insert("types_import", types_content.trim_end());
+        } else {
+            files.push(GeneratedFile {
+                path: format!("{}/types.ak", lib_prefix),
+                content: types_content,
+            });
+        }
 
         // Render validator file
         let validator_content = self.tera.render("custom/validator.ak", &ctx)?;
@@ -464,10 +891,22 @@ impl ProjectGenerator {
             content: validator_content,
         });
 
-        Ok(RenderResult {
+        ensure_declarations(&files)?;
+        let properties: Vec<String> = resolved
+            .iter()
+            .map(|f| format!("{}: {}", f.name(), f.description()))
+            .collect();
+        add_validator_headers(&mut files, options.template, &properties, &composed.param_descriptions());
+        let meta = GenerationMeta::new(
+            resolved.iter().map(|f| f.name().to_string()).collect(),
+            composed.validator_params.clone(),
+        );
+        Ok(RenderResult::sorted(
             files,
-            template: Template::Custom,
-        })
+            options.template,
+            composed.diagnostics,
+            meta,
+        ))
     }
 
     fn build_context(&self, options: &GenerateOptions) -> Context {
@@ -479,6 +918,13 @@ impl ProjectGenerator {
         ctx.insert("module_name", &options.module_name);
         ctx.insert("description", &options.description);
         ctx.insert("validator_name", &options.validator_name);
+        ctx.insert("stdlib_version", &options.stdlib_version);
+        ctx.insert("aiken_version", AIKEN_VERSION);
+        ctx.insert("network", options.network.slug());
+        // Property tests use aiken-lang/fuzz, so the project depends on it
+        let fuzz_version = (options.template.is_composed() && options.property_tests)
+            .then_some(FUZZ_VERSION);
+        ctx.insert("fuzz_version", &fuzz_version);
 
         // Simple Mint fields
         if let Some(ref name) = options.token_name {
@@ -493,10 +939,137 @@ impl ProjectGenerator {
         ctx.insert("cancellable", &options.cancellable);
         ctx.insert("partial_claim", &options.partial_claim);
 
+        // NFT Marketplace fields
+        ctx.insert("royalty", &options.royalty);
+
         ctx
     }
 }
 
+/// The kaido version a validator's module header records (see `add_validator_headers`), if any
+pub fn recorded_kaido_version(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .take_while(|line| line.starts_with("////"))
+        .find_map(|line| line.strip_prefix("//// Generated by kaido "))
+        .map(str::trim)
+}
+
+/// Reject a rendered `.ak` module that declares nothing, e.g. because a template conditional
+/// excluded its whole body; aiken would otherwise fail on it much later and less clearly
+fn ensure_declarations(files: &[GeneratedFile]) -> Result<()> {
+    let declares = |content: &str| {
+        content.lines().any(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix("pub ").unwrap_or(line);
+            let line = line.strip_prefix("opaque ").unwrap_or(line);
+            ["validator ", "fn ", "type "].iter().any(|kw| line.starts_with(kw))
+        })
+    };
+    match files
+        .iter()
+        .find(|f| f.path.ends_with(".ak") && !declares(&f.content))
+    {
+        Some(file) => Err(KaidoError::EmptyRender(file.path.clone())),
+        None => Ok(()),
+    }
+}
+
+/// Start every rendered validator with a standard `////` module header: the template, the kaido
+/// version, the security properties enforced and each validator parameter with its description
+fn add_validator_headers(
+    files: &mut [GeneratedFile],
+    template: Template,
+    properties: &[String],
+    param_descriptions: &[(String, String)],
+) {
+    for file in files.iter_mut().filter(|f| f.path.starts_with("validators/")) {
+        let mut header = format!(
+            "//// Template: {}\n//// Generated by kaido {}\n////\n",
+            template.slug(),
+            KAIDO_VERSION
+        );
+        if properties.is_empty() {
+            header.push_str("//// Security properties: none\n");
+        } else {
+            header.push_str("//// Security properties:\n");
+            for property in properties {
+                header.push_str(&format!("//// - {}\n", property));
+            }
+        }
+        header.push_str("////\n");
+        let params = declared_params(&file.content);
+        if params.is_empty() {
+            header.push_str("//// Parameters: none\n");
+        } else {
+            header.push_str("//// Parameters:\n");
+            for (name, ty) in params {
+                match param_descriptions.iter().find(|(n, _)| *n == name) {
+                    Some((_, description)) => {
+                        header.push_str(&format!("//// - {}: {} — {}\n", name, ty, description))
+                    }
+                    None => header.push_str(&format!("//// - {}: {}\n", name, ty)),
+                }
+            }
+        }
+        file.content = format!("{}\n{}", header, file.content);
+    }
+}
+
+/// The `(name, type)` parameters of the first validator declared in `content`, read from its
+/// signature so that option-dependent parameters (e.g. `lock_after`) are listed only when present
+fn declared_params(content: &str) -> Vec<(String, String)> {
+    let Some(start) = content
+        .match_indices("validator ")
+        .map(|(i, _)| i)
+        .find(|&i| i == 0 || content[..i].ends_with('\n'))
+    else {
+        return Vec::new();
+    };
+    let signature = &content[start..];
+    let signature = &signature[..signature.find('{').unwrap_or(signature.len())];
+    let (Some(open), Some(close)) = (signature.find('('), signature.rfind(')')) else {
+        return Vec::new();
+    };
+
+    // Split on top-level commas: types like `Pairs<A, B>` contain their own
+    let mut params = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for ch in signature[open + 1..close].chars().chain([',']) {
+        match ch {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                if let Some((name, ty)) = current.split_once(':') {
+                    params.push((name.trim().to_string(), ty.trim().to_string()));
+                }
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    params
+}
+
+/// Files under `dir/rel`, recursively, as paths relative to `dir`
+fn template_files(dir: &Path, rel: &Path) -> Result<Vec<std::path::PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir.join(rel))? {
+        let entry = entry?;
+        let path = rel.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            files.extend(template_files(dir, &path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,6 +1090,278 @@ mod tests {
         assert!(toml.content.contains("v3.0.0"));
     }
 
+    #[test]
+    fn test_render_simple_mint_validator_header() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::simple_mint("myorg", "my-token", "MyToken", "MYTOKEN", true);
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path.starts_with("validators/")).unwrap();
+        let expected = format!(
+            "//// Template: simple_mint\n//// Generated by kaido {}\n////\n//// Security properties:\n//// - Minting requires the admin_pkh signature\n",
+            KAIDO_VERSION
+        );
+        assert!(validator.content.starts_with(&expected));
+        assert!(validator.content.contains("//// - Minting is disabled after `lock_after`\n"));
+        assert!(validator.content.contains("//// Parameters:\n//// - admin_pkh: ByteArray — key hash whose signature authorizes minting\n//// - lock_after: Int — POSIX time (ms) after which minting is disabled\n\nuse "));
+
+        assert_eq!(recorded_kaido_version(&validator.content), Some(KAIDO_VERSION));
+        assert_eq!(recorded_kaido_version("use aiken/collection/list\n// Generated by kaido 0.0.1\n"), None);
+    }
+
+    #[test]
+    fn test_every_builtin_validator_parameter_is_described() {
+        let gen = ProjectGenerator::new().unwrap();
+        for &template in Template::all() {
+            if template.is_composed() {
+                continue;
+            }
+            let opts = crate::generator::build_options(template, "myorg", "my-project", &Default::default()).unwrap();
+            let result = gen.render(&opts).unwrap();
+            for file in result.files.iter().filter(|f| f.path.starts_with("validators/")) {
+                assert!(file.content.starts_with(&format!("//// Template: {}\n", template.slug())));
+                for (name, ty) in declared_params(&file.content) {
+                    assert!(
+                        file.content.contains(&format!("//// - {}: {} — ", name, ty)),
+                        "{}: parameter '{}' has no description",
+                        file.path,
+                        name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validator_params_lists_custom_and_builtin_params_in_order() {
+        use crate::features::types::{DatumField, RedeemerAction};
+
+        let gen = ProjectGenerator::new().unwrap();
+        let custom = GenerateOptions::custom(
+            "myorg",
+            "my-vault",
+            "spend",
+            vec![DatumField { name: "admin".to_string(), aiken_type: "ByteArray".to_string() }],
+            vec![RedeemerAction { name: "Withdraw".to_string(), fields: vec![] }],
+            vec!["signature-auth".to_string(), "datum-continuity".to_string(), "bounded-operations".to_string()],
+        );
+        let validators = gen.validator_params(&custom).unwrap();
+        assert_eq!(validators.len(), 1);
+        assert_eq!(validators[0].path, "validators/my_vault_validator.ak");
+        let params: Vec<(&str, &str, Option<&str>)> = validators[0]
+            .params
+            .iter()
+            .map(|p| (p.name.as_str(), p.aiken_type.as_str(), p.description.as_deref()))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("admin_pkh", "ByteArray", Some("added by signature-auth")),
+                ("min_lovelace", "Int", Some("added by bounded-operations")),
+            ]
+        );
+
+        let referral = GenerateOptions::referral_system("myorg", "my-referral");
+        let validators = gen.validator_params(&referral).unwrap();
+        assert_eq!(validators.len(), 2);
+        assert!(validators
+            .iter()
+            .flat_map(|v| &v.params)
+            .all(|p| p.description.is_some()));
+    }
+
+    #[test]
+    fn test_render_meta_lists_resolved_features_and_params() {
+        use crate::features::types::{DatumField, RedeemerAction};
+
+        let gen = ProjectGenerator::new().unwrap();
+        let custom = GenerateOptions::custom(
+            "myorg",
+            "my-vault",
+            "spend",
+            vec![DatumField { name: "admin".to_string(), aiken_type: "ByteArray".to_string() }],
+            vec![RedeemerAction { name: "Withdraw".to_string(), fields: vec![] }],
+            vec!["signature-auth".to_string(), "bounded-operations".to_string()],
+        );
+        let meta = gen.render(&custom).unwrap().meta;
+        assert_eq!(meta.kaido_version, KAIDO_VERSION);
+        assert!(meta.generated_at.is_some());
+        // bounded-operations pulls in datum-continuity; composition order, not selection order
+        assert_eq!(
+            meta.resolved_features,
+            vec!["signature-auth", "datum-continuity", "bounded-operations"]
+        );
+        assert_eq!(
+            meta.validator_params,
+            vec![
+                ("admin_pkh".to_string(), "ByteArray".to_string()),
+                ("min_lovelace".to_string(), "Int".to_string()),
+            ]
+        );
+
+        // Built-in templates compose nothing; both referral validators take admin_pkh, listed once
+        let meta = gen.render(&GenerateOptions::referral_system("myorg", "my-referral")).unwrap().meta;
+        assert!(meta.resolved_features.is_empty());
+        let names: Vec<&str> = meta.validator_params.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names.iter().filter(|n| **n == "admin_pkh").count(), 1);
+        assert!(names.contains(&"own_policy_id"));
+    }
+
+    #[test]
+    fn test_render_paths_referral_system() {
+        let opts = GenerateOptions::referral_system("myorg", "my-referral");
+        let planned = ProjectGenerator::render_paths(&opts);
+
+        let validators: Vec<&str> = planned
+            .iter()
+            .filter(|f| f.kind == FileKind::Validator)
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(
+            validators,
+            vec![
+                format!("validators/{}_mint.ak", opts.validator_name),
+                format!("validators/{}_treasury.ak", opts.validator_name),
+            ]
+        );
+        assert!(planned.contains(&PlannedFile {
+            path: "lib/myorg/my_referral/validation.ak".to_string(),
+            kind: FileKind::Lib,
+        }));
+    }
+
+    #[test]
+    fn test_render_paths_match_render() {
+        let gen = ProjectGenerator::new().unwrap();
+        let mut all: Vec<GenerateOptions> = Template::all()
+            .iter()
+            .filter(|t| **t != Template::Custom)
+            .map(|t| {
+                crate::generator::build_options(*t, "myorg", "my-project", &Default::default())
+                    .unwrap()
+            })
+            .collect();
+        let mut custom = GenerateOptions::custom(
+            "myorg",
+            "my-project",
+            "spend",
+            features::types::parse_datum_fields("owner:ByteArray").unwrap(),
+            features::types::parse_redeemer_actions("Claim").unwrap(),
+            vec!["signature-auth".to_string()],
+        );
+        all.push(custom.clone());
+        custom.single_file = true;
+        custom.emit_lock = true;
+        custom.emit_ci = Some(CiProvider::GitHub);
+        all.push(custom);
+
+        for opts in all {
+            let rendered: Vec<String> =
+                gen.render(&opts).unwrap().files.into_iter().map(|f| f.path).collect();
+            let planned: Vec<String> = ProjectGenerator::render_paths(&opts)
+                .into_iter()
+                .map(|f| f.path)
+                .collect();
+            assert_eq!(planned, rendered, "{}", opts.template.slug());
+        }
+    }
+
+    #[test]
+    fn test_render_emit_lock() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::escrow("myorg", "my-escrow");
+        let result = gen.render(&opts).unwrap();
+        assert!(!result.files.iter().any(|f| f.path == "aiken.lock"));
+
+        let mut opts = GenerateOptions::escrow("myorg", "my-escrow");
+        opts.emit_lock = true;
+        opts.stdlib_version = "v3.0.1".to_string();
+        let result = gen.render(&opts).unwrap();
+
+        let lock = result.files.iter().find(|f| f.path == "aiken.lock").unwrap();
+        assert!(lock.content.contains("name = \"aiken-lang/stdlib\""));
+        assert!(lock.content.contains("version = \"v3.0.1\""));
+        let toml = result.files.iter().find(|f| f.path == "aiken.toml").unwrap();
+        assert!(toml.content.contains("version = \"v3.0.1\""));
+    }
+
+    #[test]
+    fn test_render_emit_ci_github() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::escrow("myorg", "my-escrow");
+        let result = gen.render(&opts).unwrap();
+        assert!(!result.files.iter().any(|f| f.path.starts_with(".github/")));
+
+        let mut opts = GenerateOptions::escrow("myorg", "my-escrow");
+        opts.emit_ci = Some(CiProvider::GitHub);
+        let result = gen.render(&opts).unwrap();
+
+        let workflow = result
+            .files
+            .iter()
+            .find(|f| f.path == ".github/workflows/aiken.yml")
+            .unwrap();
+        assert!(workflow
+            .content
+            .contains(&format!("version: {}", AIKEN_VERSION)));
+        assert!(workflow.content.contains("run: aiken check"));
+        assert!(workflow.content.contains("if: hashFiles('.aikido.toml') != ''"));
+        let toml = result.files.iter().find(|f| f.path == "aiken.toml").unwrap();
+        assert!(toml
+            .content
+            .contains(&format!("compiler = \"{}\"", AIKEN_VERSION)));
+    }
+
+    #[test]
+    fn test_render_emits_aiken_editorconfig() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::escrow("myorg", "my-escrow");
+        let result = gen.render(&opts).unwrap();
+
+        let editorconfig = result.files.iter().find(|f| f.path == ".editorconfig").unwrap();
+        assert!(editorconfig.content.contains("root = true"));
+        assert!(editorconfig.content.contains("[*.ak]\nindent_style = space\nindent_size = 2\n"));
+    }
+
+    #[test]
+    fn test_template_dir_overrides_embedded_templates_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("base")).unwrap();
+        std::fs::write(
+            dir.path().join("base/aiken.toml.tera"),
+            "name = \"{{ namespace }}/{{ project_name }}\"\n# house style\n",
+        )
+        .unwrap();
+
+        let gen = ProjectGenerator::new_with_overrides(dir.path()).unwrap();
+        let result = gen.render(&GenerateOptions::escrow("myorg", "my-escrow")).unwrap();
+        let toml = result.files.iter().find(|f| f.path == "aiken.toml").unwrap();
+        assert_eq!(toml.content, "name = \"myorg/my-escrow\"\n# house style\n");
+        // Templates the directory doesn't provide stay embedded
+        let validator = result.files.iter().find(|f| f.path.starts_with("validators/")).unwrap();
+        assert!(validator.content.contains("validator my_escrow"));
+
+        std::fs::write(dir.path().join("base/aiken.tml.tera"), "").unwrap();
+        let err = ProjectGenerator::new_with_overrides(dir.path()).err().unwrap().to_string();
+        assert!(err.contains("'base/aiken.tml.tera' in template directory"));
+        assert!(ProjectGenerator::new_with_overrides(&dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_render_rejects_a_module_that_renders_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("escrow")).unwrap();
+        std::fs::write(
+            dir.path().join("escrow/validator.ak.tera"),
+            "{% if false %}validator {{ validator_name }}() {}{% endif %}\n  \n",
+        )
+        .unwrap();
+
+        let gen = ProjectGenerator::new_with_overrides(dir.path()).unwrap();
+        let err = gen.render(&GenerateOptions::escrow("myorg", "my-escrow")).err().unwrap();
+        assert!(matches!(&err, KaidoError::EmptyRender(path) if path == "validators/my_escrow_escrow.ak"));
+    }
+
     #[test]
     fn test_render_simple_mint_with_timelock() {
         let gen = ProjectGenerator::new().unwrap();
@@ -594,7 +1439,7 @@ mod tests {
     #[test]
     fn test_render_nft_marketplace() {
         let gen = ProjectGenerator::new().unwrap();
-        let opts = GenerateOptions::nft_marketplace("myorg", "my-market");
+        let opts = GenerateOptions::nft_marketplace("myorg", "my-market", false);
         let result = gen.render(&opts).unwrap();
 
         let validator = result.files.iter().find(|f| f.path.starts_with("validators/")).unwrap();
@@ -606,6 +1451,45 @@ mod tests {
         let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
         assert!(types.content.contains("ListingDatum"));
         assert!(types.content.contains("MarketplaceRedeemer"));
+        assert!(!types.content.contains("royalty_pkh"));
+        assert!(!validator.content.contains("royalty_lovelace"));
+    }
+
+    #[test]
+    fn test_render_nft_marketplace_royalty() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::nft_marketplace("myorg", "my-market", true);
+        let result = gen.render(&opts).unwrap();
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("royalty_pkh: ByteArray"));
+        assert!(types.content.contains("royalty_bps: Int"));
+
+        let validator = result.files.iter().find(|f| f.path.starts_with("validators/")).unwrap();
+        assert!(validator
+            .content
+            .contains("let royalty_lovelace = datum.price_lovelace * datum.royalty_bps / 10_000"));
+        assert!(validator
+            .content
+            .contains("assets.lovelace_of(o.value) >= royalty_lovelace"));
+        assert!(validator.content.contains("datum.price_lovelace - royalty_lovelace"));
+        assert!(validator.content.contains("test buy_without_royalty_fails()"));
+        // The bps bound gates Buy only, so Delist still works on a bad listing
+        let buy = validator.content.find("Buy -> {").unwrap();
+        let bps_check = validator
+            .content
+            .find("expect datum.royalty_bps >= 0 && datum.royalty_bps <= 10_000")
+            .unwrap();
+        assert!(bps_check > buy && bps_check < validator.content.find("Delist -> {").unwrap());
+        assert!(validator.content.contains("test delist_royalty_above_100_percent_valid()"));
+
+        let sdk = gen.render_sdk(&opts).unwrap();
+        let serialization = sdk
+            .files
+            .iter()
+            .find(|f| f.path.ends_with("serialization.ts"))
+            .unwrap();
+        assert!(serialization.content.contains("{ int: datum.royaltyBps }"));
     }
 
     #[test]
@@ -667,6 +1551,30 @@ mod tests {
         assert!(treasury.content.contains("2_000_000"));
     }
 
+    #[test]
+    fn test_render_validator_name_override() {
+        let gen = ProjectGenerator::new().unwrap();
+        let mut opts = GenerateOptions::escrow("myorg", "my-escrow");
+        opts.validator_name = "deal_keeper".to_string();
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path == "validators/deal_keeper.ak").unwrap();
+        assert!(validator.content.contains("validator deal_keeper("));
+        assert!(validator.content.contains("deal_keeper.spend("));
+    }
+
+    #[test]
+    fn test_render_validator_name_override_referral_suffixes() {
+        let gen = ProjectGenerator::new().unwrap();
+        let mut opts = GenerateOptions::referral_system("myorg", "my-referral");
+        opts.validator_name = "invite".to_string();
+        let result = gen.render(&opts).unwrap();
+
+        assert!(result.files.iter().any(|f| f.path == "validators/invite_mint.ak"));
+        let treasury = result.files.iter().find(|f| f.path == "validators/invite_treasury.ak").unwrap();
+        assert!(treasury.content.contains("validator invite_treasury("));
+    }
+
     #[test]
     fn test_render_lib_path_structure() {
         let gen = ProjectGenerator::new().unwrap();
@@ -718,6 +1626,35 @@ mod tests {
         assert!(validator.content.contains("signature-auth"));
     }
 
+    #[test]
+    fn test_render_custom_list_redeemer_field() {
+        use crate::features::types::{DatumField, RedeemerAction};
+
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::custom(
+            "myorg",
+            "my-custom",
+            "spend",
+            vec![
+                DatumField { name: "admin".to_string(), aiken_type: "ByteArray".to_string() },
+            ],
+            vec![
+                RedeemerAction {
+                    name: "Claim".to_string(),
+                    fields: vec![("proof".to_string(), "List<ByteArray>".to_string())],
+                },
+            ],
+            vec!["signature-auth".to_string()],
+        );
+        let result = gen.render(&opts).unwrap();
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("proof: List<ByteArray>"));
+        let validator = result.files.iter().find(|f| f.path.starts_with("validators/")).unwrap();
+        assert!(validator.content.contains("Claim { proof: [] }"));
+        assert!(!validator.content.contains("todo"));
+    }
+
     #[test]
     fn test_render_custom_vesting_like() {
         use crate::features::types::{DatumField, RedeemerAction};
@@ -782,6 +1719,60 @@ mod tests {
         assert!(types.content.contains("amount: Int"));
     }
 
+    #[test]
+    fn test_render_custom_explain_annotations() {
+        use crate::features::types::{DatumField, RedeemerAction};
+
+        let gen = ProjectGenerator::new().unwrap();
+        let mut opts = GenerateOptions::custom(
+            "myorg",
+            "my-vault",
+            "spend",
+            vec![DatumField { name: "admin".to_string(), aiken_type: "ByteArray".to_string() }],
+            vec![RedeemerAction { name: "Withdraw".to_string(), fields: vec![] }],
+            vec!["signature-auth".to_string(), "datum-continuity".to_string(), "bounded-operations".to_string()],
+        );
+        opts.explain = true;
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path.starts_with("validators/")).unwrap();
+        assert!(validator.content.contains("// [feature: signature-auth] Require a specific signer in extra_signatories"));
+        assert!(validator.content.contains("// [feature: datum-continuity]"));
+        assert!(validator.content.contains("// [feature: bounded-operations] Enforce minimum lovelace floor on continuing output"));
+        // Annotations are comments only: every annotated line is a `//` line
+        assert!(validator.content.lines().filter(|l| l.contains("[feature:")).all(|l| l.trim_start().starts_with("//")));
+    }
+
+    #[test]
+    fn test_render_custom_property_tests_depend_on_fuzz() {
+        use crate::features::types::{DatumField, RedeemerAction};
+
+        let gen = ProjectGenerator::new().unwrap();
+        let mut opts = GenerateOptions::custom(
+            "myorg",
+            "my-vault",
+            "spend",
+            vec![DatumField { name: "owner".to_string(), aiken_type: "ByteArray".to_string() }],
+            vec![RedeemerAction { name: "Withdraw".to_string(), fields: vec![] }],
+            vec!["signature-auth".to_string(), "value-preservation".to_string()],
+        );
+        let toml = |opts: &GenerateOptions| {
+            let result = gen.render(opts).unwrap();
+            result.files.into_iter().find(|f| f.path == "aiken.toml").unwrap().content
+        };
+        assert!(!toml(&opts).contains("aiken-lang/fuzz"));
+
+        opts.property_tests = true;
+        opts.emit_lock = true;
+        let result = gen.render(&opts).unwrap();
+        let dependency = format!("name = \"aiken-lang/fuzz\"\nversion = \"{}\"", FUZZ_VERSION);
+        assert!(toml(&opts).contains(&dependency));
+        let lock = result.files.iter().find(|f| f.path == "aiken.lock").unwrap();
+        assert_eq!(lock.content.matches(&dependency).count(), 2);
+        let validator = result.files.iter().find(|f| f.path.starts_with("validators/")).unwrap();
+        assert!(validator.content.contains("test withdraw_value_preserved_property("));
+    }
+
     #[test]
     fn test_render_custom_mint() {
         use crate::features::types::RedeemerAction;
@@ -879,12 +1870,199 @@ mod tests {
         assert!(validator.content.contains("TopUp"));
         assert!(validator.content.contains("is_entirely_after"));
         assert!(validator.content.contains("claimed_amount"));
+        assert!(validator.content.contains("claimable_amount(datum, now)"));
+        assert!(validator.content.contains("test claim_half_valid()"));
+        assert!(validator.content.contains("test claim_too_much_fails() fail"));
 
         let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
         assert!(types.content.contains("StreamDatum"));
         assert!(types.content.contains("StreamRedeemer"));
     }
 
+    #[test]
+    fn test_render_hash_timelock() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::hash_timelock("myorg", "my-swap");
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path == "validators/my_swap_htlc.ak").unwrap();
+        assert!(validator.content.contains("validator my_swap_htlc"));
+        assert!(validator.content.contains("expect blake2b_256(secret) == datum.hash_lock"));
+        assert!(validator.content.contains("interval.is_entirely_before(self.validity_range, datum.timeout)"));
+        assert!(validator.content.contains("interval.is_entirely_after(self.validity_range, datum.timeout)"));
+        assert!(validator.content.contains("pays_at_least(self.outputs, datum.receiver, locked)"));
+        assert!(validator.content.contains("test claim_wrong_secret_fails() fail"));
+        assert!(validator.content.contains("test refund_before_timeout_fails() fail"));
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("HtlcDatum"));
+        assert!(types.content.contains("Claim { secret: ByteArray }"));
+        assert!(types.content.contains("Refund"));
+    }
+
+    #[test]
+    fn test_render_payroll() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::payroll("myorg", "my-payroll");
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path == "validators/my_payroll_payroll.ak").unwrap();
+        assert!(validator.content.contains("Pay ->"));
+        assert!(validator.content.contains("paid_to(self.outputs, recipient) >= owed_to("));
+        assert!(validator.content.contains("let (recipient, _) = entry"));
+        assert!(validator.content.contains("Reclaim ->"));
+        assert!(validator.content.contains("expect list.has(self.extra_signatories, datum.admin)"));
+        assert!(validator.content.contains("interval.is_entirely_after(self.validity_range, datum.expiry)"));
+        assert!(validator.content.contains("test pay_duplicate_recipient_underpaid_fails() fail"));
+        assert!(validator.content.contains("test reclaim_before_expiry_fails() fail"));
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("recipients: List<(ByteArray, Int)>"));
+        assert!(types.content.contains("expiry: Int"));
+    }
+
+    #[test]
+    fn test_render_faucet() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::faucet("myorg", "my-faucet");
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path == "validators/my_faucet_faucet.ak").unwrap();
+        assert!(validator.content.contains("Claim { claimant } ->"));
+        assert!(validator.content.contains("expect cooled_down(datum.last_claim, claimant, now, datum.cooldown)"));
+        assert!(validator.content.contains("pkh != claimant || now >= last + cooldown"));
+        assert!(validator.content.contains("logged == (claimant, now),"));
+        assert!(validator.content.contains("expect Finite(now) = self.validity_range.upper_bound.bound_type"));
+        assert!(validator.content.contains("expect now - earliest <= datum.cooldown"));
+        assert!(validator.content.contains("pruned(others, kept, earliest, datum.cooldown),"));
+        assert!(validator.content.contains("test claim_backdated_range_fails() fail"));
+        assert!(validator.content.contains("last + cooldown <= now && pruned(rest, kept, now, cooldown)"));
+        assert!(validator.content.contains("test claim_prunes_stale_entry_valid()"));
+        assert!(validator.content.contains("test claim_prunes_active_entry_fails() fail"));
+        assert!(validator.content.contains("Refill ->"));
+        assert!(validator.content.contains("expect lovelace_of(cont_output.value) > locked"));
+        assert!(validator.content.contains("test claim_during_cooldown_fails() fail"));
+        assert!(validator.content.contains("test claim_after_cooldown_valid()"));
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("last_claim: List<(ByteArray, Int)>"));
+        assert!(types.content.contains("cooldown: Int"));
+    }
+
+    #[test]
+    fn test_render_merkle_allowlist_mint() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::merkle_allowlist_mint("myorg", "my-drop");
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path == "validators/my_drop_allowlist.ak").unwrap();
+        assert!(validator.content.contains("use myorg/my_drop/validation.{leaf_hash, node_hash, verify}"));
+        assert!(validator.content.contains("OpenMint ->"));
+        assert!(validator.content.contains("MintAllowed { claimant, proof } ->"));
+        assert!(validator.content.contains("expect verify(merkle_root, leaf, proof)"));
+        assert!(validator.content.contains("minted == [Pair(leaf, claim_quantity)]"));
+        assert!(validator.content.contains("CloseMint ->"));
+        assert!(validator.content.contains("minted == [Pair(state_token, -1)]"));
+        assert!(validator.content.contains("test mint_not_allowlisted_fails() fail"));
+        assert!(validator.content.contains("test mint_over_quantity_fails() fail"));
+
+        let validation = result.files.iter().find(|f| f.path == "lib/myorg/my_drop/validation.ak").unwrap();
+        assert!(validation.content.contains("pub fn verify(root: ByteArray, leaf: ByteArray, proof: List<ProofStep>) -> Bool"));
+        assert!(result.files.iter().any(|f| f.path == "lib/myorg/my_drop/types.ak"));
+    }
+
+    #[test]
+    fn test_render_zk_gate() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::zk_gate("myorg", "my-gate");
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path == "validators/my_gate_zk_gate.ak").unwrap();
+        assert!(validator.content.contains("bls12_381_final_verify, bls12_381_g1_scalar_mul"));
+        assert!(validator.content.contains("bls12_381_miller_loop, bls12_381_mul_miller_loop_result,"));
+        assert!(validator.content.contains("use aiken/crypto/bls12_381/g1"));
+        assert!(validator.content.contains("validator my_gate_zk_gate(vk: VerificationKey)"));
+        assert!(validator.content.contains("VerifyProof { a, b, c } ->"));
+        assert!(validator.content.contains("groth16_verify(vk, datum.statement, a, b, c)"));
+        assert!(validator.content.contains("bls12_381_final_verify(lhs, rhs)"));
+        assert!(validator.content.contains("test verify_proof_tampered_fails() fail"));
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("VerifyProof { a: ByteArray, b: ByteArray, c: ByteArray }"));
+    }
+
+    #[test]
+    fn test_render_dutch_auction() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::dutch_auction("myorg", "my-sale");
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path == "validators/my_sale_dutch_auction.ak").unwrap();
+        assert!(validator.content.contains("validator my_sale_dutch_auction {"));
+        assert!(validator.content.contains("expect Finite(now) = self.validity_range.lower_bound.bound_type"));
+        assert!(validator.content.contains("paid_to(self.outputs, datum.seller) >= current_price(datum, now)"));
+        assert!(validator.content.contains("if now <= datum.start_time {\n    datum.start_price"));
+        assert!(validator.content.contains("} else if now >= datum.end_time {\n    datum.floor_price"));
+        assert!(validator.content.contains("datum.start_price - spread * elapsed / duration"));
+        assert!(validator.content.contains("interval.is_entirely_after(self.validity_range, datum.end_time)"));
+        assert!(validator.content.contains("expect list.has(self.extra_signatories, datum.seller)"));
+        assert!(validator.content.contains("current_price(datum, test_mid_time) == test_mid_price"));
+        assert!(validator.content.contains("test buy_below_current_price_fails() fail"));
+        assert!(validator.content.contains("test reclaim_before_end_fails() fail"));
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("pub type DutchAuctionDatum {"));
+        assert!(types.content.contains("floor_price: Int"));
+        assert!(types.content.contains("  Buy\n  /// Seller takes the unsold item back after `end_time`\n  Reclaim"));
+    }
+
+    #[test]
+    fn test_render_oracle_feed() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::oracle_feed("myorg", "my-feed");
+        let result = gen.render(&opts).unwrap();
+
+        let validator = result.files.iter().find(|f| f.path == "validators/my_feed_oracle_feed.ak").unwrap();
+        assert!(validator.content.contains("validator my_feed_oracle_feed(oracle_operator: ByteArray) {"));
+        assert!(validator.content.contains("expect list.has(self.extra_signatories, oracle_operator)"));
+        assert!(validator.content.contains("new_datum.timestamp > datum.timestamp,"));
+        assert!(validator.content.contains("new_datum.timestamp <= latest,"));
+        assert!(validator.content.contains("expect script_input_count == 1"));
+        assert!(validator.content.contains("without_lovelace(feed_output.value) == without_lovelace("));
+        assert!(validator.content.contains("test update_backdated_fails() fail"));
+        assert!(validator.content.contains("test update_replayed_timestamp_fails() fail"));
+        assert!(validator.content.contains("test update_without_operator_fails() fail"));
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("pub type OracleFeedDatum {"));
+        assert!(types.content.contains("timestamp: Int"));
+        assert!(types.content.contains("  UpdatePrice\n}"));
+    }
+
+    #[test]
+    fn test_render_token_lock() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::token_lock("myorg", "my-lock");
+        assert_eq!(opts.template, Template::TokenLock);
+        let result = gen.render(&opts).unwrap();
+        assert_eq!(result.template, Template::TokenLock);
+
+        let validator = result.files.iter().find(|f| f.path == "validators/my_lock_token_lock.ak").unwrap();
+        assert!(validator.content.starts_with("//// Template: token_lock\n"));
+        // The datum owner signs; there is no admin_pkh parameter to disagree with it
+        assert!(validator.content.contains("validator my_lock_token_lock() {"));
+        assert!(validator.content.contains("Unlock -> {"));
+        assert!(validator.content.contains("expect list.has(self.extra_signatories, datum.owner)"));
+        assert!(!validator.content.contains("admin_pkh"));
+        assert!(validator.content.contains("interval.is_entirely_after(self.validity_range, datum.unlock_time)"));
+        assert!(validator.content.contains("test unlock_before_deadline_fails() fail"));
+        assert!(validator.content.contains("my_lock_token_lock.spend(Some(test_datum()), Unlock, test_oref(), tx)"));
+
+        let types = result.files.iter().find(|f| f.path.contains("types.ak")).unwrap();
+        assert!(types.content.contains("owner: ByteArray,"));
+        assert!(types.content.contains("unlock_time: Int,"));
+    }
+
     #[test]
     fn test_render_custom_lib_structure() {
         use crate::features::types::{DatumField, RedeemerAction};
@@ -904,6 +2082,70 @@ mod tests {
         assert!(result.files.iter().any(|f| f.path == "lib/myorg/my_custom/types.ak"));
     }
 
+    #[test]
+    fn test_render_custom_single_file() {
+        use crate::features::types::{DatumField, RedeemerAction};
+
+        let gen = ProjectGenerator::new().unwrap();
+        let mut opts = GenerateOptions::custom(
+            "myorg",
+            "my-custom",
+            "spend",
+            vec![DatumField { name: "admin".to_string(), aiken_type: "ByteArray".to_string() }],
+            vec![RedeemerAction { name: "Execute".to_string(), fields: vec![] }],
+            vec!["signature-auth".to_string()],
+        );
+        opts.single_file = true;
+        let result = gen.render(&opts).unwrap();
+
+        assert!(!result.files.iter().any(|f| f.path.starts_with("lib/")));
+        let validator = result.files.iter().find(|f| f.path.starts_with("validators/")).unwrap();
+        assert!(validator.content.contains("pub type CustomDatum {\n  admin: ByteArray,"));
+        assert!(validator.content.contains("pub type CustomRedeemer {\n  Execute\n}"));
+        assert!(!validator.content.contains("/types.{"));
+        assert!(!validator.content.contains("/// Custom types for"));
+    }
+
+    #[test]
+    fn test_render_files_sorted_by_path() {
+        let gen = ProjectGenerator::new().unwrap();
+        let is_sorted = |result: &RenderResult| {
+            result.files.windows(2).all(|w| w[0].path < w[1].path)
+        };
+
+        let opts = GenerateOptions::referral_system("myorg", "my-referral");
+        assert!(is_sorted(&gen.render(&opts).unwrap()));
+        let opts = GenerateOptions::simple_mint("myorg", "my-token", "MyToken", "MY_TOKEN", false);
+        assert!(is_sorted(&gen.render_sdk(&opts).unwrap()));
+        assert!(is_sorted(&gen.render_sdk_lang(&opts, SdkLang::Python).unwrap()));
+    }
+
+    #[test]
+    fn test_render_returns_composition_warnings() {
+        use crate::features::types::{DatumField, RedeemerAction};
+
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::custom(
+            "myorg",
+            "my-custom",
+            "spend",
+            vec![
+                DatumField { name: "owner".to_string(), aiken_type: "ByteArray".to_string() },
+            ],
+            vec![
+                RedeemerAction { name: "Update".to_string(), fields: vec![] },
+            ],
+            vec!["datum-continuity".to_string()],
+        );
+        let result = gen.render(&opts).unwrap();
+        assert!(result.files.iter().any(|f| f.path.starts_with("validators/")));
+        assert!(!result.diagnostics.is_empty());
+        assert_eq!(result.diagnostics.warnings[0].code, "unchecked-action");
+
+        let opts = GenerateOptions::escrow("myorg", "my-escrow");
+        assert!(gen.render(&opts).unwrap().diagnostics.is_empty());
+    }
+
     #[test]
     fn test_render_sdk_supported_template() {
         let gen = ProjectGenerator::new().unwrap();
@@ -916,6 +2158,88 @@ mod tests {
         assert!(result.files.iter().any(|f| f.path == "sdk/src/client.ts"));
     }
 
+    #[test]
+    fn test_render_sdk_emits_shared_address_helper() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::simple_mint("myorg", "my-token", "MyToken", "MY_TOKEN", false);
+        let result = gen.render_sdk(&opts).unwrap();
+        let file = |path: &str| &result.files.iter().find(|f| f.path == path).unwrap().content;
+
+        let address = file("sdk/src/address.ts");
+        assert!(address.contains("export function scriptAddress(\n  blueprint: Blueprint,\n  network: Network,"));
+        assert!(address.contains("export function scriptHash(blueprint: Blueprint"));
+        assert!(file("sdk/src/client.ts").contains("from \"./address.js\";"));
+        assert!(file("sdk/src/index.ts").contains("export { scriptAddress, scriptHash } from \"./address.js\";"));
+
+        // Every TypeScript client derives its hash (and address) through the shared helper
+        for &template in Template::all().iter().filter(|t| t.supports_sdk()) {
+            let opts = crate::generator::build_options(template, "myorg", "my-project", &Default::default()).unwrap();
+            let result = gen.render_sdk(&opts).unwrap();
+            let client = &result.files.iter().find(|f| f.path == "sdk/src/client.ts").unwrap().content;
+            assert!(client.contains("static fromBlueprint("), "{}", template.slug());
+            assert!(client.contains("scriptHash(blueprint"), "{}", template.slug());
+        }
+    }
+
+    #[test]
+    fn test_render_network_in_sdk_client_and_aiken_toml() {
+        let gen = ProjectGenerator::new().unwrap();
+        let mut opts = GenerateOptions::simple_mint("myorg", "my-token", "MyToken", "MY_TOKEN", false);
+        let client = |opts: &GenerateOptions| {
+            let result = gen.render_sdk(opts).unwrap();
+            result.files.into_iter().find(|f| f.path == "sdk/src/client.ts").unwrap().content
+        };
+        assert!(client(&opts).contains("export const NETWORK = \"preview\" as const;"));
+
+        opts.network = crate::templates::Network::Mainnet;
+        assert!(client(&opts).contains("export const NETWORK = \"mainnet\" as const;"));
+        let result = gen.render(&opts).unwrap();
+        let toml = result.files.iter().find(|f| f.path == "aiken.toml").unwrap();
+        assert!(toml.content.contains("# Target network: mainnet"));
+    }
+
+    #[test]
+    fn test_render_sdk_py_simple_mint() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::simple_mint("myorg", "my-token", "MyToken", "MY_TOKEN", false);
+        let result = gen.render_sdk_lang(&opts, SdkLang::Python).unwrap();
+
+        let pyproject = result.files.iter().find(|f| f.path == "sdk_py/pyproject.toml").unwrap();
+        assert!(pyproject.content.contains("pycardano"));
+        assert!(pyproject.content.contains("src/my_token_sdk"));
+
+        let types = result.files.iter().find(|f| f.path == "sdk_py/src/my_token_sdk/types.py").unwrap();
+        assert!(types.content.contains("class Mint(PlutusData)"));
+        assert!(types.content.contains("CONSTR_ID = 1"));
+        assert!(result.files.iter().any(|f| f.path == "sdk_py/src/my_token_sdk/serialization.py"));
+        assert!(result.files.iter().any(|f| f.path == "sdk_py/src/my_token_sdk/__init__.py"));
+    }
+
+    #[test]
+    fn test_render_sdk_py_vesting_cancellable() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::vesting("myorg", "my-vesting", true, false);
+        let result = gen.render_sdk_py(&opts).unwrap();
+
+        let types = result.files.iter().find(|f| f.path.ends_with("types.py")).unwrap();
+        assert!(types.content.contains("class VestingDatum(PlutusData)"));
+        assert!(types.content.contains("lock_until: int"));
+        assert!(types.content.contains("VestingRedeemer = Union[Claim, Cancel]"));
+
+        let opts = GenerateOptions::vesting("myorg", "my-vesting", false, false);
+        let result = gen.render_sdk_py(&opts).unwrap();
+        let types = result.files.iter().find(|f| f.path.ends_with("types.py")).unwrap();
+        assert!(!types.content.contains("class Cancel"));
+    }
+
+    #[test]
+    fn test_render_sdk_py_unsupported_template_returns_clear_error() {
+        let gen = ProjectGenerator::new().unwrap();
+        let opts = GenerateOptions::escrow("myorg", "my-escrow");
+        let err = gen.render_sdk_py(&opts).expect_err("escrow python sdk should be unsupported");
+        assert!(err.to_string().contains("Python SDK is not available"));
+    }
+
     #[test]
     fn test_render_sdk_unsupported_template_returns_clear_error() {
         let gen = ProjectGenerator::new().unwrap();
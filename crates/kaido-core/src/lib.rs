@@ -1,6 +1,9 @@
+pub mod diagnostics;
 pub mod error;
 pub mod features;
 pub mod generator;
+pub mod options;
+pub mod schema;
 pub mod templates;
 
 #[cfg(feature = "wasm")]
@@ -0,0 +1,148 @@
+//! Build `GenerateOptions` from a JSON object (the shape the MCP `kaido_generate` tool,
+//! the WASM `generate` export and `kaido generate --from-stdin` accept).
+
+use serde_json::Value;
+
+use crate::error::{KaidoError, Result};
+use crate::generator::{build_options, validate_options, OptionExtras};
+use crate::templates::{GenerateOptions, Template};
+
+fn str_arg(args: &Value, key: &str) -> Option<String> {
+    args.get(key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+fn bool_arg(args: &Value, key: &str) -> bool {
+    args.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Parse and validate generate options from a JSON object.
+/// Keys other than template/project options (e.g. `output`, `sdk`) are ignored.
+pub fn build_options_from_json(args: &Value) -> Result<GenerateOptions> {
+    let (template, namespace, project_name) = template_and_project(args)?;
+    build_options(template, &namespace, &project_name, &extras_from_json(args))
+}
+
+/// Like `build_options_from_json`, but collects every error instead of stopping at the first
+pub fn validate_options_from_json(args: &Value) -> Vec<KaidoError> {
+    match template_and_project(args) {
+        Ok((template, namespace, project_name)) => {
+            validate_options(template, &namespace, &project_name, &extras_from_json(args))
+        }
+        Err(e) => vec![e],
+    }
+}
+
+fn template_and_project(args: &Value) -> Result<(Template, String, String)> {
+    if !args.is_object() {
+        return Err(KaidoError::InvalidOption(
+            "options must be a JSON object".to_string(),
+        ));
+    }
+
+    let template_str = str_arg(args, "template").unwrap_or_default();
+    let namespace = str_arg(args, "namespace").unwrap_or_default();
+    let project_name = str_arg(args, "project_name").unwrap_or_default();
+
+    let template = template_str
+        .parse::<Template>()
+        .map_err(|_| KaidoError::InvalidOption(format!("Unknown template '{}'", template_str)))?;
+    Ok((template, namespace, project_name))
+}
+
+/// Template-specific and project options from their JSON keys
+pub fn extras_from_json(args: &Value) -> OptionExtras {
+    OptionExtras {
+        token_name: str_arg(args, "token_name"),
+        asset_name: str_arg(args, "asset_name"),
+        time_lock: bool_arg(args, "time_lock"),
+        cancellable: bool_arg(args, "cancellable"),
+        partial_claim: bool_arg(args, "partial_claim"),
+        royalty: bool_arg(args, "royalty"),
+        features: str_arg(args, "features")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.trim().to_string())
+            .collect(),
+        datum: str_arg(args, "datum"),
+        redeemer: str_arg(args, "redeemer"),
+        purpose: str_arg(args, "purpose"),
+        counter_field: str_arg(args, "counter_field"),
+        expiry_field: str_arg(args, "expiry_field"),
+        seed_utxo: str_arg(args, "seed_utxo"),
+        explain: bool_arg(args, "explain"),
+        trace: bool_arg(args, "trace"),
+        property_tests: bool_arg(args, "property_tests"),
+        single_file: bool_arg(args, "single_file"),
+        validator_name: str_arg(args, "validator_name"),
+        stdlib_version: str_arg(args, "stdlib_version"),
+        emit_lock: bool_arg(args, "emit_lock"),
+        network: str_arg(args, "network"),
+        emit_ci: str_arg(args, "emit_ci"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::DEFAULT_STDLIB_VERSION;
+    use serde_json::json;
+
+    #[test]
+    fn maps_json_keys_onto_options() {
+        let options = build_options_from_json(&json!({
+            "template": "custom",
+            "namespace": "myorg",
+            "project_name": "my-validator",
+            "features": "sig, timelock",
+            "datum": "owner:ByteArray,deadline:Int",
+            "redeemer": "Claim,Cancel",
+            "explain": true,
+            "output": "ignored",
+        }))
+        .unwrap();
+        assert_eq!(options.template, Template::Custom);
+        assert_eq!(options.feature_names, vec!["signature-auth", "timelock"]);
+        assert!(options.explain);
+        assert_eq!(options.stdlib_version, DEFAULT_STDLIB_VERSION);
+
+        let options = build_options_from_json(&json!({
+            "template": "mint",
+            "namespace": "myorg",
+            "project_name": "my_token",
+            "time_lock": true,
+            "validator_name": "token_policy",
+        }))
+        .unwrap();
+        assert_eq!(options.asset_name.as_deref(), Some("MY_TOKEN"));
+        assert!(options.time_lock);
+        assert_eq!(options.validator_name, "token_policy");
+    }
+
+    #[test]
+    fn rejects_unknown_template_and_non_objects() {
+        let err = build_options_from_json(
+            &json!({ "template": "nope", "namespace": "o", "project_name": "p" }),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Unknown template 'nope'"));
+        assert!(build_options_from_json(&json!(["escrow"])).is_err());
+    }
+
+    #[test]
+    fn validate_from_json_collects_errors() {
+        let errors = validate_options_from_json(&json!({
+            "template": "custom",
+            "namespace": "myorg",
+            "project_name": "p",
+            "features": "sig,nope",
+        }));
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("Unknown feature 'nope'")));
+        assert!(messages.iter().any(|m| m.contains("datum is required")));
+        assert!(messages.iter().any(|m| m.contains("redeemer is required")));
+        assert_eq!(validate_options_from_json(&json!("x")).len(), 1);
+    }
+}
@@ -0,0 +1,144 @@
+//! JSON Schema for generate options, derived from the template/feature/type enums.
+
+use serde_json::{json, Map, Value};
+
+use crate::features::types::VALID_TYPES;
+use crate::features::Feature;
+use crate::templates::{
+    CiProvider, Network, SdkLang, Template, DEFAULT_STDLIB_VERSION, PROJECT_OPTIONS,
+};
+
+/// Build the JSON Schema (draft 2020-12) describing all valid generate inputs.
+pub fn options_schema() -> Value {
+    let template_slugs: Vec<&str> = Template::all().iter().map(|t| t.slug()).collect();
+    let feature_names: Vec<&str> = Feature::all().iter().map(|f| f.name()).collect();
+    let sdk_langs: Vec<&str> = SdkLang::all().iter().map(|l| l.slug()).collect();
+    let networks: Vec<&str> = Network::all().iter().map(|n| n.slug()).collect();
+    let ci_providers: Vec<&str> = CiProvider::all().iter().map(|c| c.slug()).collect();
+
+    let mut properties = Map::new();
+    properties.insert(
+        "template".to_string(),
+        json!({
+            "type": "string",
+            "enum": template_slugs,
+            "description": "Contract template",
+        }),
+    );
+    let mut required = vec!["template"];
+    for info in PROJECT_OPTIONS {
+        let mut prop = json!({ "type": info.kind, "description": info.description });
+        match info.name {
+            "namespace" | "project_name" => prop["pattern"] = json!("^[a-z][a-z0-9_-]*$"),
+            "validator_name" => prop["pattern"] = json!("^[a-z][a-z0-9_]*$"),
+            "sdk_lang" => prop["enum"] = json!(sdk_langs),
+            "stdlib_version" => {
+                prop["pattern"] = json!("^v[0-9]+\\.[0-9]+\\.[0-9]+$");
+                prop["default"] = json!(DEFAULT_STDLIB_VERSION);
+            }
+            "network" => {
+                prop["enum"] = json!(networks);
+                prop["default"] = json!(Network::default().slug());
+            }
+            "emit_ci" => prop["enum"] = json!(ci_providers),
+            _ => {}
+        }
+        if info.required {
+            required.push(info.name);
+        }
+        properties.insert(info.name.to_string(), prop);
+    }
+
+    let mut applicability = Vec::new();
+    for info in Template::all().iter().flat_map(|t| t.applicable_options()) {
+        if properties.contains_key(info.name) {
+            continue;
+        }
+        let templates: Vec<&str> = Template::all()
+            .iter()
+            .filter(|t| t.applicable_options().iter().any(|o| o.name == info.name))
+            .map(|t| t.slug())
+            .collect();
+
+        let mut prop = json!({
+            "type": info.kind,
+            "description": info.description,
+            "x-kaido-templates": templates,
+        });
+        match info.name {
+            "purpose" => prop["enum"] = json!(["spend", "mint"]),
+            "features" => {
+                let name_alt = feature_names.join("|");
+                prop["pattern"] = json!(format!("^({name_alt})(,({name_alt}))*$"));
+                prop["x-kaido-features"] = json!(feature_names);
+            }
+            "datum" | "redeemer" => prop["x-kaido-types"] = json!(VALID_TYPES),
+            _ => {}
+        }
+        properties.insert(info.name.to_string(), prop);
+
+        // Supplying a template-specific option requires a template that accepts it
+        applicability.push(json!({
+            "if": { "required": [info.name] },
+            "then": { "properties": { "template": { "enum": templates } } },
+        }));
+    }
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Kaido generate options",
+        "type": "object",
+        "required": required,
+        "properties": properties,
+        "allOf": applicability,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_is_valid_json_schema() {
+        // Building a validator checks the schema against the draft 2020-12 meta-schema
+        assert!(jsonschema::validator_for(&options_schema()).is_ok());
+        assert!(jsonschema::validator_for(&json!({ "type": 5 })).is_err());
+    }
+
+    #[test]
+    fn schema_tracks_enums_and_validates_inputs() {
+        let schema = options_schema();
+        let templates = schema["properties"]["template"]["enum"].as_array().unwrap();
+        assert_eq!(templates.len(), Template::all().len());
+        assert!(templates.contains(&json!("hash_timelock")));
+        assert_eq!(
+            schema["properties"]["features"]["x-kaido-features"]
+                .as_array()
+                .unwrap()
+                .len(),
+            Feature::all().len()
+        );
+
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        assert!(validator.is_valid(&json!({
+            "template": "custom",
+            "namespace": "myorg",
+            "project_name": "my-validator",
+            "features": "signature-auth,timelock",
+            "purpose": "spend",
+        })));
+        // Template-specific option on a template that doesn't accept it
+        assert!(!validator.is_valid(&json!({
+            "template": "escrow",
+            "namespace": "myorg",
+            "project_name": "my-escrow",
+            "cancellable": true,
+        })));
+        assert!(!validator.is_valid(&json!({
+            "template": "custom",
+            "namespace": "myorg",
+            "project_name": "x",
+            "features": "sig,unknown",
+        })));
+    }
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-use crate::features::types::{DatumField, RedeemerAction};
+use crate::features::types::{DatumField, RedeemerAction, SeedUtxo};
 
 /// Available contract templates
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,6 +19,14 @@ pub enum Template {
     LendingPool,
     DaoGovernance,
     StreamingPayments,
+    HashTimelock,
+    Payroll,
+    Faucet,
+    MerkleAllowlistMint,
+    ZkGate,
+    TokenLock,
+    DutchAuction,
+    OracleFeed,
     Custom,
 }
 
@@ -38,6 +46,14 @@ impl Template {
             Template::LendingPool,
             Template::DaoGovernance,
             Template::StreamingPayments,
+            Template::HashTimelock,
+            Template::Payroll,
+            Template::Faucet,
+            Template::MerkleAllowlistMint,
+            Template::ZkGate,
+            Template::TokenLock,
+            Template::DutchAuction,
+            Template::OracleFeed,
             Template::Custom,
         ]
     }
@@ -57,6 +73,14 @@ impl Template {
             Template::LendingPool => "lending_pool",
             Template::DaoGovernance => "dao_governance",
             Template::StreamingPayments => "streaming_payments",
+            Template::HashTimelock => "hash_timelock",
+            Template::Payroll => "payroll",
+            Template::Faucet => "faucet",
+            Template::MerkleAllowlistMint => "merkle_allowlist_mint",
+            Template::ZkGate => "zk_gate",
+            Template::TokenLock => "token_lock",
+            Template::DutchAuction => "dutch_auction",
+            Template::OracleFeed => "oracle_feed",
             Template::Custom => "custom",
         }
     }
@@ -94,12 +118,184 @@ impl Template {
             Template::StreamingPayments => {
                 "Streaming payments with time-based tranches and cancel/top-up"
             }
+            Template::HashTimelock => {
+                "Hashed timelock (atomic swap) with secret claim and timeout refund"
+            }
+            Template::Payroll => {
+                "Split payment / payroll paying a fixed recipient list with admin reclaim"
+            }
+            Template::Faucet => {
+                "Rate-limited testnet faucet with a fixed amount per claim and per-address cooldown"
+            }
+            Template::MerkleAllowlistMint => {
+                "Allowlist minting policy gated by a merkle proof, with a fixed quantity per claim"
+            }
+            Template::ZkGate => {
+                "Spend gated by a Groth16 proof checked with the BLS12-381 pairing builtins"
+            }
+            Template::TokenLock => "Lock any value until unlock_time, then release it to the owner",
+            Template::DutchAuction => {
+                "Dutch auction with a price falling linearly to a floor and seller reclaim"
+            }
+            Template::OracleFeed => {
+                "Operator-signed price feed whose timestamp strictly increases with every update"
+            }
             Template::Custom => {
                 "Custom validator with composable features (sig, timelock, datum-continuity, ...)"
             }
         }
     }
 
+    /// Security properties every validator of this template enforces, for the doc header of
+    /// each generated validator. Options that add checks append theirs in
+    /// `GenerateOptions::security_properties`; custom derives its own from the features.
+    pub fn security_properties(&self) -> &'static [&'static str] {
+        match self {
+            Template::SimpleMint => &[
+                "Minting requires the admin_pkh signature",
+                "Burning requires every quantity under the policy to be negative",
+            ],
+            Template::Vesting => &[
+                "Funds stay locked until `lock_until`",
+                "Only the beneficiary can claim, after the lock period",
+            ],
+            Template::Escrow => &[
+                "Complete pays the seller",
+                "The seller can reclaim only after the deadline",
+                "Cancel requires both parties' signatures",
+            ],
+            Template::MultisigTreasury => &[
+                "Withdrawals require `threshold` of the authorized `signers`",
+                "2 ADA minimum floor maintained at all times",
+                "Datum continuity enforced on every spend",
+                "Reference script injection protection",
+            ],
+            Template::NftMarketplace => &[
+                "Buy pays the seller's price to the seller's address",
+                "Only the seller can delist",
+                "Reference script injection protection",
+            ],
+            Template::StakingPool | Template::DexPool | Template::DaoGovernance => &[
+                "Admin actions require the admin signature",
+                "Datum continuity enforced on every spend",
+                "2 ADA minimum floor maintained",
+                "Reference script injection protection",
+            ],
+            Template::OracleSettlement => &[
+                "Settlement requires the oracle signature",
+                "The buyer can reclaim only after the deadline",
+            ],
+            Template::ReferralSystem => &[
+                "Project tokens, config updates and project teardown require the admin signature",
+                "One proof-of-referral token per referred user (anti-sybil)",
+                "Treasury withdrawals are admin-only and keep a 2 ADA floor with datum continuity",
+                "Reference script injection protection",
+            ],
+            Template::LendingPool => &[
+                "Borrowing enforces the collateral ratio",
+                "Liquidations and parameter updates require the admin signature",
+                "Datum continuity enforced on every spend",
+                "2 ADA minimum floor maintained",
+                "Reference script injection protection",
+            ],
+            Template::StreamingPayments => &[
+                "Claims are capped by the linearly vested tranches",
+                "Cancel requires the sender signature",
+                "Claim and TopUp require datum continuity",
+                "2 ADA minimum floor maintained on Claim",
+                "Reference script injection protection",
+            ],
+            Template::HashTimelock => &[
+                "Claim requires the secret hashing to `hash_lock`, before the timeout",
+                "Refund returns the locked lovelace to the sender only after the timeout",
+            ],
+            Template::Payroll => &[
+                "Pay gives every recipient at least the sum of their entries",
+                "Reclaim requires the admin signature after expiry",
+            ],
+            Template::Faucet => &[
+                "Each claim pays `amount` to a signing claimant",
+                "Per-address cooldown between claims",
+                "Refill and reclaim require the admin signature",
+            ],
+            Template::MerkleAllowlistMint => &[
+                "Claims require a merkle proof of the claimant against `merkle_root`",
+                "Each claim mints exactly `claim_quantity`",
+                "Opening and closing the mint require the admin signature",
+                "The seed UTxO makes the state token one-shot",
+            ],
+            Template::ZkGate => &[
+                "Spending requires a Groth16 proof verified against `vk`",
+                "The beneficiary must sign, so a mempool proof can't be front-run",
+            ],
+            Template::DutchAuction => &[
+                "Buy pays the seller at least the current price at the validity range lower bound",
+                "Reclaim requires the seller signature after `end_time`",
+            ],
+            Template::OracleFeed => &[
+                "Updates require the oracle_operator signature",
+                "Timestamps strictly increase, so backdated and replayed updates fail",
+                "The feed keeps its tokens in a single continuing output",
+            ],
+            Template::TokenLock | Template::Custom => &[],
+        }
+    }
+
+    /// Descriptions of the validator parameters this template can declare, by name
+    pub fn param_descriptions(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Template::SimpleMint => &[
+                ("admin_pkh", "key hash whose signature authorizes minting"),
+                (
+                    "lock_after",
+                    "POSIX time (ms) after which minting is disabled",
+                ),
+            ],
+            Template::Vesting => &[(
+                "owner_pkh",
+                "key hash allowed to cancel before the lock period ends",
+            )],
+            Template::MultisigTreasury => &[
+                ("signers", "key hashes authorized to approve withdrawals"),
+                ("threshold", "number of signers a withdrawal needs"),
+            ],
+            Template::ReferralSystem => &[
+                ("admin_pkh", "key hash of the project admin"),
+                ("own_policy_id", "policy id of the referral mint validator"),
+            ],
+            Template::MerkleAllowlistMint => &[
+                ("admin", "key hash that opens and closes the mint"),
+                ("merkle_root", "root of the allowlist merkle tree"),
+                ("claim_quantity", "quantity minted per claim"),
+                (
+                    "seed_utxo",
+                    "UTxO spent when opening the mint, making the state token unique",
+                ),
+            ],
+            Template::ZkGate => &[("vk", "Groth16 verification key of the statement circuit")],
+            Template::OracleFeed => &[("oracle_operator", "key hash allowed to publish prices")],
+            _ => &[],
+        }
+    }
+
+    /// Template-specific generate options this template accepts
+    /// (every template also accepts `PROJECT_OPTIONS`)
+    pub fn applicable_options(&self) -> &'static [OptionInfo] {
+        match self {
+            Template::SimpleMint => SIMPLE_MINT_OPTIONS,
+            Template::Vesting => VESTING_OPTIONS,
+            Template::NftMarketplace => NFT_MARKETPLACE_OPTIONS,
+            Template::Custom => CUSTOM_OPTIONS,
+            Template::TokenLock => TOKEN_LOCK_OPTIONS,
+            _ => &[],
+        }
+    }
+
+    /// Whether the validator is composed from features: custom, and the templates built on it
+    pub fn is_composed(&self) -> bool {
+        matches!(self, Template::Custom | Template::TokenLock)
+    }
+
     /// Whether this template currently has TypeScript SDK templates available.
     pub fn supports_sdk(&self) -> bool {
         matches!(
@@ -114,6 +310,296 @@ impl Template {
                 | Template::ReferralSystem
         )
     }
+
+    /// The `TEMPLATE_CHANGELOG` entries affecting this template that shipped after kaido `since`
+    /// and up to this release, oldest first. None if `since` isn't a `major.minor.patch` version.
+    pub fn changes_since(&self, since: &str) -> Option<Vec<&'static TemplateChange>> {
+        let since = parse_version(since)?;
+        let current = parse_version(KAIDO_VERSION);
+        Some(
+            TEMPLATE_CHANGELOG
+                .iter()
+                .filter(|c| c.templates.is_empty() || c.templates.contains(self))
+                .filter(|c| {
+                    let version = parse_version(c.version);
+                    version > Some(since) && version <= current
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether this template has SDK templates available for the given language.
+    pub fn supports_sdk_lang(&self, lang: SdkLang) -> bool {
+        match lang {
+            SdkLang::TypeScript => self.supports_sdk(),
+            SdkLang::Python => matches!(self, Template::SimpleMint | Template::Vesting),
+        }
+    }
+}
+
+/// A generate option as it appears in the JSON input (MCP, WASM, `--from-stdin`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OptionInfo {
+    pub name: &'static str,
+    /// JSON type: "string" or "boolean"
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+const fn option(name: &'static str, kind: &'static str, description: &'static str) -> OptionInfo {
+    OptionInfo {
+        name,
+        kind,
+        required: false,
+        description,
+    }
+}
+
+const fn required(name: &'static str, kind: &'static str, description: &'static str) -> OptionInfo {
+    OptionInfo {
+        name,
+        kind,
+        required: true,
+        description,
+    }
+}
+
+/// Options every template accepts
+pub const PROJECT_OPTIONS: &[OptionInfo] = &[
+    required("namespace", "string", "Project namespace (e.g., 'myorg')"),
+    required("project_name", "string", "Project name (e.g., 'my-token')"),
+    option(
+        "validator_name",
+        "string",
+        "Override the derived validator name (snake_case)",
+    ),
+    option("sdk", "boolean", "Generate off-chain SDK"),
+    option("sdk_lang", "string", "SDK language"),
+    option(
+        "strict",
+        "boolean",
+        "Fail generation if it produces any warning",
+    ),
+    option(
+        "emit_lock",
+        "boolean",
+        "Emit an aiken.lock pinning the stdlib version",
+    ),
+    option("stdlib_version", "string", "aiken-lang/stdlib release tag"),
+    option(
+        "network",
+        "string",
+        "Target network for the SDK client and aiken.toml",
+    ),
+    option(
+        "emit_ci",
+        "string",
+        "Emit a CI workflow running aiken check (and the aikido scan)",
+    ),
+];
+
+const SIMPLE_MINT_OPTIONS: &[OptionInfo] = &[
+    option("token_name", "string", "Token display name"),
+    option("asset_name", "string", "On-chain asset name"),
+    option("time_lock", "boolean", "Enable time-lock on minting policy"),
+];
+
+const VESTING_OPTIONS: &[OptionInfo] = &[
+    option(
+        "cancellable",
+        "boolean",
+        "Allow owner to cancel before lock period",
+    ),
+    option(
+        "partial_claim",
+        "boolean",
+        "Allow partial claims with value preservation",
+    ),
+];
+
+const NFT_MARKETPLACE_OPTIONS: &[OptionInfo] = &[option(
+    "royalty",
+    "boolean",
+    "Enforce a CIP-27 royalty payout from the listing datum on buy",
+)];
+
+const CUSTOM_OPTIONS: &[OptionInfo] = &[
+    option("purpose", "string", "Validator purpose"),
+    option("features", "string", "Comma-separated composable features"),
+    // Required for spend only, which `validate_custom_extras` reports
+    option(
+        "datum",
+        "string",
+        "Datum fields as name:Type pairs, comma-separated (required for spend)",
+    ),
+    required(
+        "redeemer",
+        "string",
+        "Redeemer actions, e.g. Claim,Withdraw(amount:Int)",
+    ),
+    option(
+        "counter_field",
+        "string",
+        "Int datum field advanced by monotonic-counter",
+    ),
+    option(
+        "expiry_field",
+        "string",
+        "Int datum field the expiry feature binds",
+    ),
+    option(
+        "seed_utxo",
+        "string",
+        "UTxO (<tx hash>#<index>) the generated unique-mint tests consume",
+    ),
+    EXPLAIN_OPTION,
+    TRACE_OPTION,
+    option(
+        "property_tests",
+        "boolean",
+        "Also emit Aiken property (fuzz) tests, e.g. fuzzing the lovelace value-preservation keeps",
+    ),
+    SINGLE_FILE_OPTION,
+];
+
+/// Token lock fixes its features, datum and redeemer, so only the output options apply
+const TOKEN_LOCK_OPTIONS: &[OptionInfo] = &[EXPLAIN_OPTION, TRACE_OPTION, SINGLE_FILE_OPTION];
+
+const EXPLAIN_OPTION: OptionInfo = option(
+    "explain",
+    "boolean",
+    "Annotate each check with the feature that produced it",
+);
+
+const TRACE_OPTION: OptionInfo = option(
+    "trace",
+    "boolean",
+    "Emit an Aiken trace before each composed check (kept only in builds with traces enabled)",
+);
+
+const SINGLE_FILE_OPTION: OptionInfo = option(
+    "single_file",
+    "boolean",
+    "Inline the custom types into the validator file instead of a lib/ types module",
+);
+
+/// Off-chain SDK target language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SdkLang {
+    TypeScript,
+    Python,
+}
+
+impl SdkLang {
+    /// All supported SDK languages
+    pub fn all() -> &'static [SdkLang] {
+        &[SdkLang::TypeScript, SdkLang::Python]
+    }
+
+    /// Language slug used in CLI/MCP arguments
+    pub fn slug(&self) -> &'static str {
+        match self {
+            SdkLang::TypeScript => "typescript",
+            SdkLang::Python => "python",
+        }
+    }
+}
+
+impl FromStr for SdkLang {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "typescript" | "ts" => Ok(SdkLang::TypeScript),
+            "python" | "py" => Ok(SdkLang::Python),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for SdkLang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.slug())
+    }
+}
+
+/// CI provider a workflow can be generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CiProvider {
+    /// `.github/workflows/aiken.yml`
+    GitHub,
+}
+
+impl CiProvider {
+    /// All supported CI providers
+    pub fn all() -> &'static [CiProvider] {
+        &[CiProvider::GitHub]
+    }
+
+    /// Provider slug used in CLI/MCP arguments
+    pub fn slug(&self) -> &'static str {
+        match self {
+            CiProvider::GitHub => "github",
+        }
+    }
+}
+
+impl FromStr for CiProvider {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(CiProvider::GitHub),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Cardano network the generated SDK client and project target
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Network {
+    Mainnet,
+    Preprod,
+    /// Default, so nothing points at mainnet unless asked
+    #[default]
+    Preview,
+}
+
+impl Network {
+    /// All supported networks
+    pub fn all() -> &'static [Network] {
+        &[Network::Mainnet, Network::Preprod, Network::Preview]
+    }
+
+    /// Network slug used in CLI/MCP arguments and generated code
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Preprod => "preprod",
+            Network::Preview => "preview",
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" => Ok(Network::Mainnet),
+            "preprod" => Ok(Network::Preprod),
+            "preview" => Ok(Network::Preview),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.slug())
+    }
 }
 
 impl FromStr for Template {
@@ -137,6 +623,16 @@ impl FromStr for Template {
             "streaming_payments" | "streaming-payments" | "streaming" => {
                 Ok(Template::StreamingPayments)
             }
+            "hash_timelock" | "hash-timelock" | "htlc" => Ok(Template::HashTimelock),
+            "payroll" | "split_payment" | "split-payment" => Ok(Template::Payroll),
+            "faucet" => Ok(Template::Faucet),
+            "merkle_allowlist_mint" | "merkle-allowlist-mint" | "allowlist" => {
+                Ok(Template::MerkleAllowlistMint)
+            }
+            "zk_gate" | "zk-gate" | "zk" | "groth16" => Ok(Template::ZkGate),
+            "token_lock" | "token-lock" | "lock" => Ok(Template::TokenLock),
+            "dutch_auction" | "dutch-auction" | "dutch" => Ok(Template::DutchAuction),
+            "oracle_feed" | "oracle-feed" | "price_feed" | "price-feed" => Ok(Template::OracleFeed),
             "custom" => Ok(Template::Custom),
             _ => Err(()),
         }
@@ -149,6 +645,75 @@ impl fmt::Display for Template {
     }
 }
 
+/// Aiken stdlib version kaido templates are tested against
+pub const DEFAULT_STDLIB_VERSION: &str = "v3.0.0";
+
+/// kaido release recorded in the header of every generated validator
+pub const KAIDO_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A change to the code kaido generates, recorded under the kaido release that ships it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TemplateChange {
+    /// kaido release (`major.minor.patch`) the change first appears in
+    pub version: &'static str,
+    /// Templates whose output changed; empty when every template's did
+    pub templates: &'static [Template],
+    pub note: &'static str,
+}
+
+/// Notable changes to generated code, oldest first. `--diff-against` lists the entries after the
+/// kaido version recorded in the existing project's validator headers.
+pub const TEMPLATE_CHANGELOG: &[TemplateChange] = &[
+    TemplateChange {
+        version: "0.1.0",
+        templates: &[],
+        note: "validators start with a module header recording the template, kaido version, \
+               security properties and parameters",
+    },
+    TemplateChange {
+        version: "0.1.0",
+        templates: &[Template::StreamingPayments],
+        note: "claims are capped at the linearly vested amount between tranches",
+    },
+    TemplateChange {
+        version: "0.1.0",
+        templates: &[Template::NftMarketplace],
+        note: "optional CIP-27 royalty enforcement on Buy (--royalty)",
+    },
+    TemplateChange {
+        version: "0.1.0",
+        templates: &[
+            Template::SimpleMint,
+            Template::Vesting,
+            Template::Escrow,
+            Template::MultisigTreasury,
+            Template::NftMarketplace,
+            Template::StakingPool,
+            Template::OracleSettlement,
+            Template::ReferralSystem,
+        ],
+        note: "the TypeScript SDK derives script addresses from plutus.json (sdk/src/address.ts, \
+               Client.fromBlueprint)",
+    },
+];
+
+/// `major.minor.patch`, with an optional leading `v`
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.strip_prefix('v').unwrap_or(version).split('.');
+    let mut next = || parts.next()?.parse().ok();
+    let parsed = (next()?, next()?, next()?);
+    parts.next().is_none().then_some(parsed)
+}
+
+/// aiken-lang/fuzz version added as a dependency when property tests are generated
+pub const FUZZ_VERSION: &str = "v2.2.0";
+
+/// Token lock datum field holding the key that may unlock (checked by signature-auth)
+pub const TOKEN_LOCK_OWNER_FIELD: &str = "owner";
+
+/// Aiken compiler release kaido templates target (aiken.toml `compiler`, CI workflow)
+pub const AIKEN_VERSION: &str = "v1.1.21";
+
 /// Options for generating a contract
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerateOptions {
@@ -179,6 +744,10 @@ pub struct GenerateOptions {
     /// Allow partial claims with value preservation
     pub partial_claim: bool,
 
+    // --- NFT Marketplace options ---
+    /// Enforce a CIP-27 royalty payout on every buy
+    pub royalty: bool,
+
     // --- Custom template options ---
     /// Validator purpose: "spend" or "mint"
     pub purpose: String,
@@ -188,6 +757,30 @@ pub struct GenerateOptions {
     pub redeemer_actions: Vec<RedeemerAction>,
     /// Selected feature names (resolved)
     pub feature_names: Vec<String>,
+    /// Annotate composed checks with the feature that produced them
+    pub explain: bool,
+    /// Precede composed checks with Aiken `trace` statements
+    pub trace: bool,
+    /// Add Aiken property (fuzz) tests alongside the example tests
+    pub property_tests: bool,
+    /// Inline the custom types into the validator instead of emitting lib/.../types.ak
+    pub single_file: bool,
+    /// Int datum field advanced by the monotonic-counter feature
+    pub counter_field: Option<String>,
+    /// Int datum field the expiry feature binds
+    pub expiry_field: Option<String>,
+    /// UTxO the generated unique-mint tests consume (placeholder if None)
+    pub seed_utxo: Option<SeedUtxo>,
+
+    // --- Project options ---
+    /// Pinned aiken-lang/stdlib version (e.g., "v3.0.0")
+    pub stdlib_version: String,
+    /// Emit an aiken.lock pinning the stdlib version
+    pub emit_lock: bool,
+    /// Target network for the SDK client and the aiken.toml annotation
+    pub network: Network,
+    /// CI workflow to emit, if any
+    pub emit_ci: Option<CiProvider>,
 }
 
 impl GenerateOptions {
@@ -201,6 +794,117 @@ impl GenerateOptions {
         Ok(())
     }
 
+    /// Rewrite a namespace/project name into the form aiken accepts: camelCase split with `_`,
+    /// lowercased, other invalid characters replaced by `_`, repeated `_` collapsed and
+    /// leading/trailing `_` trimmed. Path-like names are still rejected, since they aren't
+    /// typos to fix; so is anything that stays invalid (e.g. a leading digit).
+    pub fn force_snake_case(value: &str, field: &str) -> Result<String, String> {
+        if has_path_characters(value) {
+            // Reported as invalid path characters
+            validate_name_part(value, field)?;
+        }
+
+        let chars: Vec<char> = value.chars().collect();
+        let mut fixed = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let next = if c.is_ascii_uppercase() {
+                let prev = i.checked_sub(1).map(|j| chars[j]);
+                // "myToken" -> "my_token", "MYToken" -> "my_token"
+                let boundary = prev.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+                    || (prev.is_some_and(|p| p.is_ascii_uppercase())
+                        && chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase()));
+                if boundary {
+                    fixed.push('_');
+                }
+                c.to_ascii_lowercase()
+            } else if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' {
+                c
+            } else {
+                '_'
+            };
+            if !(next == '_' && fixed.ends_with('_')) {
+                fixed.push(next);
+            }
+        }
+        let fixed = fixed.trim_matches('_').to_string();
+
+        validate_name_part(&fixed, field)?;
+        Ok(fixed)
+    }
+
+    /// Validate a user-supplied stdlib version (a `vMAJOR.MINOR.PATCH` tag).
+    pub fn validate_stdlib_version(version: &str) -> Result<(), String> {
+        let parts: Vec<&str> = version
+            .strip_prefix('v')
+            .map(|v| v.split('.').collect())
+            .unwrap_or_default();
+        if parts.len() != 3
+            || parts
+                .iter()
+                .any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit()))
+        {
+            return Err(format!(
+                "stdlib_version '{}' must be a release tag like '{}'",
+                version, DEFAULT_STDLIB_VERSION
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate a user-supplied validator name override (snake_case Aiken identifier).
+    pub fn validate_validator_name(name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("validator_name cannot be empty".to_string());
+        }
+        if !name.starts_with(|c: char| c.is_ascii_lowercase()) {
+            return Err(format!(
+                "validator_name '{name}' must start with a lowercase letter"
+            ));
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(format!(
+                "validator_name '{name}' must be snake_case; use lowercase letters, digits or '_' only"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Security properties of the validator these options render: the template's, plus those
+    /// of the options that add checks. Empty for custom, whose properties come from its features.
+    pub fn security_properties(&self) -> Vec<String> {
+        let mut properties: Vec<String> = self
+            .template
+            .security_properties()
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let optional = [
+            (self.time_lock, "Minting is disabled after `lock_after`"),
+            (
+                self.cancellable,
+                "The owner can cancel and reclaim before the lock period ends",
+            ),
+            (
+                self.partial_claim,
+                "Partial claims preserve the remaining value at the script",
+            ),
+            (
+                self.royalty,
+                "Every buy pays a CIP-27 royalty to the royalty address",
+            ),
+        ];
+        properties.extend(
+            optional
+                .iter()
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, p)| p.to_string()),
+        );
+        properties
+    }
+
     /// Create options for a simple mint template
     pub fn simple_mint(
         namespace: &str,
@@ -222,10 +926,22 @@ impl GenerateOptions {
             time_lock,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "mint".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -249,10 +965,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable,
             partial_claim,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -271,10 +999,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -293,10 +1033,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -315,10 +1067,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -337,15 +1101,27 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
     /// Create options for an NFT marketplace template
-    pub fn nft_marketplace(namespace: &str, project_name: &str) -> Self {
+    pub fn nft_marketplace(namespace: &str, project_name: &str, royalty: bool) -> Self {
         let module_name = Self::to_snake_case(project_name);
         Self {
             template: Template::NftMarketplace,
@@ -359,10 +1135,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -381,10 +1169,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -403,10 +1203,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -425,10 +1237,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -447,10 +1271,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -469,10 +1305,296 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
+            purpose: "spend".to_string(),
+            datum_fields: vec![],
+            redeemer_actions: vec![],
+            feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
+        }
+    }
+
+    /// Create options for a hashed timelock (atomic swap) template
+    pub fn hash_timelock(namespace: &str, project_name: &str) -> Self {
+        let module_name = Self::to_snake_case(project_name);
+        Self {
+            template: Template::HashTimelock,
+            namespace: namespace.to_string(),
+            project_name: project_name.to_string(),
+            module_name: module_name.clone(),
+            description: format!("{} hashed timelock", project_name),
+            validator_name: format!("{}_htlc", module_name),
+            token_name: None,
+            asset_name: None,
+            time_lock: false,
+            cancellable: false,
+            partial_claim: false,
+            royalty: false,
+            purpose: "spend".to_string(),
+            datum_fields: vec![],
+            redeemer_actions: vec![],
+            feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
+        }
+    }
+
+    /// Create options for a payroll (split payment) template
+    pub fn payroll(namespace: &str, project_name: &str) -> Self {
+        let module_name = Self::to_snake_case(project_name);
+        Self {
+            template: Template::Payroll,
+            namespace: namespace.to_string(),
+            project_name: project_name.to_string(),
+            module_name: module_name.clone(),
+            description: format!("{} payroll", project_name),
+            validator_name: format!("{}_payroll", module_name),
+            token_name: None,
+            asset_name: None,
+            time_lock: false,
+            cancellable: false,
+            partial_claim: false,
+            royalty: false,
+            purpose: "spend".to_string(),
+            datum_fields: vec![],
+            redeemer_actions: vec![],
+            feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
+        }
+    }
+
+    /// Create options for a rate-limited faucet template
+    pub fn faucet(namespace: &str, project_name: &str) -> Self {
+        let module_name = Self::to_snake_case(project_name);
+        Self {
+            template: Template::Faucet,
+            namespace: namespace.to_string(),
+            project_name: project_name.to_string(),
+            module_name: module_name.clone(),
+            description: format!("{} faucet", project_name),
+            validator_name: format!("{}_faucet", module_name),
+            token_name: None,
+            asset_name: None,
+            time_lock: false,
+            cancellable: false,
+            partial_claim: false,
+            royalty: false,
+            purpose: "spend".to_string(),
+            datum_fields: vec![],
+            redeemer_actions: vec![],
+            feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
+        }
+    }
+
+    /// Create options for a merkle allowlist mint template
+    pub fn merkle_allowlist_mint(namespace: &str, project_name: &str) -> Self {
+        let module_name = Self::to_snake_case(project_name);
+        Self {
+            template: Template::MerkleAllowlistMint,
+            namespace: namespace.to_string(),
+            project_name: project_name.to_string(),
+            module_name: module_name.clone(),
+            description: format!("{} allowlist mint", project_name),
+            validator_name: format!("{}_allowlist", module_name),
+            token_name: None,
+            asset_name: None,
+            time_lock: false,
+            cancellable: false,
+            partial_claim: false,
+            royalty: false,
+            purpose: "mint".to_string(),
+            datum_fields: vec![],
+            redeemer_actions: vec![],
+            feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
+        }
+    }
+
+    /// Create options for a zk-gated spend template (Groth16 over BLS12-381)
+    pub fn zk_gate(namespace: &str, project_name: &str) -> Self {
+        let module_name = Self::to_snake_case(project_name);
+        Self {
+            template: Template::ZkGate,
+            namespace: namespace.to_string(),
+            project_name: project_name.to_string(),
+            module_name: module_name.clone(),
+            description: format!("{} zk gate", project_name),
+            validator_name: format!("{}_zk_gate", module_name),
+            token_name: None,
+            asset_name: None,
+            time_lock: false,
+            cancellable: false,
+            partial_claim: false,
+            royalty: false,
+            purpose: "spend".to_string(),
+            datum_fields: vec![],
+            redeemer_actions: vec![],
+            feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
+        }
+    }
+
+    /// Create options for a Dutch (declining-price) auction template
+    pub fn dutch_auction(namespace: &str, project_name: &str) -> Self {
+        let module_name = Self::to_snake_case(project_name);
+        Self {
+            template: Template::DutchAuction,
+            namespace: namespace.to_string(),
+            project_name: project_name.to_string(),
+            module_name: module_name.clone(),
+            description: format!("{} Dutch auction", project_name),
+            validator_name: format!("{}_dutch_auction", module_name),
+            token_name: None,
+            asset_name: None,
+            time_lock: false,
+            cancellable: false,
+            partial_claim: false,
+            royalty: false,
+            purpose: "spend".to_string(),
+            datum_fields: vec![],
+            redeemer_actions: vec![],
+            feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
+        }
+    }
+
+    /// Create options for an oracle feed: the publishing side of an oracle, where
+    /// `oracle_operator` updates the feed UTxO's price with strictly increasing timestamps
+    pub fn oracle_feed(namespace: &str, project_name: &str) -> Self {
+        let module_name = Self::to_snake_case(project_name);
+        Self {
+            template: Template::OracleFeed,
+            namespace: namespace.to_string(),
+            project_name: project_name.to_string(),
+            module_name: module_name.clone(),
+            description: format!("{} oracle feed", project_name),
+            validator_name: format!("{}_oracle_feed", module_name),
+            token_name: None,
+            asset_name: None,
+            time_lock: false,
+            cancellable: false,
+            partial_claim: false,
+            royalty: false,
             purpose: "spend".to_string(),
             datum_fields: vec![],
             redeemer_actions: vec![],
             feature_names: vec![],
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
+        }
+    }
+
+    /// Create options for a token lock: any value locked until `unlock_time`, then released by a
+    /// single `Unlock` action signed by the owner. Composed like the custom template from
+    /// signature-auth and timelock, with signature-auth checking the datum's
+    /// `TOKEN_LOCK_OWNER_FIELD` instead of an `admin_pkh` parameter, so each lock has its own owner.
+    pub fn token_lock(namespace: &str, project_name: &str) -> Self {
+        let module_name = Self::to_snake_case(project_name);
+        let datum_fields = vec![
+            DatumField {
+                name: TOKEN_LOCK_OWNER_FIELD.to_string(),
+                aiken_type: "ByteArray".to_string(),
+            },
+            DatumField {
+                name: "unlock_time".to_string(),
+                aiken_type: "Int".to_string(),
+            },
+        ];
+        let redeemer_actions = vec![RedeemerAction {
+            name: "Unlock".to_string(),
+            fields: vec![],
+        }];
+        Self {
+            template: Template::TokenLock,
+            description: format!("{} token lock", project_name),
+            validator_name: format!("{}_token_lock", module_name),
+            ..Self::custom(
+                namespace,
+                project_name,
+                "spend",
+                datum_fields,
+                redeemer_actions,
+                vec!["signature-auth".to_string(), "timelock".to_string()],
+            )
         }
     }
 
@@ -503,10 +1625,22 @@ impl GenerateOptions {
             time_lock: false,
             cancellable: false,
             partial_claim: false,
+            royalty: false,
             purpose: purpose.to_string(),
             datum_fields,
             redeemer_actions,
             feature_names,
+            explain: false,
+            trace: false,
+            property_tests: false,
+            single_file: false,
+            counter_field: None,
+            expiry_field: None,
+            seed_utxo: None,
+            stdlib_version: DEFAULT_STDLIB_VERSION.to_string(),
+            emit_lock: false,
+            network: Network::default(),
+            emit_ci: None,
         }
     }
 
@@ -519,7 +1653,7 @@ fn validate_name_part(value: &str, field: &str) -> Result<(), String> {
     if value.is_empty() {
         return Err(format!("{field} cannot be empty"));
     }
-    if value.contains('/') || value.contains('\\') || value.contains(':') || value.contains("..") {
+    if has_path_characters(value) {
         return Err(format!(
             "{field} contains invalid path characters; use letters, digits, '-' or '_' only"
         ));
@@ -532,12 +1666,47 @@ fn validate_name_part(value: &str, field: &str) -> Result<(), String> {
             "{field} contains invalid characters; use letters, digits, '-' or '_' only"
         ));
     }
+    // aiken.toml's `name` is "<namespace>/<project_name>"; aiken build only accepts parts
+    // matching ^[a-z][a-z0-9_-]*$
+    if !value.starts_with(|c: char| c.is_ascii_lowercase()) {
+        return Err(format!(
+            "{field} '{value}' must start with a lowercase letter (aiken package names match [a-z][a-z0-9_-]*)"
+        ));
+    }
+    if value.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(format!(
+            "{field} '{value}' must be lowercase (aiken package names match [a-z][a-z0-9_-]*)"
+        ));
+    }
     Ok(())
 }
 
+fn has_path_characters(value: &str) -> bool {
+    value.contains('/') || value.contains('\\') || value.contains(':') || value.contains("..")
+}
+
 #[cfg(test)]
 mod tests {
-    use super::GenerateOptions;
+    use super::{GenerateOptions, SdkLang, Template, KAIDO_VERSION, TEMPLATE_CHANGELOG};
+
+    #[test]
+    fn applicable_options_cover_template_specific_flags() {
+        let names =
+            |t: Template| -> Vec<&str> { t.applicable_options().iter().map(|o| o.name).collect() };
+        let vesting = names(Template::Vesting);
+        assert!(vesting.contains(&"cancellable"));
+        assert!(vesting.contains(&"partial_claim"));
+        assert_eq!(names(Template::NftMarketplace), vec!["royalty"]);
+        assert!(names(Template::DexPool).is_empty());
+
+        let redeemer = Template::Custom
+            .applicable_options()
+            .iter()
+            .find(|o| o.name == "redeemer")
+            .unwrap();
+        assert!(redeemer.required);
+        assert_eq!(redeemer.kind, "string");
+    }
 
     #[test]
     fn validate_namespace_and_project_rejects_path_traversal() {
@@ -550,4 +1719,102 @@ mod tests {
     fn validate_namespace_and_project_accepts_safe_values() {
         assert!(GenerateOptions::validate_namespace_and_project("my_org", "my-project").is_ok());
     }
+
+    #[test]
+    fn validate_namespace_and_project_follows_aiken_package_names() {
+        let err = GenerateOptions::validate_namespace_and_project("1org", "token")
+            .expect_err("leading digit");
+        assert!(err.contains("namespace '1org' must start with a lowercase letter"));
+        let err = GenerateOptions::validate_namespace_and_project("myorg", "_token")
+            .expect_err("leading underscore");
+        assert!(err.contains("project_name '_token' must start with a lowercase letter"));
+        let err = GenerateOptions::validate_namespace_and_project("myorg", "MyToken")
+            .expect_err("uppercase");
+        assert!(err.contains("project_name 'MyToken' must start with a lowercase letter"));
+        let err = GenerateOptions::validate_namespace_and_project("myOrg", "token")
+            .expect_err("uppercase");
+        assert!(err.contains("namespace 'myOrg' must be lowercase"));
+        assert!(GenerateOptions::validate_namespace_and_project("org2", "token-v2_a").is_ok());
+    }
+
+    #[test]
+    fn force_snake_case_rewrites_invalid_names() {
+        let fix = |value| GenerateOptions::force_snake_case(value, "project_name");
+        assert_eq!(fix("MyToken").as_deref(), Ok("my_token"));
+        assert_eq!(fix("my.token").as_deref(), Ok("my_token"));
+        assert_eq!(fix("MYToken v2").as_deref(), Ok("my_token_v2"));
+        assert_eq!(fix("__my  token__").as_deref(), Ok("my_token"));
+        assert_eq!(fix("my-token").as_deref(), Ok("my-token"));
+        assert!(fix("1token")
+            .unwrap_err()
+            .contains("must start with a lowercase letter"));
+        assert!(fix("...").is_err());
+    }
+
+    #[test]
+    fn force_snake_case_still_rejects_path_traversal() {
+        let err = GenerateOptions::force_snake_case("../evil", "project_name").unwrap_err();
+        assert!(err.contains("invalid path characters"));
+        assert!(GenerateOptions::force_snake_case("my/org", "namespace").is_err());
+    }
+
+    #[test]
+    fn validate_validator_name_rejects_non_snake_case() {
+        let err = GenerateOptions::validate_validator_name("my-validator").expect_err("hyphen");
+        assert!(err.contains("snake_case"));
+        let err = GenerateOptions::validate_validator_name("MyValidator").expect_err("uppercase");
+        assert!(err.contains("lowercase letter"));
+        assert!(GenerateOptions::validate_validator_name("my_Validator").is_err());
+        assert!(GenerateOptions::validate_validator_name("").is_err());
+    }
+
+    #[test]
+    fn validate_validator_name_accepts_snake_case() {
+        assert!(GenerateOptions::validate_validator_name("treasury_v2").is_ok());
+    }
+
+    #[test]
+    fn validate_stdlib_version_requires_release_tag() {
+        assert!(GenerateOptions::validate_stdlib_version("v3.0.0").is_ok());
+        assert!(GenerateOptions::validate_stdlib_version("v2.10.1").is_ok());
+        assert!(GenerateOptions::validate_stdlib_version("3.0.0").is_err());
+        assert!(GenerateOptions::validate_stdlib_version("main").is_err());
+        assert!(GenerateOptions::validate_stdlib_version("v3.0.0\"\nx = \"").is_err());
+    }
+
+    #[test]
+    fn supports_sdk_lang_python_limited_to_mint_and_vesting() {
+        assert!(Template::SimpleMint.supports_sdk_lang(SdkLang::Python));
+        assert!(Template::Vesting.supports_sdk_lang(SdkLang::Python));
+        assert!(!Template::Escrow.supports_sdk_lang(SdkLang::Python));
+        assert!(Template::Escrow.supports_sdk_lang(SdkLang::TypeScript));
+        assert_eq!("py".parse::<SdkLang>(), Ok(SdkLang::Python));
+    }
+
+    #[test]
+    fn changes_since_lists_entries_after_the_recorded_version() {
+        let notes = |template: Template, since: &str| -> Vec<&str> {
+            template
+                .changes_since(since)
+                .unwrap()
+                .iter()
+                .map(|c| c.note)
+                .collect()
+        };
+        let streaming = notes(Template::StreamingPayments, "0.0.9");
+        assert!(streaming.iter().any(|n| n.contains("linearly vested")));
+        // Entries for every template are included, other templates' are not
+        assert!(streaming.iter().any(|n| n.contains("module header")));
+        assert!(!streaming.iter().any(|n| n.contains("royalty")));
+        assert!(notes(Template::NftMarketplace, "v0.0.9")
+            .iter()
+            .any(|n| n.contains("royalty")));
+
+        assert!(notes(Template::StreamingPayments, KAIDO_VERSION).is_empty());
+        assert!(Template::Escrow.changes_since("latest").is_none());
+        assert!(Template::Escrow.changes_since("0.1").is_none());
+        assert!(TEMPLATE_CHANGELOG
+            .iter()
+            .all(|c| super::parse_version(c.version).is_some()));
+    }
 }
@@ -23,6 +23,13 @@ struct JsonRpcResponse {
     error: Option<JsonRpcError>,
 }
 
+#[derive(Serialize)]
+struct JsonRpcNotification {
+    jsonrpc: String,
+    method: String,
+    params: Value,
+}
+
 #[derive(Serialize)]
 struct JsonRpcError {
     code: i64,
@@ -71,19 +78,28 @@ fn main() {
         }
 
         let id = request.id.clone().unwrap_or(Value::Null);
-        let response = handle_request(&request.method, &request.params, id);
+        let response = handle_request(&request.method, &request.params, id, &mut |n| {
+            write_response(&stdout, &n)
+        });
         write_response(&stdout, &response);
     }
 }
 
-fn write_response(stdout: &io::Stdout, response: &JsonRpcResponse) {
+fn write_response<T: Serialize>(stdout: &io::Stdout, response: &T) {
     let json = serde_json::to_string(response).unwrap();
     let mut out = stdout.lock();
     let _ = writeln!(out, "{}", json);
     let _ = out.flush();
 }
 
-fn handle_request(method: &str, params: &Value, id: Value) -> JsonRpcResponse {
+/// Handle a single request. Notifications emitted while the request runs (e.g. progress for a
+/// `tools/call` carrying `_meta.progressToken`) are passed to `notify` before the response.
+fn handle_request(
+    method: &str,
+    params: &Value,
+    id: Value,
+    notify: &mut dyn FnMut(JsonRpcNotification),
+) -> JsonRpcResponse {
     match method {
         "initialize" => JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
@@ -114,7 +130,27 @@ fn handle_request(method: &str, params: &Value, id: Value) -> JsonRpcResponse {
             let name = params.get("name").and_then(|v| v.as_str()).unwrap_or("");
             let arguments = params.get("arguments").cloned().unwrap_or(Value::Object(Default::default()));
 
-            match tools::call_tool(name, &arguments) {
+            let progress_token = params
+                .get("_meta")
+                .and_then(|m| m.get("progressToken"))
+                .cloned();
+            let mut step = 0u64;
+            let mut progress = |message: &str| {
+                if let Some(token) = &progress_token {
+                    step += 1;
+                    notify(JsonRpcNotification {
+                        jsonrpc: "2.0".to_string(),
+                        method: "notifications/progress".to_string(),
+                        params: serde_json::json!({
+                            "progressToken": token,
+                            "progress": step,
+                            "message": message
+                        }),
+                    });
+                }
+            };
+
+            match tools::call_tool(name, &arguments, &mut progress) {
                 Ok(result) => JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     id,
@@ -126,13 +162,13 @@ fn handle_request(method: &str, params: &Value, id: Value) -> JsonRpcResponse {
                     })),
                     error: None,
                 },
-                Err(msg) => JsonRpcResponse {
+                Err(details) => JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     id,
                     result: Some(serde_json::json!({
                         "content": [{
                             "type": "text",
-                            "text": msg
+                            "text": details.to_string()
                         }],
                         "isError": true
                     })),
@@ -159,3 +195,92 @@ fn handle_request(method: &str, params: &Value, id: Value) -> JsonRpcResponse {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_request, JsonRpcNotification};
+    use serde_json::{json, Value};
+
+    fn generate_params(output: &std::path::Path, meta: Value) -> Value {
+        json!({
+            "name": "kaido_generate",
+            "arguments": {
+                "template": "mint",
+                "namespace": "acme",
+                "project_name": "progress",
+                "output": output.to_str().unwrap(),
+                "skip_verify": true
+            },
+            "_meta": meta
+        })
+    }
+
+    #[test]
+    fn tools_call_with_progress_token_emits_progress_notifications() {
+        let dir = tempfile::tempdir().unwrap();
+        let params = generate_params(&dir.path().join("out"), json!({ "progressToken": "tok-1" }));
+        let mut notifications: Vec<JsonRpcNotification> = Vec::new();
+        let response = handle_request("tools/call", &params, json!(1), &mut |n| {
+            notifications.push(n)
+        });
+
+        assert!(response.error.is_none());
+        let messages: Vec<&str> = notifications
+            .iter()
+            .map(|n| {
+                assert_eq!(n.method, "notifications/progress");
+                assert_eq!(n.params["progressToken"], "tok-1");
+                n.params["message"].as_str().unwrap()
+            })
+            .collect();
+        assert_eq!(messages, vec!["Rendering templates", "Writing files"]);
+        let steps: Vec<u64> = notifications
+            .iter()
+            .map(|n| n.params["progress"].as_u64().unwrap())
+            .collect();
+        assert_eq!(steps, vec![1, 2]);
+    }
+
+    #[test]
+    fn tools_call_without_progress_token_stays_silent() {
+        let dir = tempfile::tempdir().unwrap();
+        let params = generate_params(&dir.path().join("out"), json!({}));
+        let mut count = 0;
+        let response = handle_request("tools/call", &params, json!(1), &mut |_| count += 1);
+
+        assert!(response.error.is_none());
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn tools_call_with_missing_required_arguments_is_an_error() {
+        let params = json!({
+            "name": "kaido_generate",
+            "arguments": { "template": "escrow", "skip_verify": true }
+        });
+        let response = handle_request("tools/call", &params, json!(1), &mut |_| {});
+
+        assert!(response.error.is_none());
+        let result = response.result.unwrap();
+        assert_eq!(result["isError"], true);
+        let text: Value =
+            serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert!(text["error"].as_str().unwrap().contains("namespace"));
+    }
+
+    #[test]
+    fn tools_call_with_missing_or_unknown_name_is_an_error() {
+        for (params, message) in [
+            (json!({}), "Missing tool name"),
+            (json!({ "name": "kaido_bogus" }), "Unknown tool: kaido_bogus"),
+        ] {
+            let response = handle_request("tools/call", &params, json!(1), &mut |_| {});
+            let result = response.result.unwrap();
+            assert_eq!(result["isError"], true);
+            assert!(result["content"][0]["text"]
+                .as_str()
+                .unwrap()
+                .contains(message));
+        }
+    }
+}
@@ -2,13 +2,54 @@ use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 
-use kaido_core::features;
 use kaido_core::generator::ProjectGenerator;
-use kaido_core::templates::{GenerateOptions, Template};
+use kaido_core::options::build_options_from_json;
+use kaido_core::templates::{SdkLang, Template};
 use serde_json::Value;
 
 /// MCP tool definitions for tools/list
 pub fn tool_definitions() -> Vec<Value> {
+    let mut generate = serde_json::json!({
+        "name": "kaido_generate",
+        "description": "Generate an Aiken smart contract project from a security-focused template. Supports 21 templates including mint, vesting, escrow, treasury, marketplace, staking, oracle, referral, dex, lending, governance, streaming, htlc, payroll, faucet, allowlist, zk-gate, token-lock, dutch-auction, oracle-feed, and custom composable validators.",
+        "inputSchema": {
+            "type": "object",
+            "required": ["template", "namespace", "project_name"],
+            "properties": {
+                "template": { "type": "string", "description": "Template: mint, vesting, escrow, treasury, marketplace, staking, oracle, referral, dex, lending, governance, streaming, htlc, payroll, faucet, allowlist, zk-gate, token-lock, dutch-auction, oracle-feed, custom" },
+                "namespace": { "type": "string", "description": "Project namespace (e.g., 'myorg')" },
+                "project_name": { "type": "string", "description": "Project name (e.g., 'my-token')" },
+                "output": { "type": "string", "description": "Output directory path" },
+                "validator_name": { "type": "string", "description": "Override the derived validator name (snake_case)" },
+                "emit_lock": { "type": "boolean", "description": "Emit an aiken.lock pinning the stdlib version" },
+                "stdlib_version": { "type": "string", "description": "aiken-lang/stdlib release tag to pin (default 'v3.0.0')" },
+                "network": { "type": "string", "enum": ["mainnet", "preprod", "preview"], "description": "Target network for the SDK client and aiken.toml (default 'preview')" },
+                "emit_ci": { "type": "string", "enum": ["github"], "description": "Emit a CI workflow (.github/workflows/aiken.yml) running aiken check and the aikido scan" },
+                "sdk": { "type": "boolean", "description": "Generate off-chain SDK" },
+                "sdk_lang": { "type": "string", "description": "SDK language: 'typescript' (default) or 'python' (mint, vesting)" },
+                "skip_verify": { "type": "boolean", "description": "Skip aiken/aikido verification" },
+                "strict": { "type": "boolean", "description": "Fail without writing files if generation produces any warning" }
+            }
+        }
+    });
+
+    // Template-specific options come from the same metadata as the JSON schema
+    let properties = &mut generate["inputSchema"]["properties"];
+    for info in Template::all().iter().flat_map(|t| t.applicable_options()) {
+        if properties.get(info.name).is_some() {
+            continue;
+        }
+        let templates: Vec<&str> = Template::all()
+            .iter()
+            .filter(|t| t.applicable_options().iter().any(|o| o.name == info.name))
+            .map(|t| t.slug())
+            .collect();
+        properties[info.name] = serde_json::json!({
+            "type": info.kind,
+            "description": format!("{} ({})", info.description, templates.join(", ")),
+        });
+    }
+
     vec![
         serde_json::json!({
             "name": "kaido_list_templates",
@@ -18,31 +59,7 @@ pub fn tool_definitions() -> Vec<Value> {
                 "properties": {}
             }
         }),
-        serde_json::json!({
-            "name": "kaido_generate",
-            "description": "Generate an Aiken smart contract project from a security-focused template. Supports 13 templates including mint, vesting, escrow, treasury, marketplace, staking, oracle, referral, dex, lending, governance, streaming, and custom composable validators.",
-            "inputSchema": {
-                "type": "object",
-                "required": ["template", "namespace", "project_name"],
-                "properties": {
-                    "template": { "type": "string", "description": "Template: mint, vesting, escrow, treasury, marketplace, staking, oracle, referral, dex, lending, governance, streaming, custom" },
-                    "namespace": { "type": "string", "description": "Project namespace (e.g., 'myorg')" },
-                    "project_name": { "type": "string", "description": "Project name (e.g., 'my-token')" },
-                    "output": { "type": "string", "description": "Output directory path" },
-                    "token_name": { "type": "string", "description": "Token display name (mint template)" },
-                    "asset_name": { "type": "string", "description": "On-chain asset name (mint template)" },
-                    "time_lock": { "type": "boolean", "description": "Enable time-lock (mint template)" },
-                    "cancellable": { "type": "boolean", "description": "Allow cancellation (vesting)" },
-                    "partial_claim": { "type": "boolean", "description": "Allow partial claims (vesting)" },
-                    "features": { "type": "string", "description": "Composable features CSV (custom template)" },
-                    "datum": { "type": "string", "description": "Datum fields (custom, e.g., 'owner:ByteArray,amount:Int')" },
-                    "redeemer": { "type": "string", "description": "Redeemer actions (custom, e.g., 'Claim,Cancel')" },
-                    "purpose": { "type": "string", "description": "Validator purpose: 'spend' or 'mint' (custom)" },
-                    "sdk": { "type": "boolean", "description": "Generate TypeScript SDK" },
-                    "skip_verify": { "type": "boolean", "description": "Skip aiken/aikido verification" }
-                }
-            }
-        }),
+        generate,
         serde_json::json!({
             "name": "kaido_verify",
             "description": "Verify an existing Aiken project compiles and passes tests. Runs aiken build, aiken check, and aikido static analysis.",
@@ -57,22 +74,34 @@ pub fn tool_definitions() -> Vec<Value> {
     ]
 }
 
-/// Dispatch a tool call by name
-pub fn call_tool(name: &str, arguments: &Value) -> Result<String, String> {
+/// Dispatch a tool call by name. `progress` is called with a message as each long-running
+/// stage starts (render, write, build, check, scan).
+/// A failed call (unknown tool, invalid arguments, write or verification failure) returns
+/// `Err` with a JSON object carrying at least `error`, which the server reports with `isError`.
+pub fn call_tool(
+    name: &str,
+    arguments: &Value,
+    progress: &mut dyn FnMut(&str),
+) -> Result<String, Value> {
     match name {
         "kaido_list_templates" => Ok(list_templates()),
-        "kaido_generate" => Ok(generate(arguments)),
+        "kaido_generate" => generate(arguments, progress),
         "kaido_verify" => {
             let path = arguments
                 .get("path")
                 .and_then(|v| v.as_str())
                 .unwrap_or(".");
-            Ok(verify(path))
+            verify(path, progress)
         }
-        _ => Err(format!("Unknown tool: {}", name)),
+        "" => Err(tool_error("Missing tool name")),
+        _ => Err(tool_error(format!("Unknown tool: {}", name))),
     }
 }
 
+fn tool_error(message: impl std::fmt::Display) -> Value {
+    serde_json::json!({ "error": message.to_string() })
+}
+
 fn list_templates() -> String {
     let templates: Vec<Value> = Template::all()
         .iter()
@@ -80,6 +109,7 @@ fn list_templates() -> String {
             serde_json::json!({
                 "slug": t.slug(),
                 "description": t.description(),
+                "options": t.applicable_options(),
             })
         })
         .collect();
@@ -140,9 +170,13 @@ fn parse_aikido_scan(
     Ok(parsed)
 }
 
-fn run_verification(project_dir: &PathBuf) -> Result<Value, String> {
+fn run_verification(
+    project_dir: &PathBuf,
+    progress: &mut dyn FnMut(&str),
+) -> Result<Value, String> {
     let mut verification = serde_json::Map::new();
 
+    progress("Running aiken build");
     let build = run_command(project_dir, "aiken", &["build"])?;
     if !build.status.success() {
         return Err(format!(
@@ -153,6 +187,7 @@ fn run_verification(project_dir: &PathBuf) -> Result<Value, String> {
     }
     verification.insert("aiken_build".to_string(), serde_json::json!("passed"));
 
+    progress("Running aiken check");
     let check = run_command(project_dir, "aiken", &["check"])?;
     if !check.status.success() {
         return Err(format!(
@@ -163,6 +198,7 @@ fn run_verification(project_dir: &PathBuf) -> Result<Value, String> {
     }
     verification.insert("aiken_check".to_string(), serde_json::json!("passed"));
 
+    progress("Running aikido scan");
     let scan = run_command(
         project_dir,
         "aikido",
@@ -179,69 +215,57 @@ fn run_verification(project_dir: &PathBuf) -> Result<Value, String> {
     Ok(Value::Object(verification))
 }
 
-fn generate(args: &Value) -> String {
-    let template = args.get("template").and_then(|v| v.as_str()).unwrap_or("");
-    let namespace = args.get("namespace").and_then(|v| v.as_str()).unwrap_or("");
+fn generate(args: &Value, progress: &mut dyn FnMut(&str)) -> Result<String, Value> {
     let project_name = args
         .get("project_name")
         .and_then(|v| v.as_str())
         .unwrap_or("");
     let output = args.get("output").and_then(|v| v.as_str());
-    let token_name = args.get("token_name").and_then(|v| v.as_str());
-    let asset_name = args.get("asset_name").and_then(|v| v.as_str());
-    let time_lock = args
-        .get("time_lock")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-    let cancellable = args
-        .get("cancellable")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-    let partial_claim = args
-        .get("partial_claim")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-    let features_str = args.get("features").and_then(|v| v.as_str());
-    let datum = args.get("datum").and_then(|v| v.as_str());
-    let redeemer = args.get("redeemer").and_then(|v| v.as_str());
-    let purpose = args
-        .get("purpose")
-        .and_then(|v| v.as_str())
-        .unwrap_or("spend");
     let sdk = args.get("sdk").and_then(|v| v.as_bool()).unwrap_or(false);
+    let sdk_lang = match args.get("sdk_lang").and_then(|v| v.as_str()) {
+        Some(l) => match l.parse::<SdkLang>() {
+            Ok(lang) => lang,
+            Err(_) => {
+                return Err(tool_error(format!(
+                    "Unknown sdk_lang '{}'. Use 'typescript' or 'python'.",
+                    l
+                )))
+            }
+        },
+        None => SdkLang::TypeScript,
+    };
     let skip_verify = skip_verify_arg(args);
 
     let output_dir = PathBuf::from(output.unwrap_or(project_name));
 
-    let options = match build_options(
-        template,
-        namespace,
-        project_name,
-        token_name,
-        asset_name,
-        time_lock,
-        cancellable,
-        partial_claim,
-        features_str,
-        datum,
-        redeemer,
-        purpose,
-    ) {
+    let options = match build_options_from_json(args) {
         Ok(opts) => opts,
-        Err(e) => return serde_json::json!({"error": e}).to_string(),
+        Err(e) => return Err(tool_error(e)),
     };
 
     let gen = match ProjectGenerator::new() {
         Ok(g) => g,
-        Err(e) => return serde_json::json!({"error": e.to_string()}).to_string(),
+        Err(e) => return Err(tool_error(e)),
     };
 
+    progress("Rendering templates");
     let result = match gen.render(&options) {
         Ok(r) => r,
-        Err(e) => return serde_json::json!({"error": e.to_string()}).to_string(),
+        Err(e) => return Err(tool_error(e)),
     };
+    let strict = args
+        .get("strict")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if let Err(e) = result.diagnostics.check_strict(strict) {
+        return Err(serde_json::json!({
+            "error": e.to_string(),
+            "warnings": result.diagnostics.warnings,
+        }));
+    }
 
     // Write files to disk
+    progress("Writing files");
     let mut written_paths = Vec::new();
     for file in &result.files {
         let full_path = output_dir.join(&file.path);
@@ -249,7 +273,7 @@ fn generate(args: &Value) -> String {
             let _ = fs::create_dir_all(parent);
         }
         if let Err(e) = fs::write(&full_path, &file.content) {
-            return serde_json::json!({"error": format!("Write failed: {}", e)}).to_string();
+            return Err(tool_error(format!("Write failed: {}", e)));
         }
         written_paths.push(file.path.clone());
     }
@@ -257,7 +281,7 @@ fn generate(args: &Value) -> String {
     // SDK generation
     let mut sdk_paths = Vec::new();
     if sdk {
-        if let Ok(sdk_result) = gen.render_sdk(&options) {
+        if let Ok(sdk_result) = gen.render_sdk_lang(&options, sdk_lang) {
             for file in &sdk_result.files {
                 let full_path = output_dir.join(&file.path);
                 if let Some(parent) = full_path.parent() {
@@ -273,156 +297,45 @@ fn generate(args: &Value) -> String {
     let verification = if skip_verify {
         serde_json::json!(null)
     } else {
-        match run_verification(&output_dir) {
+        match run_verification(&output_dir, progress) {
             Ok(v) => v,
             Err(e) => {
-                return serde_json::json!({
+                return Err(serde_json::json!({
                     "error": format!("verification failed: {}", e),
                     "template": options.template.slug(),
                     "output_dir": output_dir.display().to_string(),
                     "files": written_paths,
                     "sdk_files": sdk_paths,
-                })
-                .to_string()
+                }))
             }
         }
     };
 
-    serde_json::json!({
+    Ok(serde_json::json!({
         "template": options.template.slug(),
         "output_dir": output_dir.display().to_string(),
         "files": written_paths,
         "sdk_files": sdk_paths,
+        "warnings": result.diagnostics.warnings,
+        "meta": result.meta,
         "verification": verification,
     })
-    .to_string()
+    .to_string())
 }
 
-fn verify(path: &str) -> String {
+fn verify(path: &str, progress: &mut dyn FnMut(&str)) -> Result<String, Value> {
     let project_dir = PathBuf::from(path);
 
     if !project_dir.join("aiken.toml").exists() {
-        return serde_json::json!({"error": "No aiken.toml found"}).to_string();
+        return Err(tool_error("No aiken.toml found"));
     }
 
-    match run_verification(&project_dir) {
-        Ok(v) => serde_json::json!({ "ok": true, "verification": v }).to_string(),
-        Err(e) => serde_json::json!({
+    match run_verification(&project_dir, progress) {
+        Ok(v) => Ok(serde_json::json!({ "ok": true, "verification": v }).to_string()),
+        Err(e) => Err(serde_json::json!({
             "ok": false,
             "error": format!("verification failed: {}", e)
-        })
-        .to_string(),
-    }
-}
-
-#[allow(clippy::too_many_arguments)]
-fn build_options(
-    template: &str,
-    namespace: &str,
-    project_name: &str,
-    token_name: Option<&str>,
-    asset_name: Option<&str>,
-    time_lock: bool,
-    cancellable: bool,
-    partial_claim: bool,
-    features_str: Option<&str>,
-    datum: Option<&str>,
-    redeemer: Option<&str>,
-    purpose: &str,
-) -> Result<GenerateOptions, String> {
-    GenerateOptions::validate_namespace_and_project(namespace, project_name)?;
-
-    match template {
-        "mint" | "simple_mint" | "simple-mint" => {
-            let tn = token_name.unwrap_or(project_name);
-            let default_an = tn.to_uppercase().replace(' ', "_");
-            let an = asset_name.unwrap_or(&default_an);
-            Ok(GenerateOptions::simple_mint(
-                namespace,
-                project_name,
-                tn,
-                an,
-                time_lock,
-            ))
-        }
-        "vesting" => Ok(GenerateOptions::vesting(
-            namespace,
-            project_name,
-            cancellable,
-            partial_claim,
-        )),
-        "escrow" => Ok(GenerateOptions::escrow(namespace, project_name)),
-        "treasury" | "multisig_treasury" => {
-            Ok(GenerateOptions::multisig_treasury(namespace, project_name))
-        }
-        "marketplace" | "nft_marketplace" => {
-            Ok(GenerateOptions::nft_marketplace(namespace, project_name))
-        }
-        "staking" | "staking_pool" => Ok(GenerateOptions::staking_pool(namespace, project_name)),
-        "oracle" | "oracle_settlement" => {
-            Ok(GenerateOptions::oracle_settlement(namespace, project_name))
-        }
-        "referral" | "referral_system" => {
-            Ok(GenerateOptions::referral_system(namespace, project_name))
-        }
-        "dex" | "dex_pool" => Ok(GenerateOptions::dex_pool(namespace, project_name)),
-        "lending" | "lending_pool" => Ok(GenerateOptions::lending_pool(namespace, project_name)),
-        "governance" | "dao_governance" => {
-            Ok(GenerateOptions::dao_governance(namespace, project_name))
-        }
-        "streaming" | "streaming_payments" => {
-            Ok(GenerateOptions::streaming_payments(namespace, project_name))
-        }
-        "custom" => {
-            if purpose != "spend" && purpose != "mint" {
-                return Err(format!(
-                    "Invalid purpose '{}'. Must be 'spend' or 'mint'",
-                    purpose
-                ));
-            }
-
-            let feature_strs: Vec<String> = features_str
-                .unwrap_or("")
-                .split(',')
-                .filter(|s| !s.is_empty())
-                .map(|s| s.trim().to_string())
-                .collect();
-
-            let parsed = features::parse_features(&feature_strs).map_err(|e| e.to_string())?;
-            let resolved =
-                features::compose::resolve_features(&parsed, purpose).map_err(|e| e.to_string())?;
-            let names: Vec<String> = resolved.iter().map(|f| f.name().to_string()).collect();
-
-            let datum_fields = if let Some(d) = datum {
-                features::types::parse_datum_fields(d).map_err(|e| e.to_string())?
-            } else if purpose == "spend" {
-                return Err("datum is required for spend-purpose custom validators".to_string());
-            } else {
-                vec![]
-            };
-
-            let redeemer_actions = if let Some(r) = redeemer {
-                features::types::parse_redeemer_actions(r).map_err(|e| e.to_string())?
-            } else {
-                return Err("redeemer is required for custom validators".to_string());
-            };
-
-            features::types::validate_features_against_types(&resolved, &datum_fields, purpose)
-                .map_err(|e| e.to_string())?;
-
-            Ok(GenerateOptions::custom(
-                namespace,
-                project_name,
-                purpose,
-                datum_fields,
-                redeemer_actions,
-                names,
-            ))
-        }
-        _ => Err(format!(
-            "Unknown template '{}'. Use kaido_list_templates to see available.",
-            template
-        )),
+        })),
     }
 }
 
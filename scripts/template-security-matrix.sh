@@ -2,7 +2,7 @@
 set -euo pipefail
 
 ROOT="${1:-$(mktemp -d /tmp/kaido_security_matrix_XXXXXX)}"
-TEMPLATES=(mint vesting escrow treasury marketplace staking oracle referral dex lending governance streaming custom)
+TEMPLATES=(mint vesting escrow treasury marketplace staking oracle referral dex lending governance streaming htlc payroll faucet allowlist zk-gate token-lock dutch-auction oracle-feed custom)
 
 echo "workspace=$ROOT"
 
@@ -20,6 +20,14 @@ export interface FeatureInfo {
 export interface ValidationResult {
   valid: boolean
   errors: string[]
+  /** Non-fatal findings, e.g. actions no selected feature checks */
+  warnings?: Diagnostic[]
+}
+
+export interface Diagnostic {
+  code: string
+  message: string
+  location: string | null
 }
 
 const SDK_SUPPORTED_SLUGS = new Set([